@@ -0,0 +1,297 @@
+// Portable, chunked LZ4 container for asset files.
+//
+// Metal's `IOCompression` (MTLIO) files can only be written and read on macOS, so assets compressed
+// with this container can be produced, inspected and verified on any platform (see
+// `cube_texture::Compression`).
+//
+// Format (version 1, all integers little-endian):
+//
+//   offset  size             field
+//   0       4                magic, "UOLZ"
+//   4       4                version, 1
+//   8       4                chunk size, uncompressed bytes per chunk (the last chunk may be smaller)
+//   12      4                chunk count, uncompressed size / chunk size (rounded up)
+//   16      8                uncompressed size
+//   24      4 * chunk count  compressed size of each chunk
+//   ...                      chunks, back to back
+//
+// Like MTLIO's chunked files, each chunk is compressed independently (LZ4 block format, no frame
+// headers or checksums), so chunks can be decoded in any order or in parallel. A chunk whose compressed
+// size equals its uncompressed size is stored uncompressed (incompressible data).
+use std::{fmt::Display, path::Path};
+
+pub const MAGIC: [u8; 4] = *b"UOLZ";
+pub const VERSION: u32 = 1;
+// Same as MTLIO's default chunk size (`IOCompression::default_chunk_size()`).
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+const HEADER_SIZE: usize = 24;
+const CHUNK_TABLE_ENTRY_SIZE: usize = 4;
+// LZ4 produces at most 255 bytes per compressed byte (each additional match length byte adds 255),
+// bounding how large a chunk can decompress to.
+const MAX_LZ4_RATIO: usize = 255;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ContainerError {
+    // Not enough bytes for the header or the chunk table.
+    Truncated,
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    // Chunk size, chunk count, and uncompressed size do not agree.
+    InvalidHeader,
+    // Chunk sizes in the chunk table do not add up to the remaining bytes.
+    InvalidChunkTable,
+    // Chunk (index) could not be decompressed, or decompressed to the wrong size.
+    CorruptChunk(usize),
+}
+
+impl Display for ContainerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerError::Truncated => write!(f, "container is truncated"),
+            ContainerError::InvalidMagic => write!(f, "not a container (invalid magic)"),
+            ContainerError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported container version {version} (expected {VERSION})"
+            ),
+            ContainerError::InvalidHeader => write!(f, "container header is invalid"),
+            ContainerError::InvalidChunkTable => write!(f, "container chunk table is invalid"),
+            ContainerError::CorruptChunk(chunk) => write!(f, "container chunk {chunk} is corrupt"),
+        }
+    }
+}
+
+impl std::error::Error for ContainerError {}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+#[inline]
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+pub fn compress(bytes: &[u8], chunk_size: usize) -> Vec<u8> {
+    assert!(
+        chunk_size > 0 && chunk_size <= u32::MAX as usize,
+        "Invalid container chunk size"
+    );
+    let chunks: Vec<Vec<u8>> = bytes
+        .chunks(chunk_size)
+        .map(|chunk| {
+            let compressed = lz4_flex::block::compress(chunk);
+            if compressed.len() < chunk.len() {
+                compressed
+            } else {
+                chunk.to_vec()
+            }
+        })
+        .collect();
+    let mut container = Vec::with_capacity(
+        HEADER_SIZE
+            + chunks.len() * CHUNK_TABLE_ENTRY_SIZE
+            + chunks.iter().map(|c| c.len()).sum::<usize>(),
+    );
+    container.extend(MAGIC);
+    container.extend(VERSION.to_le_bytes());
+    container.extend((chunk_size as u32).to_le_bytes());
+    container.extend((chunks.len() as u32).to_le_bytes());
+    container.extend((bytes.len() as u64).to_le_bytes());
+    for chunk in &chunks {
+        container.extend((chunk.len() as u32).to_le_bytes());
+    }
+    for chunk in &chunks {
+        container.extend(chunk);
+    }
+    container
+}
+
+pub fn decompress(container: &[u8]) -> Result<Vec<u8>, ContainerError> {
+    if container.len() < HEADER_SIZE {
+        return Err(ContainerError::Truncated);
+    }
+    if container[0..4] != MAGIC {
+        return Err(ContainerError::InvalidMagic);
+    }
+    let version = read_u32(container, 4);
+    if version != VERSION {
+        return Err(ContainerError::UnsupportedVersion(version));
+    }
+    let chunk_size = read_u32(container, 8) as usize;
+    let chunk_count = read_u32(container, 12) as usize;
+    let uncompressed_size = read_u64(container, 16);
+    // Also rejects sizes that would overflow rounding up to a whole number of chunks.
+    if chunk_size == 0 || uncompressed_size > (usize::MAX - chunk_size) as u64 {
+        return Err(ContainerError::InvalidHeader);
+    }
+    let uncompressed_size = uncompressed_size as usize;
+    let expected_chunk_count = (uncompressed_size + chunk_size - 1) / chunk_size;
+    if chunk_count != expected_chunk_count {
+        return Err(ContainerError::InvalidHeader);
+    }
+
+    let chunks_offset = HEADER_SIZE + chunk_count * CHUNK_TABLE_ENTRY_SIZE;
+    if container.len() < chunks_offset {
+        return Err(ContainerError::Truncated);
+    }
+    let compressed_sizes: Vec<usize> = (0..chunk_count)
+        .map(|chunk| read_u32(container, HEADER_SIZE + chunk * CHUNK_TABLE_ENTRY_SIZE) as usize)
+        .collect();
+    if chunks_offset + compressed_sizes.iter().sum::<usize>() != container.len() {
+        return Err(ContainerError::InvalidChunkTable);
+    }
+    let expected_size = |chunk: usize| chunk_size.min(uncompressed_size - chunk * chunk_size);
+    // The uncompressed size is untrusted, verify every chunk could decompress to its expected size
+    // before allocating.
+    if compressed_sizes
+        .iter()
+        .enumerate()
+        .any(|(chunk, &size)| expected_size(chunk) > size.saturating_mul(MAX_LZ4_RATIO))
+    {
+        return Err(ContainerError::InvalidChunkTable);
+    }
+
+    let mut bytes = Vec::with_capacity(uncompressed_size);
+    let mut offset = chunks_offset;
+    for (chunk, compressed_size) in compressed_sizes.into_iter().enumerate() {
+        let expected_size = expected_size(chunk);
+        let compressed = &container[offset..offset + compressed_size];
+        if compressed_size == expected_size {
+            bytes.extend(compressed);
+        } else {
+            match lz4_flex::block::decompress(compressed, expected_size) {
+                Ok(chunk_bytes) if chunk_bytes.len() == expected_size => bytes.extend(chunk_bytes),
+                _ => return Err(ContainerError::CorruptChunk(chunk)),
+            }
+        }
+        offset += compressed_size;
+    }
+    Ok(bytes)
+}
+
+pub fn write_file<P: AsRef<Path>>(dest_file: P, bytes: &[u8]) {
+    std::fs::write(&dest_file, compress(bytes, DEFAULT_CHUNK_SIZE)).unwrap_or_else(|error| {
+        panic!(
+            "Failed to write compressed file ({:?}): {error}",
+            dest_file.as_ref()
+        )
+    });
+}
+
+pub fn read_file<P: AsRef<Path>>(src_file: P) -> Vec<u8> {
+    let container = std::fs::read(&src_file).unwrap_or_else(|error| {
+        panic!(
+            "Failed to read compressed file ({:?}): {error}",
+            src_file.as_ref()
+        )
+    });
+    decompress(&container).unwrap_or_else(|error| {
+        panic!(
+            "Failed to decompress compressed file ({:?}): {error}",
+            src_file.as_ref()
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Deterministic, incompressible bytes (xorshift).
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x2545f4914f6cdd1du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut compressible = vec![];
+        for i in 0..100_000u32 {
+            compressible.extend([(i / 1000) as u8, 0, 0, 255]);
+        }
+        for (bytes, chunk_size) in [
+            (vec![], DEFAULT_CHUNK_SIZE),
+            (vec![7], DEFAULT_CHUNK_SIZE),
+            (compressible.clone(), DEFAULT_CHUNK_SIZE),
+            (compressible.clone(), 1000),
+            (noise(10_000), 4096),
+        ] {
+            let container = compress(&bytes, chunk_size);
+            assert_eq!(decompress(&container), Ok(bytes));
+        }
+        assert!(compress(&compressible, DEFAULT_CHUNK_SIZE).len() < compressible.len() / 10);
+
+        // Best case compression stays within `MAX_LZ4_RATIO`.
+        let zeros = vec![0; 1 << 20];
+        let container = compress(&zeros, zeros.len());
+        assert!(
+            container.len() - HEADER_SIZE - CHUNK_TABLE_ENTRY_SIZE > zeros.len() / MAX_LZ4_RATIO
+        );
+        assert_eq!(decompress(&container), Ok(zeros));
+    }
+
+    #[test]
+    fn test_header() {
+        let container = compress(&noise(10), 4);
+        #[rustfmt::skip]
+        assert_eq!(&container[..HEADER_SIZE + 12], [
+            b'U', b'O', b'L', b'Z',
+            1, 0, 0, 0,
+            4, 0, 0, 0,
+            3, 0, 0, 0,
+            10, 0, 0, 0, 0, 0, 0, 0,
+            // Incompressible chunks are stored uncompressed
+            4, 0, 0, 0,
+            4, 0, 0, 0,
+            2, 0, 0, 0,
+        ]);
+        assert_eq!(&container[HEADER_SIZE + 12..], noise(10));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let bytes = vec![1; 1000];
+        let container = compress(&bytes, 256);
+        assert_eq!(decompress(&[]), Err(ContainerError::Truncated));
+        assert_eq!(
+            decompress(&container[..HEADER_SIZE + 4]),
+            Err(ContainerError::Truncated)
+        );
+        assert_eq!(
+            decompress(&container[..container.len() - 1]),
+            Err(ContainerError::InvalidChunkTable)
+        );
+
+        let corrupt = |offset: usize, value: u8| {
+            let mut container = container.clone();
+            container[offset] = value;
+            decompress(&container)
+        };
+        assert_eq!(corrupt(0, b'X'), Err(ContainerError::InvalidMagic));
+        assert_eq!(corrupt(4, 2), Err(ContainerError::UnsupportedVersion(2)));
+        assert_eq!(corrupt(12, 5), Err(ContainerError::InvalidHeader));
+        // Uncompressed size (and chunk size) larger than the chunks could decompress to.
+        let mut huge = container.clone();
+        huge[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        huge[12..16].copy_from_slice(&1u32.to_le_bytes());
+        huge[16..24].copy_from_slice(&(u32::MAX as u64).to_le_bytes());
+        let chunks = &container[HEADER_SIZE + 4 * CHUNK_TABLE_ENTRY_SIZE..];
+        huge.truncate(HEADER_SIZE);
+        huge.extend((chunks.len() as u32).to_le_bytes());
+        huge.extend(chunks);
+        assert_eq!(decompress(&huge), Err(ContainerError::InvalidChunkTable));
+        // First chunk's data: LZ4 token with a literal length longer than the chunk.
+        assert_eq!(
+            corrupt(HEADER_SIZE + 4 * CHUNK_TABLE_ENTRY_SIZE, 0xf0),
+            Err(ContainerError::CorruptChunk(0))
+        );
+    }
+}
@@ -1,171 +1,155 @@
+#[cfg(feature = "metal")]
+use crate::texture_asset::{asset_level_loads, load_levels};
+use crate::{
+    equirectangular::{equirectangular_to_cube_faces, Image},
+    metadata::{AssetKind, Compression, Metadata, PixelFormat, CUBE_FACE_ORDER},
+    mipmap::{mip_level_count, MipmapOptions},
+    texture_asset::{
+        load_image_from_png, read_slice_level_bytes, read_slice_levels, write_slice_levels,
+    },
+};
+#[cfg(feature = "metal")]
 use metal::*;
-use std::{mem::MaybeUninit, path::Path};
+use std::path::Path;
 
-const BYTES_PER_PIXELS: u64 = 4; // RGBA
-const COMPRESSION_METHOD: MTLIOCompressionMethod = MTLIOCompressionMethod::lz4;
-const CUBE_ASSET_DIR_FILENAMES: [&'static str; 6] =
-    ["posx", "negx", "posy", "negy", "posz", "negz"];
-const CUBE_ASSET_DIR_METADATA: &'static str = "metadata.info";
-const CUBE_TEXTURE_DEPTH: u64 = 1;
-const SUPPORTED_PIXEL_FORMAT: MTLPixelFormat = MTLPixelFormat::RGBA8Unorm;
-const SUPPORTED_INPUT_PIXEL_FORMAT: png::ColorType = png::ColorType::Rgba;
-
-fn load_image_bytes_from_png<P: AsRef<Path>>(image_path: P) -> (Vec<u8>, (usize, usize)) {
-    let decoder = png::Decoder::new(std::fs::File::open(&image_path).expect(&format!(
-        "Could not open input PNG file ({:?}).",
-        image_path.as_ref()
-    )));
-    let mut reader = decoder
-        .read_info()
-        .expect("Failed to decode PNG information");
-    let info = reader.info();
-    assert_eq!(
-        info.color_type, SUPPORTED_INPUT_PIXEL_FORMAT,
-        "Invalid cube texture PNG color format. Must be RGBA."
-    );
-    let (width, height) = (info.width, info.height);
-    let mut buf = vec![0; reader.output_buffer_size()];
-    reader.next_frame(&mut buf).expect(&format!(
-        "Failed to load image data ({:?}) into buffer.",
-        image_path.as_ref()
-    ));
-    (buf, (width as _, height as _))
-}
+const SUPPORTED_PIXEL_FORMAT: PixelFormat = PixelFormat::Rgba8Unorm;
 
 pub fn create_cube_texture_asset_dir<P: AsRef<Path> + Send, P2: AsRef<Path> + Send>(
     target_dir: P,
     cube_face_files: &[P2; 6],
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
+) {
+    let faces = std::thread::scope(|s| {
+        let faces = [0, 1, 2, 3, 4, 5].map(|face| {
+            let src_file = cube_face_files[face].as_ref();
+            s.spawn(move || load_image_from_png(src_file))
+        });
+        faces.map(|f| f.join().expect("Failed to load cube face texture"))
+    });
+    create_cube_texture_asset_dir_from_faces(target_dir, &faces, mipmaps, compression);
+}
+
+// Same as `create_cube_texture_asset_dir()`, but from RGBA faces already in memory (ordered +X, -X,
+// +Y, -Y, +Z, -Z).
+//
+// If `mipmaps` is specified, a full mip chain is generated for each face (see
+// `mipmap::generate_mip_chain()`), otherwise only a single level is stored.
+pub fn create_cube_texture_asset_dir_from_faces<P: AsRef<Path>>(
+    target_dir: P,
+    faces: &[Image<u8>; 6],
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
 ) {
-    let mut all_face_sizes: [std::mem::MaybeUninit<(usize, usize)>; 6] =
-        std::mem::MaybeUninit::uninit_array();
+    let (width, height) = (faces[0].width, faces[0].height);
+    assert!(
+        width > 0
+            && height > 0
+            && faces
+                .iter()
+                .all(|face| (face.width, face.height) == (width, height)),
+        "Width and Height of each cube face texture must be the same"
+    );
+    let mip_level_count = if mipmaps.is_some() {
+        mip_level_count(width, height)
+    } else {
+        1
+    };
+    assert!(
+        width <= u32::MAX as usize && height <= u32::MAX as usize,
+        "Cube face texture is too large"
+    );
+
+    let target_dir = target_dir.as_ref();
     std::thread::scope(|s| {
-        for ((src_file, dest_file), face_size) in cube_face_files
-            .iter()
-            .map(|s| s.as_ref())
-            .zip(
-                CUBE_ASSET_DIR_FILENAMES
-                    .iter()
-                    .map(|a| target_dir.as_ref().join(a)),
-            )
-            .zip(&mut all_face_sizes)
-        {
+        for (face, face_name) in faces.iter().zip(CUBE_FACE_ORDER) {
             s.spawn(move || {
-                let (img_buf, size) = load_image_bytes_from_png(src_file);
-                face_size.write(size);
-
-                let io = IOCompression::new(
-                    &dest_file.to_string_lossy(),
-                    COMPRESSION_METHOD,
-                    IOCompression::default_chunk_size(),
-                );
-                io.append(img_buf.as_ptr() as _, img_buf.len() as _);
-                let io_flush_result = io.flush();
-                assert_eq!(
-                    io_flush_result,
-                    MTLIOCompressionStatus::complete,
-                    "Failed to write compressed file"
-                );
+                write_slice_levels(target_dir, face_name.name(), face, mipmaps, compression)
             });
         }
     });
-
-    // Verify all faces have the same size
-    let mut cur_width = 0;
-    let mut cur_height = 0;
-    for (width, height) in unsafe { MaybeUninit::array_assume_init(all_face_sizes) } {
-        assert!(
-            ((cur_width == 0 && width > 0) || (cur_width == width))
-                && ((cur_height == 0 && height > 0) || (cur_height == height)),
-            "Width and Height of each cube face texture must be the same"
-        );
-        cur_width = width;
-        cur_height = height;
-    }
-    let metadata = Metadata {
-        width: cur_width as _,
-        height: cur_height as _,
+    Metadata {
+        kind: AssetKind::CubeTexture,
         pixel_format: SUPPORTED_PIXEL_FORMAT,
-    };
-    let metadata_ptr = &metadata as *const Metadata;
-    let metadata_raw_bytes = unsafe {
-        std::slice::from_raw_parts(metadata_ptr as *const u8, std::mem::size_of::<Metadata>())
-    };
-    std::fs::write(
-        target_dir.as_ref().join(CUBE_ASSET_DIR_METADATA),
-        metadata_raw_bytes,
-    )
-    .expect("Failed to write cube asset");
+        compression,
+        width: width as _,
+        height: height as _,
+        mip_level_count: mip_level_count as _,
+        faces: CUBE_FACE_ORDER.to_vec(),
+    }
+    .write(target_dir);
 }
 
-fn encode_load_cube_face_texture<P: AsRef<Path>>(
-    device: &Device,
-    command_buffer: &IOCommandBufferRef,
-    cube_texture: &TextureRef,
-    face: usize,
-    width: u32,
-    height: u32,
-    cube_asset_face_file: P,
+// Creates a cube texture asset from an equirectangular panorama (RGBA PNG), sampling each face at
+// `face_size` x `face_size` (see `equirectangular::equirectangular_to_cube_faces()`).
+pub fn create_cube_texture_asset_dir_from_equirectangular<P: AsRef<Path>, P2: AsRef<Path>>(
+    target_dir: P,
+    equirectangular_file: P2,
+    face_size: usize,
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
 ) {
-    let handle = device
-        .new_io_handle(
-            URL::new_with_string(&format!(
-                "file:///{}",
-                cube_asset_face_file.as_ref().to_string_lossy()
-            )),
-            COMPRESSION_METHOD,
-        )
-        .expect("Failed to get IO file handle");
-    let (width, height) = (width as u64, height as u64);
-    let source_bytes_per_row = width * BYTES_PER_PIXELS;
-    command_buffer.load_texture(
-        cube_texture,
-        face as _,
-        0,
-        MTLSize {
-            width,
-            height,
-            depth: CUBE_TEXTURE_DEPTH,
-        },
-        source_bytes_per_row,
-        height * source_bytes_per_row,
-        MTLOrigin { x: 0, y: 0, z: 0 },
-        &handle,
-        0,
-    );
+    let faces =
+        equirectangular_to_cube_faces(&load_image_from_png(equirectangular_file), face_size);
+    create_cube_texture_asset_dir_from_faces(target_dir, &faces, mipmaps, compression);
 }
 
-#[repr(C)]
-struct Metadata {
-    width: u32,
-    height: u32,
-    pixel_format: MTLPixelFormat,
+// Reads a cube texture asset's faces (ordered +X, -X, +Y, -Y, +Z, -Z), each with all of its mip levels
+// (ex. to inspect or verify assets, or as the source of other assets).
+//
+// Assets compressed with `Compression::MetalIoLz4` can only be read with the `metal` feature.
+pub fn read_cube_texture_asset_dir<P: AsRef<Path>>(cube_asset_dir: P) -> [Vec<Image<u8>>; 6] {
+    let cube_asset_dir = cube_asset_dir.as_ref();
+    let metadata = Metadata::read(cube_asset_dir, AssetKind::CubeTexture);
+    CUBE_FACE_ORDER.map(|face| read_slice_levels(cube_asset_dir, face.name(), &metadata))
 }
 
-pub fn load_cube_texture_asset_dir<P: AsRef<Path>>(device: &Device, cube_asset_dir: P) -> Texture {
-    let metadata_file = cube_asset_dir.as_ref().join(CUBE_ASSET_DIR_METADATA);
-    let metadata_raw =
-        std::fs::read(&metadata_file).expect("Failed to find/read cube asset's metadata file");
+// Same as `read_cube_texture_asset_dir()`, but for any kind of cube asset (see
+// `AssetKind::is_cube()`), with each level's raw bytes (in `pixel_format`).
+pub(crate) fn read_cube_asset_dir_level_bytes(
+    cube_asset_dir: &Path,
+    kind: AssetKind,
+    pixel_format: PixelFormat,
+) -> (Metadata, [Vec<Vec<u8>>; 6]) {
+    let metadata = Metadata::read(cube_asset_dir, kind);
     assert_eq!(
-        metadata_raw.len(),
-        std::mem::size_of::<Metadata>(),
-        "Cube asset's metadata file is invalid (size)"
+        metadata.pixel_format, pixel_format,
+        "Unsupported cube asset's pixel format"
     );
-    let &Metadata {
-        width,
-        height,
-        pixel_format,
-    } = unsafe { &*(metadata_raw.as_ptr() as *const Metadata) };
+    let faces =
+        CUBE_FACE_ORDER.map(|face| read_slice_level_bytes(cube_asset_dir, face.name(), &metadata));
+    (metadata, faces)
+}
+
+#[cfg(feature = "metal")]
+pub fn load_cube_texture_asset_dir<P: AsRef<Path>>(device: &Device, cube_asset_dir: P) -> Texture {
+    load_cube_asset_dir(
+        device,
+        cube_asset_dir.as_ref(),
+        AssetKind::CubeTexture,
+        SUPPORTED_PIXEL_FORMAT,
+        "Cube Texture",
+    )
+}
+
+// Same as `load_cube_texture_asset_dir()`, but for any kind of cube asset (see `AssetKind::is_cube()`).
+#[cfg(feature = "metal")]
+pub(crate) fn load_cube_asset_dir(
+    device: &Device,
+    cube_asset_dir: &Path,
+    kind: AssetKind,
+    pixel_format: PixelFormat,
+    label: &str,
+) -> Texture {
+    let metadata = Metadata::read(cube_asset_dir, kind);
     assert_eq!(
-        pixel_format, SUPPORTED_PIXEL_FORMAT,
+        metadata.pixel_format, pixel_format,
         "Unsupported cube asset's pixel format"
     );
 
-    let queue = device
-        .new_io_command_queue(&IOCommandQueueDescriptor::new())
-        .expect("Failed to create IO Command Queue");
     let cube_texture = {
         let desc = TextureDescriptor::new();
-        desc.set_pixel_format(pixel_format);
+        desc.set_pixel_format(metadata.pixel_format.mtl_pixel_format());
         desc.set_texture_type(MTLTextureType::Cube);
         #[cfg(test)]
         let opts = MTLResourceOptions::StorageModeShared;
@@ -173,30 +157,20 @@ pub fn load_cube_texture_asset_dir<P: AsRef<Path>>(device: &Device, cube_asset_d
         let opts = MTLResourceOptions::StorageModePrivate;
         desc.set_resource_options(opts);
         desc.set_usage(MTLTextureUsage::ShaderRead);
-        desc.set_width(width as _);
-        desc.set_height(height as _);
+        desc.set_width(metadata.width as _);
+        desc.set_height(metadata.height as _);
+        desc.set_mipmap_level_count(metadata.mip_level_count as _);
         device.new_texture(&desc)
     };
-    cube_texture.set_label("Cube Texture");
-
-    let command_buffer = queue.new_command_buffer();
-    for (face, face_file) in CUBE_ASSET_DIR_FILENAMES.iter().enumerate() {
-        encode_load_cube_face_texture(
-            &device,
-            &command_buffer,
+    cube_texture.set_label(label);
+    load_levels(
+        device,
+        &asset_level_loads(
             &cube_texture,
-            face,
-            width,
-            height,
-            cube_asset_dir.as_ref().join(face_file),
-        );
-    }
-    command_buffer.commit();
-    command_buffer.wait_until_completed();
-    assert_eq!(
-        command_buffer.status(),
-        MTLIOStatus::complete,
-        "Failed to load texture for face."
+            cube_asset_dir,
+            &metadata,
+            &CUBE_FACE_ORDER.map(|face| face.name()),
+        ),
     );
     cube_texture
 }
@@ -204,133 +178,187 @@ pub fn load_cube_texture_asset_dir<P: AsRef<Path>>(device: &Device, cube_asset_d
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::PathBuf;
-    use std::time::Instant;
+    use crate::{metadata::METADATA_FILENAME, mipmap::generate_mip_chain, test_util::temp_dir};
 
-    const MICROS_PER_MILLI: u128 = 1000;
-    const TEST_CUBE_TEXTURE_WIDTH: usize = 2048;
-    const TEST_CUBE_TEXTURE_HEIGHT: usize = TEST_CUBE_TEXTURE_WIDTH;
-    const TEST_CUBE_TEXTURE_BYTES_PER_IMAGE: usize =
-        TEST_CUBE_TEXTURE_WIDTH * TEST_CUBE_TEXTURE_HEIGHT * (BYTES_PER_PIXELS as usize);
-    const TEST_CUBE_TEXTURES: [&'static str; 6] = [
-        "cubemap_posx.png",
-        "cubemap_negx.png",
-        "cubemap_posy.png",
-        "cubemap_negy.png",
-        "cubemap_posz.png",
-        "cubemap_negz.png",
-    ];
+    #[test]
+    fn test_read_portable() {
+        let asset_dir = temp_dir("read-portable");
+        let faces = [0, 1, 2, 3, 4, 5].map(|face| {
+            let data = (0..8 * 4)
+                .flat_map(|i| [face as u8 * 40, i as u8 * 8, 0, 255])
+                .collect();
+            Image::new(8, 4, data)
+        });
+        let mipmaps = MipmapOptions::default();
+        create_cube_texture_asset_dir_from_faces(
+            &asset_dir,
+            &faces,
+            Some(mipmaps),
+            Compression::PortableLz4,
+        );
 
-    fn debug_time<T>(label: &'static str, f: impl FnOnce() -> T) -> T {
-        #[cfg(debug_assertions)]
-        {
-            let now = Instant::now();
-            let r = f();
-            let elapsed = now.elapsed();
-            let elapsed_micro = elapsed.as_micros();
-            let (elapsed_display, unit) = if elapsed_micro > MICROS_PER_MILLI {
-                (elapsed_micro / MICROS_PER_MILLI, "ms")
-            } else {
-                (elapsed_micro, "μ")
-            };
-            println!("[{label:<40}] {:>6} {}", elapsed_display, unit);
-            return r;
-        }
-        #[cfg(not(debug_assertions))]
-        {
-            return f();
+        let actual = read_cube_texture_asset_dir(&asset_dir);
+        for (face, actual) in faces.iter().zip(actual) {
+            let expected = generate_mip_chain(face, mipmaps);
+            assert_eq!(actual.len(), 4);
+            for (expected, actual) in expected.iter().zip(actual) {
+                assert_eq!(
+                    (actual.width, actual.height, &actual.data),
+                    (expected.width, expected.height, &expected.data)
+                );
+            }
         }
+        std::fs::remove_dir_all(&asset_dir).expect("Failed to remove temp asset directory");
     }
 
     #[test]
-    fn test() {
-        let asset_dir_name = std::time::SystemTime::now()
-            .duration_since(std::time::SystemTime::UNIX_EPOCH)
-            .expect("Failed to get epoch time (for temp asset directory)")
-            .as_millis()
-            .to_string();
-        let asset_dir = std::env::temp_dir().join(asset_dir_name);
-        std::fs::create_dir(&asset_dir).expect("Failed to create temp asset directory");
-        let cube_textures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("test-assets")
-            .join("cube-textures");
-        let test_cube_texture_files = TEST_CUBE_TEXTURES.map(|f| cube_textures_dir.join(f));
-        debug_time("Create Asset", || {
-            create_cube_texture_asset_dir(&asset_dir, &test_cube_texture_files)
-        });
-        let device = Device::system_default().expect("Failed to access a Metal Device");
-        let texture = debug_time("Load Asset", || {
-            load_cube_texture_asset_dir(&device, &asset_dir)
-        });
+    #[should_panic(expected = "created by an older asset compiler")]
+    fn test_read_unversioned_metadata() {
+        let asset_dir = temp_dir("unversioned-metadata");
+        // Unversioned metadata (raw `#[repr(C)]` struct, starting with the width and height)
+        let metadata = [2048u32, 2048, 70, 0, 1, 0].map(u32::to_le_bytes).concat();
+        std::fs::write(asset_dir.join(METADATA_FILENAME), metadata).unwrap();
+        read_cube_texture_asset_dir(&asset_dir);
+    }
 
-        assert_eq!(texture.width(), TEST_CUBE_TEXTURE_WIDTH as _);
-        assert_eq!(texture.height(), TEST_CUBE_TEXTURE_HEIGHT as _);
+    #[cfg(feature = "metal")]
+    mod metal_loader {
+        use super::*;
+        use std::{path::PathBuf, time::Instant};
 
-        debug_time("Verified Cube Texture Faces", || {
-            std::thread::scope(|s| {
-                // As we are only reading from the Metal Cube Texture (slice for each thread), this
-                // should be a thread-safe operation.
-                #[derive(Clone, Copy)]
-                struct SendTexture<'a>(&'a TextureRef);
-                impl<'a> SendTexture<'a> {
-                    #[inline]
-                    pub fn get_bytes_in_slice(
-                        &self,
-                        bytes: *mut std::ffi::c_void,
-                        stride: NSUInteger,
-                        image_stride: NSUInteger,
-                        region: MTLRegion,
-                        mipmap_level: NSUInteger,
-                        slice: NSUInteger,
-                    ) {
-                        self.0.get_bytes_in_slice(
-                            bytes,
-                            stride,
-                            image_stride,
-                            region,
-                            mipmap_level,
-                            slice,
-                        )
-                    }
-                    #[inline]
-                    pub fn size(&self) -> MTLSize {
-                        MTLSize {
-                            width: self.0.width(),
-                            height: self.0.height(),
-                            depth: self.0.depth(),
+        const BYTES_PER_PIXELS: u64 = SUPPORTED_PIXEL_FORMAT.bytes_per_pixel() as _;
+
+        const MICROS_PER_MILLI: u128 = 1000;
+        const TEST_CUBE_TEXTURE_WIDTH: usize = 2048;
+        const TEST_CUBE_TEXTURE_HEIGHT: usize = TEST_CUBE_TEXTURE_WIDTH;
+        const TEST_CUBE_TEXTURE_BYTES_PER_IMAGE: usize =
+            TEST_CUBE_TEXTURE_WIDTH * TEST_CUBE_TEXTURE_HEIGHT * (BYTES_PER_PIXELS as usize);
+        const TEST_CUBE_TEXTURES: [&'static str; 6] = [
+            "cubemap_posx.png",
+            "cubemap_negx.png",
+            "cubemap_posy.png",
+            "cubemap_negy.png",
+            "cubemap_posz.png",
+            "cubemap_negz.png",
+        ];
+
+        fn debug_time<T>(label: &'static str, f: impl FnOnce() -> T) -> T {
+            #[cfg(debug_assertions)]
+            {
+                let now = Instant::now();
+                let r = f();
+                let elapsed = now.elapsed();
+                let elapsed_micro = elapsed.as_micros();
+                let (elapsed_display, unit) = if elapsed_micro > MICROS_PER_MILLI {
+                    (elapsed_micro / MICROS_PER_MILLI, "ms")
+                } else {
+                    (elapsed_micro, "μ")
+                };
+                println!("[{label:<40}] {:>6} {}", elapsed_display, unit);
+                return r;
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                return f();
+            }
+        }
+
+        fn test_create_and_load(compression: Compression) {
+            let asset_dir = temp_dir(&format!("{compression:?}"));
+            let cube_textures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("test-assets")
+                .join("cube-textures");
+            let test_cube_texture_files = TEST_CUBE_TEXTURES.map(|f| cube_textures_dir.join(f));
+            debug_time("Create Asset", || {
+                create_cube_texture_asset_dir(
+                    &asset_dir,
+                    &test_cube_texture_files,
+                    Some(MipmapOptions::default()),
+                    compression,
+                )
+            });
+            let device = Device::system_default().expect("Failed to access a Metal Device");
+            let texture = debug_time("Load Asset", || {
+                load_cube_texture_asset_dir(&device, &asset_dir)
+            });
+
+            assert_eq!(texture.width(), TEST_CUBE_TEXTURE_WIDTH as _);
+            assert_eq!(texture.height(), TEST_CUBE_TEXTURE_HEIGHT as _);
+            assert_eq!(
+                texture.mipmap_level_count(),
+                mip_level_count(TEST_CUBE_TEXTURE_WIDTH, TEST_CUBE_TEXTURE_HEIGHT) as _
+            );
+
+            debug_time("Verified Cube Texture Faces", || {
+                std::thread::scope(|s| {
+                    // As we are only reading from the Metal Cube Texture (slice for each thread), this
+                    // should be a thread-safe operation.
+                    #[derive(Clone, Copy)]
+                    struct SendTexture<'a>(&'a TextureRef);
+                    impl<'a> SendTexture<'a> {
+                        #[inline]
+                        pub fn get_bytes_in_slice(
+                            &self,
+                            bytes: *mut std::ffi::c_void,
+                            stride: NSUInteger,
+                            image_stride: NSUInteger,
+                            region: MTLRegion,
+                            mipmap_level: NSUInteger,
+                            slice: NSUInteger,
+                        ) {
+                            self.0.get_bytes_in_slice(
+                                bytes,
+                                stride,
+                                image_stride,
+                                region,
+                                mipmap_level,
+                                slice,
+                            )
+                        }
+                        #[inline]
+                        pub fn size(&self) -> MTLSize {
+                            MTLSize {
+                                width: self.0.width(),
+                                height: self.0.height(),
+                                depth: self.0.depth(),
+                            }
                         }
                     }
-                }
-                unsafe impl<'a> Send for SendTexture<'a> {}
+                    unsafe impl<'a> Send for SendTexture<'a> {}
 
-                for (face, face_file) in test_cube_texture_files.iter().enumerate() {
-                    let texture_ref = SendTexture(&texture);
-                    s.spawn(move || {
-                        let mut actual_texture_bytes = vec![0; TEST_CUBE_TEXTURE_BYTES_PER_IMAGE];
-                        texture_ref.get_bytes_in_slice(
-                            actual_texture_bytes.as_mut_ptr() as _,
-                            TEST_CUBE_TEXTURE_WIDTH as u64 * BYTES_PER_PIXELS as u64,
-                            TEST_CUBE_TEXTURE_BYTES_PER_IMAGE as u64 * BYTES_PER_PIXELS as u64,
-                            MTLRegion {
-                                origin: MTLOrigin { x: 0, y: 0, z: 0 },
-                                size: texture_ref.size(),
-                            },
-                            0,
-                            face as _,
-                        );
-                        let (expected_texture_bytes, ..) = load_image_bytes_from_png(face_file);
-
-                        if &actual_texture_bytes != &expected_texture_bytes {
-                            println!(
-                                "Cube texture face #{} contents are incorrect: {:?} {:?}",
-                                face,
-                                &actual_texture_bytes[0..4],
-                                &expected_texture_bytes[0..4],
+                    for (face, face_file) in test_cube_texture_files.iter().enumerate() {
+                        let texture_ref = SendTexture(&texture);
+                        s.spawn(move || {
+                            let mut actual_texture_bytes =
+                                vec![0; TEST_CUBE_TEXTURE_BYTES_PER_IMAGE];
+                            texture_ref.get_bytes_in_slice(
+                                actual_texture_bytes.as_mut_ptr() as _,
+                                TEST_CUBE_TEXTURE_WIDTH as u64 * BYTES_PER_PIXELS as u64,
+                                TEST_CUBE_TEXTURE_BYTES_PER_IMAGE as u64 * BYTES_PER_PIXELS as u64,
+                                MTLRegion {
+                                    origin: MTLOrigin { x: 0, y: 0, z: 0 },
+                                    size: texture_ref.size(),
+                                },
+                                0,
+                                face as _,
                             );
-                        }
-                    });
-                }
-            });
-        })
+                            let expected_texture_bytes = load_image_from_png(face_file).data;
+
+                            assert_eq!(actual_texture_bytes, expected_texture_bytes, "face #{face}");
+                        });
+                    }
+                });
+            })
+        }
+
+        #[test]
+        fn test() {
+            test_create_and_load(Compression::MetalIoLz4);
+        }
+
+        #[test]
+        fn test_portable() {
+            test_create_and_load(Compression::PortableLz4);
+        }
     }
 }
@@ -0,0 +1,352 @@
+// Converts equirectangular (latitude/longitude) panoramas into cube texture faces.
+//
+// Cube faces follow Metal's cube texture convention (same as D3D and OpenGL, with the first row of
+// each face at the top):
+// - Faces are ordered +X, -X, +Y, -Y, +Z, -Z.
+// - Looking down +Z (+X right, +Y up, left-handed like the rest of this repo), the +Z face appears
+//   unflipped.
+//
+// The panorama's center is +Z, its left/right edges are -Z, and its top/bottom rows are +Y/-Y.
+use std::f32::consts::PI;
+
+pub const CUBE_FACES: usize = 6;
+const CHANNELS: usize = 4; // RGBA
+
+// Channel type of an image (ex. `u8` for PNGs, `f32` for HDR images).
+pub trait Channel: Copy + Default + Send + Sync {
+    // Value of a fully saturated channel (ex. 255 for `u8`).
+    const ONE: f32;
+    fn to_f32(self) -> f32;
+    fn from_f32(v: f32) -> Self;
+}
+
+impl Channel for u8 {
+    const ONE: f32 = u8::MAX as f32;
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+    #[inline]
+    fn from_f32(v: f32) -> Self {
+        v.round().clamp(0., u8::MAX as f32) as u8
+    }
+}
+
+impl Channel for f32 {
+    const ONE: f32 = 1.;
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self
+    }
+    #[inline]
+    fn from_f32(v: f32) -> Self {
+        v
+    }
+}
+
+// RGBA image, rows top to bottom.
+pub struct Image<T: Channel> {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<T>,
+}
+
+impl<T: Channel> Image<T> {
+    pub fn new(width: usize, height: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            data.len(),
+            width * height * CHANNELS,
+            "Image data size does not match its dimensions (RGBA)"
+        );
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    fn texel(&self, x: usize, y: usize) -> [f32; CHANNELS] {
+        let i = (y * self.width + x) * CHANNELS;
+        std::array::from_fn(|c| self.data[i + c].to_f32())
+    }
+
+    // Bilinear filtered sample of an equirectangular image, wrapping horizontally and clamping
+    // vertically. `u`/`v` are normalized texture coordinates (0 is the left/top edge).
+    fn sample_bilinear(&self, u: f32, v: f32) -> [f32; CHANNELS] {
+        let x = u * self.width as f32 - 0.5;
+        let y = (v * self.height as f32 - 0.5).clamp(0., (self.height - 1) as f32);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let w = self.width as isize;
+        let x0 = (x0 as isize).rem_euclid(w) as usize;
+        let x1 = (x0 + 1) % self.width;
+        let y0 = y0 as usize;
+        let y1 = (y0 + 1).min(self.height - 1);
+        let (a, b, c, d) = (
+            self.texel(x0, y0),
+            self.texel(x1, y0),
+            self.texel(x0, y1),
+            self.texel(x1, y1),
+        );
+        std::array::from_fn(|i| {
+            let top = a[i] + (b[i] - a[i]) * tx;
+            let bottom = c[i] + (d[i] - c[i]) * tx;
+            top + (bottom - top) * ty
+        })
+    }
+
+    // Same as `sample_bilinear()`, but clamping both horizontally and vertically (ex. a cube face).
+    pub(crate) fn sample_bilinear_clamped(&self, u: f32, v: f32) -> [f32; CHANNELS] {
+        let x = (u * self.width as f32 - 0.5).clamp(0., (self.width - 1) as f32);
+        let y = (v * self.height as f32 - 0.5).clamp(0., (self.height - 1) as f32);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as usize, y0 as usize);
+        let x1 = (x0 + 1).min(self.width - 1);
+        let y1 = (y0 + 1).min(self.height - 1);
+        let (a, b, c, d) = (
+            self.texel(x0, y0),
+            self.texel(x1, y0),
+            self.texel(x0, y1),
+            self.texel(x1, y1),
+        );
+        std::array::from_fn(|i| {
+            let top = a[i] + (b[i] - a[i]) * tx;
+            let bottom = c[i] + (d[i] - c[i]) * tx;
+            top + (bottom - top) * ty
+        })
+    }
+}
+
+// Direction (not normalized) from the center of the cube through face coordinates `sc`/`tc`, each in
+// [-1, 1] (left to right, top to bottom).
+#[inline]
+pub fn cube_face_direction(face: usize, sc: f32, tc: f32) -> [f32; 3] {
+    match face {
+        0 => [1., -tc, -sc],
+        1 => [-1., -tc, sc],
+        2 => [sc, 1., tc],
+        3 => [sc, -1., -tc],
+        4 => [sc, -tc, 1.],
+        5 => [-sc, -tc, -1.],
+        _ => panic!("Invalid cube face {face}"),
+    }
+}
+
+// Inverse of `cube_face_direction()`: the face `d` (not necessarily normalized) points into, and the
+// face coordinates (`sc`/`tc`, each in [-1, 1]) it points through.
+#[inline]
+pub fn direction_to_cube_face([x, y, z]: [f32; 3]) -> (usize, f32, f32) {
+    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+    if ax >= ay && ax >= az {
+        if x > 0. {
+            (0, -z / ax, -y / ax)
+        } else {
+            (1, z / ax, -y / ax)
+        }
+    } else if ay >= az {
+        if y > 0. {
+            (2, x / ay, z / ay)
+        } else {
+            (3, x / ay, -z / ay)
+        }
+    } else if z > 0. {
+        (4, x / az, -y / az)
+    } else {
+        (5, -x / az, -y / az)
+    }
+}
+
+// Normalized equirectangular texture coordinates for direction `d`.
+#[inline]
+fn direction_to_equirectangular([x, y, z]: [f32; 3]) -> (f32, f32) {
+    let length = (x * x + y * y + z * z).sqrt();
+    let longitude = x.atan2(z);
+    let latitude = (y / length).clamp(-1., 1.).asin();
+    (0.5 + longitude / (2. * PI), 0.5 - latitude / PI)
+}
+
+pub fn equirectangular_to_cube_face<T: Channel>(
+    equirectangular: &Image<T>,
+    face: usize,
+    face_size: usize,
+) -> Image<T> {
+    let mut data = Vec::with_capacity(face_size * face_size * CHANNELS);
+    for y in 0..face_size {
+        let tc = 2. * (y as f32 + 0.5) / face_size as f32 - 1.;
+        for x in 0..face_size {
+            let sc = 2. * (x as f32 + 0.5) / face_size as f32 - 1.;
+            let (u, v) = direction_to_equirectangular(cube_face_direction(face, sc, tc));
+            data.extend(
+                equirectangular
+                    .sample_bilinear(u, v)
+                    .into_iter()
+                    .map(T::from_f32),
+            );
+        }
+    }
+    Image::new(face_size, face_size, data)
+}
+
+// Samples all six cube faces (see ordering above), each `face_size` x `face_size`.
+pub fn equirectangular_to_cube_faces<T: Channel>(
+    equirectangular: &Image<T>,
+    face_size: usize,
+) -> [Image<T>; CUBE_FACES] {
+    assert!(face_size > 0, "Cube face size must be greater than 0");
+    assert!(
+        equirectangular.width > 0 && equirectangular.height > 0,
+        "Equirectangular image must not be empty"
+    );
+    std::thread::scope(|s| {
+        let faces = [0, 1, 2, 3, 4, 5].map(|face| {
+            s.spawn(move || equirectangular_to_cube_face(equirectangular, face, face_size))
+        });
+        faces.map(|f| f.join().expect("Failed to convert cube face"))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const AXIS_COLORS: [[u8; 4]; CUBE_FACES] = [
+        [255, 0, 0, 255],
+        [0, 255, 255, 255],
+        [0, 255, 0, 255],
+        [255, 0, 255, 255],
+        [0, 0, 255, 255],
+        [255, 255, 0, 255],
+    ];
+
+    // Equirectangular image colored by each texel direction's major axis (face).
+    fn axis_colored_equirectangular(width: usize, height: usize) -> Image<u8> {
+        let mut data = vec![];
+        for y in 0..height {
+            let latitude = PI * (0.5 - (y as f32 + 0.5) / height as f32);
+            for x in 0..width {
+                let longitude = 2. * PI * ((x as f32 + 0.5) / width as f32 - 0.5);
+                let d = [
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                    latitude.cos() * longitude.cos(),
+                ];
+                let axis = (0..3)
+                    .max_by(|&a, &b| d[a].abs().total_cmp(&d[b].abs()))
+                    .unwrap();
+                let face = axis * 2 + if d[axis] < 0. { 1 } else { 0 };
+                data.extend(AXIS_COLORS[face]);
+            }
+        }
+        Image::new(width, height, data)
+    }
+
+    fn texel(image: &Image<u8>, x: usize, y: usize) -> [u8; 4] {
+        let i = (y * image.width + x) * 4;
+        image.data[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_cube_face_direction() {
+        // Face centers point down each face's axis.
+        let expected = [
+            [1., 0., 0.],
+            [-1., 0., 0.],
+            [0., 1., 0.],
+            [0., -1., 0.],
+            [0., 0., 1.],
+            [0., 0., -1.],
+        ];
+        for (face, expected) in expected.into_iter().enumerate() {
+            assert_eq!(cube_face_direction(face, 0., 0.), expected);
+        }
+
+        // Looking down +Z, the top right of the face is +X and +Y
+        assert_eq!(cube_face_direction(4, 1., -1.), [1., 1., 1.]);
+        // Looking up +Y, the bottom of the face is +Z
+        assert_eq!(cube_face_direction(2, 0., 1.), [0., 1., 1.]);
+        // Looking down -Y, the top of the face is +Z
+        assert_eq!(cube_face_direction(3, 0., -1.), [0., -1., 1.]);
+    }
+
+    #[test]
+    fn test_direction_to_cube_face() {
+        for face in 0..CUBE_FACES {
+            for (sc, tc) in [(0., 0.), (0.5, -0.25), (-0.75, 0.9), (0.1, 0.6)] {
+                let (actual_face, actual_sc, actual_tc) =
+                    direction_to_cube_face(cube_face_direction(face, sc, tc).map(|v| v * 3.));
+                assert_eq!(actual_face, face);
+                assert!(
+                    (actual_sc - sc).abs() < 1e-6 && (actual_tc - tc).abs() < 1e-6,
+                    "face {face} ({sc}, {tc}) => ({actual_sc}, {actual_tc})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_direction_to_equirectangular() {
+        let assert_uv = |d: [f32; 3], (u, v): (f32, f32)| {
+            let (actual_u, actual_v) = direction_to_equirectangular(d);
+            assert!(
+                (actual_u - u).abs() < 1e-6 && (actual_v - v).abs() < 1e-6,
+                "{d:?} => {:?}, expected {:?}",
+                (actual_u, actual_v),
+                (u, v)
+            );
+        };
+        assert_uv([0., 0., 1.], (0.5, 0.5));
+        assert_uv([1., 0., 0.], (0.75, 0.5));
+        assert_uv([-1., 0., 0.], (0.25, 0.5));
+        assert_uv([0., 0., -1.], (1., 0.5));
+        assert_uv([0., 2., 0.], (0.5, 0.));
+        assert_uv([0., -2., 0.], (0.5, 1.));
+    }
+
+    #[test]
+    fn test_equirectangular_to_cube_faces() {
+        let face_size = 16;
+        let faces =
+            equirectangular_to_cube_faces(&axis_colored_equirectangular(256, 128), face_size);
+        for (face, image) in faces.iter().enumerate() {
+            assert_eq!((image.width, image.height), (face_size, face_size));
+            // Away from edges (where faces meet and bilinear filtering blends colors), the whole face
+            // has the color of its axis.
+            for y in 2..face_size - 2 {
+                for x in 2..face_size - 2 {
+                    assert_eq!(
+                        texel(image, x, y),
+                        AXIS_COLORS[face],
+                        "face {face} ({x}, {y})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_bilinear() {
+        #[rustfmt::skip]
+        let image = Image::new(2, 2, vec![
+            0., 0., 0., 0.,  1., 1., 1., 1.,
+            2., 2., 2., 2.,  3., 3., 3., 3.,
+        ]);
+        // Texel centers
+        assert_eq!(image.sample_bilinear(0.25, 0.25), [0.; 4]);
+        assert_eq!(image.sample_bilinear(0.75, 0.75), [3.; 4]);
+        // Between texel centers
+        assert_eq!(image.sample_bilinear(0.5, 0.25), [0.5; 4]);
+        assert_eq!(image.sample_bilinear(0.25, 0.5), [1.; 4]);
+        // Wraps horizontally
+        assert_eq!(image.sample_bilinear(1., 0.25), [0.5; 4]);
+        assert_eq!(image.sample_bilinear(0., 0.25), [0.5; 4]);
+        // Clamps vertically
+        assert_eq!(image.sample_bilinear(0.25, 0.), [0.; 4]);
+        assert_eq!(image.sample_bilinear(0.25, 1.), [2.; 4]);
+        // Clamps horizontally
+        assert_eq!(image.sample_bilinear_clamped(1., 0.25), [1.; 4]);
+        assert_eq!(image.sample_bilinear_clamped(0., 0.75), [2.; 4]);
+        assert_eq!(image.sample_bilinear_clamped(0.5, 0.5), [1.5; 4]);
+    }
+}
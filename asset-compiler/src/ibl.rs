@@ -0,0 +1,749 @@
+// Image-based lighting (IBL) precomputation from an environment (cube texture), for the split-sum
+// approximation (Karis 2013, "Real Shading in Unreal Engine 4"):
+//
+// - Prefiltered specular cube (`AssetKind::PrefilteredSpecularCube`): the environment convolved with
+//   the GGX distribution, assuming the normal and view are the reflection direction (n = v = r). Mip
+//   level `l` is prefiltered for roughness `l / (mip level count - 1)` (see
+//   `specular_level_roughness()`), GGX alpha is roughness^2.
+// - BRDF integration LUT (`AssetKind::BrdfLut`): scale (R) and bias (G) to F0 of the specular BRDF
+//   integrated over the hemisphere, indexed by the texture coordinates (n·v, roughness).
+//   specular = prefiltered specular(r, roughness) * (F0 * scale + bias)
+// - Irradiance cube (`AssetKind::IrradianceCube`): the environment convolved with the cosine lobe,
+//   divided by π (diffuse = albedo * irradiance).
+//
+// Everything is computed on the CPU by importance sampling (Hammersley points). Environment samples
+// are read from the environment's mip chain, filtered by the solid angle each sample covers
+// (Colbert and Křivánek 2007, "GPU-Based Importance Sampling"), so few samples are needed without
+// noise.
+//
+// Cubes are stored as `PixelFormat::Rgba16Float` (linear, alpha is 1), the LUT as
+// `PixelFormat::Rg16Float`.
+#[cfg(feature = "metal")]
+use crate::{cube_texture::load_cube_asset_dir, texture_2d::load_2d_asset_dir};
+use crate::{
+    cube_texture::read_cube_asset_dir_level_bytes,
+    equirectangular::{cube_face_direction, direction_to_cube_face, Image, CUBE_FACES},
+    metadata::{AssetKind, Compression, Metadata, PixelFormat, CUBE_FACE_ORDER},
+    mipmap::{
+        generate_mip_chain, mip_level_count, srgb_to_linear, ColorSpace, MipFilter, MipmapOptions,
+    },
+    texture_2d,
+    texture_asset::{read_slice_first_level, read_slice_level_bytes, write_level},
+};
+use half::f16;
+#[cfg(feature = "metal")]
+use metal::*;
+use std::{f32::consts::PI, path::Path};
+
+const CHANNELS: usize = 4; // RGBA
+const ALPHA_CHANNEL: usize = 3;
+const CUBE_PIXEL_FORMAT: PixelFormat = PixelFormat::Rgba16Float;
+const BRDF_LUT_PIXEL_FORMAT: PixelFormat = PixelFormat::Rg16Float;
+
+type Vec3 = [f32; 3];
+
+#[inline]
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline]
+fn normalize(a: Vec3) -> Vec3 {
+    let length = dot(a, a).sqrt();
+    a.map(|v| v / length)
+}
+
+// Tangent (+X), bitangent (+Y), and normal (+Z) of an orthonormal basis around `n`.
+fn tangent_frame(n: Vec3) -> [Vec3; 3] {
+    let up = if n[2].abs() < 0.999 {
+        [0., 0., 1.]
+    } else {
+        [1., 0., 0.]
+    };
+    let tangent = normalize(cross(up, n));
+    [tangent, cross(n, tangent), n]
+}
+
+#[inline]
+fn tangent_to_world([tangent, bitangent, n]: &[Vec3; 3], v: Vec3) -> Vec3 {
+    std::array::from_fn(|i| tangent[i] * v[0] + bitangent[i] * v[1] + n[i] * v[2])
+}
+
+// Low discrepancy points in [0, 1)^2.
+#[inline]
+fn hammersley(i: usize, count: usize) -> (f32, f32) {
+    (
+        i as f32 / count as f32,
+        (i as u32).reverse_bits() as f32 / 4294967296.,
+    )
+}
+
+// GGX normal distribution function.
+#[inline]
+fn ggx_distribution(n_dot_h: f32, alpha: f32) -> f32 {
+    let alpha2 = alpha * alpha;
+    let d = n_dot_h * n_dot_h * (alpha2 - 1.) + 1.;
+    alpha2 / (PI * d * d)
+}
+
+// Half vector (tangent space, +Z is the normal) importance sampled from the GGX distribution.
+#[inline]
+fn importance_sample_ggx((u1, u2): (f32, f32), alpha: f32) -> Vec3 {
+    let phi = 2. * PI * u1;
+    let cos_theta = ((1. - u2) / (1. + (alpha * alpha - 1.) * u2)).sqrt();
+    let sin_theta = (1. - cos_theta * cos_theta).max(0.).sqrt();
+    [sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta]
+}
+
+// Smith geometry term with Schlick-GGX, using k = alpha / 2 for IBL (Karis 2013).
+#[inline]
+fn geometry_smith(n_dot_v: f32, n_dot_l: f32, alpha: f32) -> f32 {
+    let k = alpha / 2.;
+    let g1 = |n_dot_x: f32| n_dot_x / (n_dot_x * (1. - k) + k);
+    g1(n_dot_v) * g1(n_dot_l)
+}
+
+// Linear RGB radiance from every direction, as cube faces (ordered like `CUBE_FACE_ORDER`) each with
+// a mip chain to filter samples.
+pub struct Environment {
+    faces: [Vec<Image<f32>>; CUBE_FACES],
+}
+
+impl Environment {
+    // From linear (ex. HDR) RGBA faces, all the same size and square. Alpha is ignored.
+    pub fn new(faces: [Image<f32>; CUBE_FACES]) -> Self {
+        let size = faces[0].width;
+        assert!(
+            size > 0
+                && faces
+                    .iter()
+                    .all(|face| (face.width, face.height) == (size, size)),
+            "Environment cube faces must be square and the same size"
+        );
+        let options = MipmapOptions {
+            filter: MipFilter::Box,
+            color_space: ColorSpace::Linear,
+        };
+        Self {
+            faces: faces.map(|face| generate_mip_chain(&face, options)),
+        }
+    }
+
+    // From sRGB encoded RGBA faces (ex. PNGs).
+    pub fn from_srgb_faces(faces: &[Image<u8>; CUBE_FACES]) -> Self {
+        Self::new([0, 1, 2, 3, 4, 5].map(|face| {
+            let face = &faces[face];
+            let data = face
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| {
+                    let v = v as f32 / u8::MAX as f32;
+                    if i % CHANNELS == ALPHA_CHANNEL {
+                        v
+                    } else {
+                        srgb_to_linear(v)
+                    }
+                })
+                .collect();
+            Image::new(face.width, face.height, data)
+        }))
+    }
+
+    // From the first mip level of a cube texture asset (the only level read, see
+    // `cube_texture::read_cube_texture_asset_dir()`).
+    pub fn from_cube_texture_asset_dir<P: AsRef<Path>>(cube_asset_dir: P) -> Self {
+        let cube_asset_dir = cube_asset_dir.as_ref();
+        let metadata = Metadata::read(cube_asset_dir, AssetKind::CubeTexture);
+        let faces = CUBE_FACE_ORDER
+            .map(|face| read_slice_first_level(cube_asset_dir, face.name(), &metadata));
+        Self::from_srgb_faces(&faces)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.faces[0][0].width
+    }
+
+    // Unfiltered faces (first mip level), ordered like `CUBE_FACE_ORDER`.
+    pub(crate) fn source_faces(&self) -> [&Image<f32>; CUBE_FACES] {
+        [0, 1, 2, 3, 4, 5].map(|face| &self.faces[face][0])
+    }
+
+    // Radiance in direction `d` (not necessarily normalized), trilinearly filtered between mip levels
+    // (`lod`).
+    fn sample(&self, d: Vec3, lod: f32) -> Vec3 {
+        let (face, sc, tc) = direction_to_cube_face(d);
+        let levels = &self.faces[face];
+        let lod = lod.clamp(0., (levels.len() - 1) as f32);
+        let level = lod.floor() as usize;
+        let t = lod - level as f32;
+        let (u, v) = ((sc + 1.) * 0.5, (tc + 1.) * 0.5);
+        let a = levels[level].sample_bilinear_clamped(u, v);
+        if t == 0. || level + 1 == levels.len() {
+            return [a[0], a[1], a[2]];
+        }
+        let b = levels[level + 1].sample_bilinear_clamped(u, v);
+        std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+    }
+
+    // Mip level to sample, so that a sample with probability density `pdf` (one of `sample_count`)
+    // covers its share of the sphere.
+    fn sample_lod(&self, pdf: f32, sample_count: usize) -> f32 {
+        let size = self.size() as f32;
+        let texel_solid_angle = 4. * PI / (CUBE_FACES as f32 * size * size);
+        let sample_solid_angle = 1. / (sample_count as f32 * pdf);
+        (0.5 * (sample_solid_angle / texel_solid_angle).log2() + 1.).max(0.)
+    }
+}
+
+// An importance sampled direction (tangent space, +Z is the normal).
+struct Sample {
+    direction: Vec3,
+    weight: f32,
+    lod: f32,
+}
+
+// Convolves the environment with `samples` for every texel's direction.
+fn convolve_cube(
+    environment: &Environment,
+    face_size: usize,
+    samples: &[Sample],
+) -> [Image<f32>; CUBE_FACES] {
+    let total_weight: f32 = samples.iter().map(|s| s.weight).sum();
+    std::thread::scope(|s| {
+        let faces = [0, 1, 2, 3, 4, 5].map(|face| {
+            s.spawn(move || {
+                let mut data = Vec::with_capacity(face_size * face_size * CHANNELS);
+                for y in 0..face_size {
+                    let tc = 2. * (y as f32 + 0.5) / face_size as f32 - 1.;
+                    for x in 0..face_size {
+                        let sc = 2. * (x as f32 + 0.5) / face_size as f32 - 1.;
+                        let frame = tangent_frame(normalize(cube_face_direction(face, sc, tc)));
+                        let mut sum = [0.; 3];
+                        for sample in samples {
+                            let radiance = environment
+                                .sample(tangent_to_world(&frame, sample.direction), sample.lod);
+                            for c in 0..3 {
+                                sum[c] += radiance[c] * sample.weight;
+                            }
+                        }
+                        data.extend(sum.map(|v| v / total_weight));
+                        data.push(1.);
+                    }
+                }
+                Image::new(face_size, face_size, data)
+            })
+        });
+        faces.map(|f| f.join().expect("Failed to convolve cube face"))
+    })
+}
+
+// Roughness the prefiltered specular cube's mip `level` is prefiltered for.
+pub fn specular_level_roughness(level: usize, mip_level_count: usize) -> f32 {
+    if mip_level_count > 1 {
+        level as f32 / (mip_level_count - 1) as f32
+    } else {
+        0.
+    }
+}
+
+// GGX samples around the reflection direction (n = v = r) for `roughness`, weighted by n·l.
+fn specular_samples(
+    environment: &Environment,
+    roughness: f32,
+    face_size: usize,
+    sample_count: usize,
+) -> Vec<Sample> {
+    if roughness == 0. {
+        // Perfect mirror, only filtered for the face size.
+        return vec![Sample {
+            direction: [0., 0., 1.],
+            weight: 1.,
+            lod: (environment.size() as f32 / face_size as f32)
+                .log2()
+                .max(0.),
+        }];
+    }
+    let alpha = roughness * roughness;
+    (0..sample_count)
+        .filter_map(|i| {
+            let h = importance_sample_ggx(hammersley(i, sample_count), alpha);
+            let n_dot_h = h[2];
+            let l = [
+                2. * n_dot_h * h[0],
+                2. * n_dot_h * h[1],
+                2. * n_dot_h * n_dot_h - 1.,
+            ];
+            let n_dot_l = l[2];
+            (n_dot_l > 0.).then(|| {
+                // pdf(l) = D(h) (n·h) / (4 (v·h)), where v·h = n·h
+                let pdf = ggx_distribution(n_dot_h, alpha) / 4.;
+                Sample {
+                    direction: l,
+                    weight: n_dot_l,
+                    lod: environment.sample_lod(pdf, sample_count),
+                }
+            })
+        })
+        .collect()
+}
+
+// Prefiltered specular cube faces (ordered like `CUBE_FACE_ORDER`), each with a full mip chain (see
+// `specular_level_roughness()`).
+pub fn prefilter_specular_cube(
+    environment: &Environment,
+    face_size: usize,
+    sample_count: usize,
+) -> [Vec<Image<f32>>; CUBE_FACES] {
+    assert!(face_size > 0, "Cube face size must be greater than 0");
+    let level_count = mip_level_count(face_size, face_size);
+    let mut faces: [Vec<Image<f32>>; CUBE_FACES] = Default::default();
+    for level in 0..level_count {
+        let level_size = (face_size >> level).max(1);
+        let samples = specular_samples(
+            environment,
+            specular_level_roughness(level, level_count),
+            level_size,
+            sample_count,
+        );
+        for (face, image) in convolve_cube(environment, level_size, &samples)
+            .into_iter()
+            .enumerate()
+        {
+            faces[face].push(image);
+        }
+    }
+    faces
+}
+
+// Irradiance cube faces (ordered like `CUBE_FACE_ORDER`), divided by π.
+pub fn irradiance_cube(
+    environment: &Environment,
+    face_size: usize,
+    sample_count: usize,
+) -> [Image<f32>; CUBE_FACES] {
+    assert!(face_size > 0, "Cube face size must be greater than 0");
+    // Cosine weighted samples: the average radiance is the irradiance divided by π.
+    let samples: Vec<Sample> = (0..sample_count)
+        .map(|i| {
+            let (u1, u2) = hammersley(i, sample_count);
+            let phi = 2. * PI * u1;
+            let (cos_theta, sin_theta) = ((1. - u2).sqrt(), u2.sqrt());
+            Sample {
+                direction: [sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta],
+                weight: 1.,
+                lod: environment.sample_lod(cos_theta / PI, sample_count),
+            }
+        })
+        .collect();
+    convolve_cube(environment, face_size, &samples)
+}
+
+// Scale and bias to F0 of the specular BRDF integrated over the hemisphere (see above).
+pub fn integrate_brdf(n_dot_v: f32, roughness: f32, sample_count: usize) -> [f32; 2] {
+    let alpha = roughness * roughness;
+    let v = [(1. - n_dot_v * n_dot_v).max(0.).sqrt(), 0., n_dot_v];
+    let (mut scale, mut bias) = (0., 0.);
+    for i in 0..sample_count {
+        let h = importance_sample_ggx(hammersley(i, sample_count), alpha);
+        let v_dot_h = dot(v, h);
+        let l: Vec3 = std::array::from_fn(|c| 2. * v_dot_h * h[c] - v[c]);
+        let (n_dot_l, n_dot_h) = (l[2], h[2]);
+        if n_dot_l > 0. {
+            let visibility =
+                geometry_smith(n_dot_v, n_dot_l, alpha) * v_dot_h / (n_dot_h * n_dot_v);
+            let fresnel = (1. - v_dot_h).powi(5);
+            scale += (1. - fresnel) * visibility;
+            bias += fresnel * visibility;
+        }
+    }
+    [scale / sample_count as f32, bias / sample_count as f32]
+}
+
+// BRDF integration LUT, `size` x `size` texels of scale and bias (see `integrate_brdf()`), rows top to
+// bottom. Texel (x, y) is n·v = (x + 0.5) / size and roughness = (y + 0.5) / size.
+pub struct BrdfLut {
+    pub size: usize,
+    pub data: Vec<[f32; 2]>,
+}
+
+impl BrdfLut {
+    pub fn new(size: usize, sample_count: usize) -> Self {
+        assert!(size > 0, "BRDF LUT size must be greater than 0");
+        let coordinate = |i: usize| (i as f32 + 0.5) / size as f32;
+        // Rows are split into contiguous chunks, one per available core.
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(size);
+        let rows_per_worker = (size + workers - 1) / workers;
+        let data = std::thread::scope(|s| {
+            let chunks: Vec<_> = (0..size)
+                .step_by(rows_per_worker)
+                .map(|first_row| {
+                    s.spawn(move || {
+                        (first_row..(first_row + rows_per_worker).min(size))
+                            .flat_map(|y| {
+                                (0..size).map(move |x| {
+                                    integrate_brdf(coordinate(x), coordinate(y), sample_count)
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            chunks
+                .into_iter()
+                .flat_map(|chunk| chunk.join().expect("Failed to integrate BRDF LUT rows"))
+                .collect()
+        });
+        Self { size, data }
+    }
+
+    #[inline]
+    pub fn texel(&self, x: usize, y: usize) -> [f32; 2] {
+        self.data[y * self.size + x]
+    }
+}
+
+fn to_f16_bytes(values: impl Iterator<Item = f32>) -> Vec<u8> {
+    values
+        .flat_map(|v| f16::from_f32(v).to_le_bytes())
+        .collect()
+}
+
+fn from_f16_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|b| f16::from_le_bytes([b[0], b[1]]).to_f32())
+        .collect()
+}
+
+fn write_cube_asset_dir(
+    target_dir: &Path,
+    kind: AssetKind,
+    faces: &[Vec<Image<f32>>; CUBE_FACES],
+    compression: Compression,
+) {
+    let (width, height) = (faces[0][0].width, faces[0][0].height);
+    for (levels, face) in faces.iter().zip(CUBE_FACE_ORDER) {
+        for (level, image) in levels.iter().enumerate() {
+            let bytes = to_f16_bytes(image.data.iter().copied());
+            write_level(target_dir, face.name(), level, &bytes, compression);
+        }
+    }
+    Metadata {
+        kind,
+        pixel_format: CUBE_PIXEL_FORMAT,
+        compression,
+        width: width as _,
+        height: height as _,
+        mip_level_count: faces[0].len() as _,
+        faces: CUBE_FACE_ORDER.to_vec(),
+    }
+    .write(target_dir);
+}
+
+fn read_cube_asset_dir(cube_asset_dir: &Path, kind: AssetKind) -> [Vec<Image<f32>>; CUBE_FACES] {
+    let (metadata, faces) =
+        read_cube_asset_dir_level_bytes(cube_asset_dir, kind, CUBE_PIXEL_FORMAT);
+    faces.map(|levels| {
+        levels
+            .iter()
+            .zip(metadata.mip_level_sizes())
+            .map(|(bytes, (width, height))| Image::new(width, height, from_f16_bytes(bytes)))
+            .collect()
+    })
+}
+
+pub fn create_prefiltered_specular_cube_asset_dir<P: AsRef<Path>>(
+    target_dir: P,
+    environment: &Environment,
+    face_size: usize,
+    sample_count: usize,
+    compression: Compression,
+) {
+    write_cube_asset_dir(
+        target_dir.as_ref(),
+        AssetKind::PrefilteredSpecularCube,
+        &prefilter_specular_cube(environment, face_size, sample_count),
+        compression,
+    );
+}
+
+pub fn create_irradiance_cube_asset_dir<P: AsRef<Path>>(
+    target_dir: P,
+    environment: &Environment,
+    face_size: usize,
+    sample_count: usize,
+    compression: Compression,
+) {
+    write_cube_asset_dir(
+        target_dir.as_ref(),
+        AssetKind::IrradianceCube,
+        &irradiance_cube(environment, face_size, sample_count).map(|face| vec![face]),
+        compression,
+    );
+}
+
+pub fn create_brdf_lut_asset_dir<P: AsRef<Path>>(
+    target_dir: P,
+    size: usize,
+    sample_count: usize,
+    compression: Compression,
+) {
+    let target_dir = target_dir.as_ref();
+    let lut = BrdfLut::new(size, sample_count);
+    let bytes = to_f16_bytes(lut.data.iter().flatten().copied());
+    write_level(target_dir, texture_2d::SLICE, 0, &bytes, compression);
+    Metadata {
+        kind: AssetKind::BrdfLut,
+        pixel_format: BRDF_LUT_PIXEL_FORMAT,
+        compression,
+        width: size as _,
+        height: size as _,
+        mip_level_count: 1,
+        faces: vec![],
+    }
+    .write(target_dir);
+}
+
+// Reads IBL assets into memory (ex. to inspect or verify assets).
+//
+// Assets compressed with `Compression::MetalIoLz4` can only be read with the `metal` feature.
+pub fn read_prefiltered_specular_cube_asset_dir<P: AsRef<Path>>(
+    asset_dir: P,
+) -> [Vec<Image<f32>>; CUBE_FACES] {
+    read_cube_asset_dir(asset_dir.as_ref(), AssetKind::PrefilteredSpecularCube)
+}
+
+pub fn read_irradiance_cube_asset_dir<P: AsRef<Path>>(asset_dir: P) -> [Image<f32>; CUBE_FACES] {
+    read_cube_asset_dir(asset_dir.as_ref(), AssetKind::IrradianceCube).map(|levels| {
+        levels
+            .into_iter()
+            .next()
+            .expect("Irradiance cube asset has no mip levels")
+    })
+}
+
+pub fn read_brdf_lut_asset_dir<P: AsRef<Path>>(asset_dir: P) -> BrdfLut {
+    let asset_dir = asset_dir.as_ref();
+    let metadata = Metadata::read(asset_dir, AssetKind::BrdfLut);
+    assert!(
+        metadata.pixel_format == BRDF_LUT_PIXEL_FORMAT && metadata.width == metadata.height,
+        "Unsupported BRDF LUT asset ({:?})",
+        asset_dir
+    );
+    let bytes = &read_slice_level_bytes(asset_dir, texture_2d::SLICE, &metadata)[0];
+    BrdfLut {
+        size: metadata.width as _,
+        data: from_f16_bytes(bytes)
+            .chunks_exact(2)
+            .map(|v| [v[0], v[1]])
+            .collect(),
+    }
+}
+
+#[cfg(feature = "metal")]
+pub fn load_prefiltered_specular_cube_asset_dir<P: AsRef<Path>>(
+    device: &Device,
+    asset_dir: P,
+) -> Texture {
+    load_cube_asset_dir(
+        device,
+        asset_dir.as_ref(),
+        AssetKind::PrefilteredSpecularCube,
+        CUBE_PIXEL_FORMAT,
+        "Prefiltered Specular Cube Texture",
+    )
+}
+
+#[cfg(feature = "metal")]
+pub fn load_irradiance_cube_asset_dir<P: AsRef<Path>>(device: &Device, asset_dir: P) -> Texture {
+    load_cube_asset_dir(
+        device,
+        asset_dir.as_ref(),
+        AssetKind::IrradianceCube,
+        CUBE_PIXEL_FORMAT,
+        "Irradiance Cube Texture",
+    )
+}
+
+#[cfg(feature = "metal")]
+pub fn load_brdf_lut_asset_dir<P: AsRef<Path>>(device: &Device, asset_dir: P) -> Texture {
+    load_2d_asset_dir(device, asset_dir.as_ref(), AssetKind::BrdfLut)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{analytic_cube_faces, temp_dir};
+
+    // Environment with `radiance(direction)` for each texel's (normalized) direction.
+    fn analytic_environment(size: usize, radiance: impl Fn(Vec3) -> Vec3) -> Environment {
+        Environment::new(analytic_cube_faces(size, radiance))
+    }
+
+    fn assert_rgb_near(actual: &[f32], expected: Vec3, tolerance: f32, label: &str) {
+        assert!(
+            (0..3).all(|c| (actual[c] - expected[c]).abs() <= tolerance),
+            "{label}: {:?}, expected {expected:?}",
+            &actual[..3]
+        );
+    }
+
+    #[test]
+    fn test_constant_environment() {
+        let color = [0.25, 0.5, 2.];
+        let environment = analytic_environment(16, |_| color);
+        for (face, levels) in prefilter_specular_cube(&environment, 8, 64)
+            .iter()
+            .enumerate()
+        {
+            assert_eq!(levels.len(), 4);
+            for (level, image) in levels.iter().enumerate() {
+                for texel in image.data.chunks_exact(CHANNELS) {
+                    assert_rgb_near(texel, color, 1e-4, &format!("face {face} level {level}"));
+                }
+            }
+        }
+        for (face, image) in irradiance_cube(&environment, 4, 64).iter().enumerate() {
+            for texel in image.data.chunks_exact(CHANNELS) {
+                assert_rgb_near(texel, color, 1e-4, &format!("face {face}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_irradiance_analytic() {
+        // Radiance is the (clamped) cosine to +Y.
+        let environment = analytic_environment(32, |d| [d[1].max(0.); 3]);
+        // Face size 1: each face's only texel is the face's axis.
+        let faces = irradiance_cube(&environment, 1, 1024);
+        // (1/π) ∫ max(0, l·y) max(0, l·n) dl
+        let expected = [
+            2. / (3. * PI),
+            2. / (3. * PI),
+            2. / 3.,
+            0.,
+            2. / (3. * PI),
+            2. / (3. * PI),
+        ];
+        for (face, expected) in expected.into_iter().enumerate() {
+            assert_rgb_near(
+                &faces[face].data,
+                [expected; 3],
+                0.01,
+                &format!("face {face}"),
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefilter_specular_roughness() {
+        let environment = analytic_environment(32, |d| [d[1].max(0.); 3]);
+        // Convolved at face size 1 (each face's only texel is the face's axis), looking up +Y, but
+        // sampled like the environment's face size (unfiltered mirror).
+        let prefiltered_up = |roughness: f32| {
+            let samples = specular_samples(&environment, roughness, 32, 256);
+            convolve_cube(&environment, 1, &samples)[2].data[0]
+        };
+        // Mirror: the environment
+        assert!((prefiltered_up(0.) - 1.).abs() < 0.02);
+        // Rougher surfaces blur the peak with darker surroundings
+        let mut previous = prefiltered_up(0.);
+        for roughness in [0.25, 0.5, 0.75, 1.] {
+            let current = prefiltered_up(roughness);
+            assert!(current < previous, "{roughness}: {current} >= {previous}");
+            previous = current;
+        }
+        assert_eq!(
+            prefilter_specular_cube(&environment, 8, 16).map(|levels| levels.len()),
+            [4; CUBE_FACES]
+        );
+        assert_eq!(specular_level_roughness(0, 4), 0.);
+        assert_eq!(specular_level_roughness(3, 4), 1.);
+        assert_eq!(specular_level_roughness(0, 1), 0.);
+    }
+
+    #[test]
+    fn test_brdf_lut() {
+        let lut = BrdfLut::new(16, 256);
+        // Smooth surfaces reflect everything (scale + bias = 1), with no Fresnel bias facing the
+        // surface.
+        let [scale, bias] = lut.texel(15, 0);
+        assert!(scale > 0.95 && bias < 0.01, "{:?}", (scale, bias));
+        for x in 0..16 {
+            let [scale, bias] = lut.texel(x, 0);
+            assert!((scale + bias - 1.).abs() < 0.03, "{x}: {:?}", (scale, bias));
+        }
+        // Rougher surfaces reflect less (shadowing and masking).
+        for y in 1..16 {
+            let [scale, bias] = lut.texel(8, y);
+            let [previous_scale, previous_bias] = lut.texel(8, y - 1);
+            assert!(scale + bias < previous_scale + previous_bias, "{y}");
+        }
+        assert!(lut.data.iter().flatten().all(|&v| (0. ..=1.).contains(&v)));
+    }
+
+    #[test]
+    fn test_create_and_read() {
+        let asset_dir = temp_dir("create-and-read");
+        let (specular_dir, irradiance_dir, brdf_lut_dir) = (
+            asset_dir.join("specular.asset"),
+            asset_dir.join("irradiance.asset"),
+            asset_dir.join("brdf_lut.asset"),
+        );
+        for dir in [&specular_dir, &irradiance_dir, &brdf_lut_dir] {
+            std::fs::create_dir(dir).expect("Failed to create temp asset directory");
+        }
+        let environment = analytic_environment(16, |d| d.map(|v| v.max(0.)));
+        let compression = Compression::PortableLz4;
+        create_prefiltered_specular_cube_asset_dir(&specular_dir, &environment, 4, 32, compression);
+        create_irradiance_cube_asset_dir(&irradiance_dir, &environment, 2, 32, compression);
+        create_brdf_lut_asset_dir(&brdf_lut_dir, 8, 32, compression);
+
+        // Half float precision
+        let assert_near = |actual: &[f32], expected: &[f32]| {
+            assert_eq!(actual.len(), expected.len());
+            for (a, e) in actual.iter().zip(expected) {
+                assert!((a - e).abs() <= e.abs() * 1e-3 + 1e-4, "{a} != {e}");
+            }
+        };
+        let expected = prefilter_specular_cube(&environment, 4, 32);
+        for (actual, expected) in read_prefiltered_specular_cube_asset_dir(&specular_dir)
+            .iter()
+            .zip(&expected)
+        {
+            assert_eq!(actual.len(), 3);
+            for (actual, expected) in actual.iter().zip(expected) {
+                assert_near(&actual.data, &expected.data);
+            }
+        }
+        let expected = irradiance_cube(&environment, 2, 32);
+        for (actual, expected) in read_irradiance_cube_asset_dir(&irradiance_dir)
+            .iter()
+            .zip(&expected)
+        {
+            assert_near(&actual.data, &expected.data);
+        }
+        let (actual, expected) = (read_brdf_lut_asset_dir(&brdf_lut_dir), BrdfLut::new(8, 32));
+        assert_eq!(actual.size, 8);
+        assert_near(&actual.data.concat(), &expected.data.concat());
+
+        assert_eq!(
+            Metadata::read(&brdf_lut_dir, AssetKind::BrdfLut).pixel_format,
+            PixelFormat::Rg16Float
+        );
+        std::fs::remove_dir_all(&asset_dir).expect("Failed to remove temp asset directory");
+    }
+}
@@ -1,3 +1,11 @@
-#![feature(maybe_uninit_uninit_array)]
-#![feature(maybe_uninit_array_assume_init)]
+pub mod container;
 pub mod cube_texture;
+pub mod equirectangular;
+pub mod ibl;
+pub mod metadata;
+pub mod mipmap;
+pub mod spherical_harmonics;
+pub mod texture_2d;
+mod texture_asset;
+#[cfg(test)]
+mod test_util;
@@ -0,0 +1,426 @@
+// Self-describing metadata stored with every asset (`metadata.info` in the asset's directory).
+//
+// Format (version 1, all integers little-endian u32s, except the magic):
+//
+//   offset  size            field
+//   0       4               magic, "UOUA"
+//   4       4               format version, 1
+//   8       4               asset kind (`AssetKind`)
+//   12      4               pixel format (`PixelFormat`)
+//   16      4               compression (`Compression`)
+//   20      4               width of mip level 0
+//   24      4               height of mip level 0
+//   28      4               mip level count (each level is half the previous, see
+//                           `mipmap::mip_level_size()`)
+//   32      4               face count (6 for cube textures, 0 for 2D textures, see
+//                           `AssetKind::is_cube()`)
+//   36      4 * face count  face order (`CubeFace`)
+//
+// Enum values are stable (never reused or renumbered), and are unrelated to Metal's values (ex.
+// `MTLPixelFormat`), so metadata can be read without Metal.
+use crate::mipmap::{self, mip_level_size};
+use std::{fmt::Display, path::Path};
+
+pub const MAGIC: [u8; 4] = *b"UOUA";
+pub const VERSION: u32 = 1;
+pub const METADATA_FILENAME: &str = "metadata.info";
+const HEADER_SIZE: usize = 36;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AssetKind {
+    CubeTexture = 1,
+    Texture2D = 2,
+    // Image-based lighting assets (see `ibl`).
+    PrefilteredSpecularCube = 3,
+    IrradianceCube = 4,
+    BrdfLut = 5,
+    // Spherical harmonics irradiance coefficients (see `spherical_harmonics`).
+    SHIrradiance = 6,
+}
+
+impl AssetKind {
+    pub fn is_cube(self) -> bool {
+        match self {
+            AssetKind::CubeTexture
+            | AssetKind::PrefilteredSpecularCube
+            | AssetKind::IrradianceCube => true,
+            AssetKind::Texture2D | AssetKind::BrdfLut | AssetKind::SHIrradiance => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PixelFormat {
+    Rgba8Unorm = 1,
+    // Half float (IEEE 754 binary16) channels, little-endian.
+    Rgba16Float = 2,
+    Rg16Float = 3,
+    // Single float (IEEE 754 binary32) channels, little-endian.
+    Rgba32Float = 4,
+}
+
+impl PixelFormat {
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgba8Unorm => 4,
+            PixelFormat::Rgba16Float => 8,
+            PixelFormat::Rg16Float => 4,
+            PixelFormat::Rgba32Float => 16,
+        }
+    }
+
+    #[cfg(feature = "metal")]
+    pub fn mtl_pixel_format(self) -> metal::MTLPixelFormat {
+        match self {
+            PixelFormat::Rgba8Unorm => metal::MTLPixelFormat::RGBA8Unorm,
+            PixelFormat::Rgba16Float => metal::MTLPixelFormat::RGBA16Float,
+            PixelFormat::Rg16Float => metal::MTLPixelFormat::RG16Float,
+            PixelFormat::Rgba32Float => metal::MTLPixelFormat::RGBA32Float,
+        }
+    }
+}
+
+// How each face's mip level is compressed. Defaults to Metal IO when available, as it is the fastest to
+// load.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Compression {
+    // Metal IO (`IOCompression`) LZ4 chunked files, loaded directly into the texture by a Metal IO
+    // command queue. Can only be written and loaded with Metal.
+    #[cfg_attr(feature = "metal", default)]
+    MetalIoLz4 = 1,
+    // Portable chunked LZ4 container (see `container`). Can be written and read on any platform, and
+    // is loaded by decompressing on the CPU and blitting into the texture.
+    #[cfg_attr(not(feature = "metal"), default)]
+    PortableLz4 = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CubeFace {
+    PosX = 1,
+    NegX = 2,
+    PosY = 3,
+    NegY = 4,
+    PosZ = 5,
+    NegZ = 6,
+}
+
+// Metal's cube texture slice order.
+pub const CUBE_FACE_ORDER: [CubeFace; 6] = [
+    CubeFace::PosX,
+    CubeFace::NegX,
+    CubeFace::PosY,
+    CubeFace::NegY,
+    CubeFace::PosZ,
+    CubeFace::NegZ,
+];
+
+impl CubeFace {
+    // Asset file name prefix for the face (ex. `posx_0`).
+    pub fn name(self) -> &'static str {
+        match self {
+            CubeFace::PosX => "posx",
+            CubeFace::NegX => "negx",
+            CubeFace::PosY => "posy",
+            CubeFace::NegY => "negy",
+            CubeFace::PosZ => "posz",
+            CubeFace::NegZ => "negz",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum MetadataError {
+    Truncated,
+    // Most likely created by an older asset compiler (before metadata was versioned).
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    UnknownAssetKind(u32),
+    UnexpectedAssetKind {
+        expected: AssetKind,
+        found: AssetKind,
+    },
+    UnknownPixelFormat(u32),
+    UnknownCompression(u32),
+    UnknownCubeFace(u32),
+    InvalidFaceOrder,
+    InvalidDimensions,
+    InvalidMipLevelCount(u32),
+    TrailingBytes,
+}
+
+impl Display for MetadataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataError::Truncated => write!(f, "metadata is truncated"),
+            MetadataError::InvalidMagic => write!(
+                f,
+                "metadata is not in a known format (invalid magic), it was likely created by an older asset compiler and must be recompiled"
+            ),
+            MetadataError::UnsupportedVersion(version) if *version < VERSION => write!(
+                f,
+                "metadata format version {version} is no longer supported (expected {VERSION}), the asset must be recompiled"
+            ),
+            MetadataError::UnsupportedVersion(version) => write!(
+                f,
+                "metadata format version {version} is newer than supported (expected {VERSION})"
+            ),
+            MetadataError::UnknownAssetKind(kind) => write!(f, "unknown asset kind {kind}"),
+            MetadataError::UnexpectedAssetKind { expected, found } => {
+                write!(f, "expected a {expected:?} asset, found a {found:?} asset")
+            }
+            MetadataError::UnknownPixelFormat(format) => {
+                write!(f, "unknown pixel format {format}")
+            }
+            MetadataError::UnknownCompression(compression) => {
+                write!(f, "unknown compression {compression}")
+            }
+            MetadataError::UnknownCubeFace(face) => write!(f, "unknown cube face {face}"),
+            MetadataError::InvalidFaceOrder => write!(f, "unsupported face order"),
+            MetadataError::InvalidDimensions => write!(f, "width and height must not be 0"),
+            MetadataError::InvalidMipLevelCount(count) => {
+                write!(f, "invalid mip level count {count} for its dimensions")
+            }
+            MetadataError::TrailingBytes => write!(f, "unexpected bytes after metadata"),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Metadata {
+    pub kind: AssetKind,
+    pub pixel_format: PixelFormat,
+    pub compression: Compression,
+    pub width: u32,
+    pub height: u32,
+    pub mip_level_count: u32,
+    pub faces: Vec<CubeFace>,
+}
+
+impl Metadata {
+    // Width and height of each mip level.
+    pub fn mip_level_sizes(&self) -> Vec<(usize, usize)> {
+        (0..self.mip_level_count as usize)
+            .map(|level| mip_level_size((self.width as _, self.height as _), level))
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.faces.len() * 4);
+        bytes.extend(MAGIC);
+        for v in [
+            VERSION,
+            self.kind as u32,
+            self.pixel_format as u32,
+            self.compression as u32,
+            self.width,
+            self.height,
+            self.mip_level_count,
+            self.faces.len() as u32,
+        ] {
+            bytes.extend(v.to_le_bytes());
+        }
+        for &face in &self.faces {
+            bytes.extend((face as u32).to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self, MetadataError> {
+        let read_u32 = |offset: usize| -> Result<u32, MetadataError> {
+            bytes
+                .get(offset..offset + 4)
+                .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+                .ok_or(MetadataError::Truncated)
+        };
+        if bytes.get(0..4).ok_or(MetadataError::Truncated)? != MAGIC {
+            return Err(MetadataError::InvalidMagic);
+        }
+        let version = read_u32(4)?;
+        if version != VERSION {
+            return Err(MetadataError::UnsupportedVersion(version));
+        }
+        let kind = match read_u32(8)? {
+            1 => AssetKind::CubeTexture,
+            2 => AssetKind::Texture2D,
+            3 => AssetKind::PrefilteredSpecularCube,
+            4 => AssetKind::IrradianceCube,
+            5 => AssetKind::BrdfLut,
+            6 => AssetKind::SHIrradiance,
+            kind => return Err(MetadataError::UnknownAssetKind(kind)),
+        };
+        let pixel_format = match read_u32(12)? {
+            1 => PixelFormat::Rgba8Unorm,
+            2 => PixelFormat::Rgba16Float,
+            3 => PixelFormat::Rg16Float,
+            4 => PixelFormat::Rgba32Float,
+            format => return Err(MetadataError::UnknownPixelFormat(format)),
+        };
+        let compression = match read_u32(16)? {
+            1 => Compression::MetalIoLz4,
+            2 => Compression::PortableLz4,
+            compression => return Err(MetadataError::UnknownCompression(compression)),
+        };
+        let (width, height, mip_level_count) = (read_u32(20)?, read_u32(24)?, read_u32(28)?);
+        if width == 0 || height == 0 {
+            return Err(MetadataError::InvalidDimensions);
+        }
+        if mip_level_count == 0
+            || mip_level_count as usize > mipmap::mip_level_count(width as _, height as _)
+        {
+            return Err(MetadataError::InvalidMipLevelCount(mip_level_count));
+        }
+        let face_count = read_u32(32)? as usize;
+        let faces = (0..face_count)
+            .map(|i| match read_u32(HEADER_SIZE + i * 4)? {
+                face @ 1..=6 => Ok(CUBE_FACE_ORDER[face as usize - 1]),
+                face => Err(MetadataError::UnknownCubeFace(face)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if bytes.len() > HEADER_SIZE + face_count * 4 {
+            return Err(MetadataError::TrailingBytes);
+        }
+        let expected_faces: &[CubeFace] = if kind.is_cube() {
+            &CUBE_FACE_ORDER
+        } else {
+            &[]
+        };
+        if faces != expected_faces {
+            return Err(MetadataError::InvalidFaceOrder);
+        }
+        Ok(Self {
+            kind,
+            pixel_format,
+            compression,
+            width,
+            height,
+            mip_level_count,
+            faces,
+        })
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, asset_dir: P) {
+        let asset_dir = asset_dir.as_ref();
+        std::fs::write(asset_dir.join(METADATA_FILENAME), self.to_bytes()).unwrap_or_else(
+            |error| panic!("Failed to write asset metadata ({asset_dir:?}): {error}"),
+        );
+    }
+
+    // Reads and validates an asset's metadata, panicking with the reason if it can not be used (ex.
+    // created by an older asset compiler).
+    pub fn read<P: AsRef<Path>>(asset_dir: P, expected_kind: AssetKind) -> Self {
+        let metadata_file = asset_dir.as_ref().join(METADATA_FILENAME);
+        let bytes = std::fs::read(&metadata_file).unwrap_or_else(|error| {
+            panic!("Failed to find/read asset's metadata file ({metadata_file:?}): {error}")
+        });
+        Self::parse(&bytes)
+            .and_then(|metadata| {
+                if metadata.kind == expected_kind {
+                    Ok(metadata)
+                } else {
+                    Err(MetadataError::UnexpectedAssetKind {
+                        expected: expected_kind,
+                        found: metadata.kind,
+                    })
+                }
+            })
+            .unwrap_or_else(|error| panic!("Invalid asset metadata ({metadata_file:?}): {error}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cube_metadata() -> Metadata {
+        Metadata {
+            kind: AssetKind::CubeTexture,
+            pixel_format: PixelFormat::Rgba8Unorm,
+            compression: Compression::PortableLz4,
+            width: 8,
+            height: 4,
+            mip_level_count: 4,
+            faces: CUBE_FACE_ORDER.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_to_bytes_and_parse() {
+        let metadata = cube_metadata();
+        let bytes = metadata.to_bytes();
+        #[rustfmt::skip]
+        assert_eq!(bytes, [
+            b'U', b'O', b'U', b'A',
+            1, 0, 0, 0,
+            1, 0, 0, 0,
+            1, 0, 0, 0,
+            2, 0, 0, 0,
+            8, 0, 0, 0,
+            4, 0, 0, 0,
+            4, 0, 0, 0,
+            6, 0, 0, 0,
+            1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0,
+        ]);
+        assert_eq!(Metadata::parse(&bytes), Ok(metadata));
+        assert_eq!(
+            cube_metadata().mip_level_sizes(),
+            [(8, 4), (4, 2), (2, 1), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let bytes = cube_metadata().to_bytes();
+        let with_u32 = |offset: usize, value: u32| {
+            let mut bytes = bytes.clone();
+            bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            Metadata::parse(&bytes)
+        };
+        assert_eq!(Metadata::parse(&[]), Err(MetadataError::Truncated));
+        assert_eq!(
+            Metadata::parse(&bytes[..bytes.len() - 1]),
+            Err(MetadataError::Truncated)
+        );
+        assert_eq!(
+            Metadata::parse(&[&bytes[..], &[0]].concat()),
+            Err(MetadataError::TrailingBytes)
+        );
+        // Unversioned metadata (raw `#[repr(C)]` struct, starting with the width)
+        assert_eq!(with_u32(0, 2048), Err(MetadataError::InvalidMagic));
+        assert_eq!(with_u32(4, 2), Err(MetadataError::UnsupportedVersion(2)));
+        assert_eq!(with_u32(8, 9), Err(MetadataError::UnknownAssetKind(9)));
+        // 2D textures have no faces
+        assert_eq!(with_u32(8, 2), Err(MetadataError::InvalidFaceOrder));
+        assert_eq!(with_u32(8, 5), Err(MetadataError::InvalidFaceOrder));
+        assert_eq!(with_u32(8, 6), Err(MetadataError::InvalidFaceOrder));
+        assert_eq!(
+            with_u32(8, 3).map(|metadata| metadata.kind),
+            Ok(AssetKind::PrefilteredSpecularCube)
+        );
+        assert_eq!(with_u32(12, 0), Err(MetadataError::UnknownPixelFormat(0)));
+        assert_eq!(with_u32(12, 5), Err(MetadataError::UnknownPixelFormat(5)));
+        assert_eq!(with_u32(16, 3), Err(MetadataError::UnknownCompression(3)));
+        assert_eq!(with_u32(20, 0), Err(MetadataError::InvalidDimensions));
+        assert_eq!(with_u32(28, 0), Err(MetadataError::InvalidMipLevelCount(0)));
+        assert_eq!(with_u32(28, 5), Err(MetadataError::InvalidMipLevelCount(5)));
+        assert_eq!(with_u32(36, 7), Err(MetadataError::UnknownCubeFace(7)));
+        assert_eq!(with_u32(36, 2), Err(MetadataError::InvalidFaceOrder));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            MetadataError::UnsupportedVersion(0).to_string(),
+            "metadata format version 0 is no longer supported (expected 1), the asset must be recompiled"
+        );
+        assert_eq!(
+            MetadataError::UnsupportedVersion(2).to_string(),
+            "metadata format version 2 is newer than supported (expected 1)"
+        );
+    }
+}
@@ -0,0 +1,367 @@
+// CPU mipmap generation for texture assets.
+//
+// Each level is downsampled from the previous level with a separable filter, clamping at the image
+// edges (cube faces are filtered independently, ignoring neighboring faces).
+use crate::equirectangular::{Channel, Image};
+use std::f32::consts::PI;
+
+const CHANNELS: usize = 4; // RGBA
+const ALPHA_CHANNEL: usize = 3;
+const KAISER_ALPHA: f32 = 4.;
+const KAISER_SUPPORT: f32 = 3.;
+const LANCZOS_SUPPORT: f32 = 3.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MipFilter {
+    // Averages all texels covered by the destination texel. Fast, but blurs and aliases more than
+    // the windowed sinc filters.
+    Box,
+    // Kaiser windowed sinc (alpha = 4, support = 3).
+    Kaiser,
+    // Lanczos windowed sinc (a = 3).
+    Lanczos,
+}
+
+// Color space of the RGB channels. Alpha is always linear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    Linear,
+    // RGB is sRGB encoded (ex. PNGs). Filtering is done on linear values (gamma-correct), then
+    // re-encoded.
+    Srgb,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MipmapOptions {
+    pub filter: MipFilter,
+    pub color_space: ColorSpace,
+}
+
+impl Default for MipmapOptions {
+    fn default() -> Self {
+        Self {
+            filter: MipFilter::Kaiser,
+            color_space: ColorSpace::Srgb,
+        }
+    }
+}
+
+// Number of levels in a full mip chain, down to 1x1.
+pub fn mip_level_count(width: usize, height: usize) -> usize {
+    (usize::BITS - width.max(height).max(1).leading_zeros()) as usize
+}
+
+// Size of mip `level` (each level is half the previous, rounded down, and at least 1).
+pub fn mip_level_size((width, height): (usize, usize), level: usize) -> (usize, usize) {
+    ((width >> level).max(1), (height >> level).max(1))
+}
+
+#[inline]
+pub(crate) fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[inline]
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[inline]
+fn sinc(x: f32) -> f32 {
+    if x == 0. {
+        1.
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+// Zeroth order modified Bessel function of the first kind (power series).
+fn bessel_i0(x: f32) -> f32 {
+    let (mut sum, mut term, mut k) = (1., 1., 1.);
+    loop {
+        term *= (x / (2. * k)) * (x / (2. * k));
+        sum += term;
+        if term < sum * 1e-8 {
+            return sum;
+        }
+        k += 1.;
+    }
+}
+
+impl MipFilter {
+    fn support(self) -> f32 {
+        match self {
+            MipFilter::Box => 0.5,
+            MipFilter::Kaiser => KAISER_SUPPORT,
+            MipFilter::Lanczos => LANCZOS_SUPPORT,
+        }
+    }
+
+    // Filter weight at `x` (in destination texels) from the destination texel's center.
+    fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            MipFilter::Box => {
+                if x < 0.5 {
+                    1.
+                } else {
+                    0.
+                }
+            }
+            MipFilter::Kaiser => {
+                if x < KAISER_SUPPORT {
+                    let t = x / KAISER_SUPPORT;
+                    sinc(x) * bessel_i0(KAISER_ALPHA * (1. - t * t).sqrt())
+                        / bessel_i0(KAISER_ALPHA)
+                } else {
+                    0.
+                }
+            }
+            MipFilter::Lanczos => {
+                if x < LANCZOS_SUPPORT {
+                    sinc(x) * sinc(x / LANCZOS_SUPPORT)
+                } else {
+                    0.
+                }
+            }
+        }
+    }
+
+    // Source texel indices (clamped to the edge) and normalized weights contributing to each
+    // destination texel.
+    fn contributions(self, src_size: usize, dst_size: usize) -> Vec<Vec<(usize, f32)>> {
+        let scale = src_size as f32 / dst_size as f32;
+        let support = self.support() * scale;
+        (0..dst_size)
+            .map(|d| {
+                let center = (d as f32 + 0.5) * scale;
+                let start = (center - support).floor() as isize;
+                let end = (center + support).ceil() as isize;
+                let mut contributions: Vec<(usize, f32)> = (start..end)
+                    .filter_map(|s| {
+                        let weight = if self == MipFilter::Box {
+                            // Portion of the source texel covered by the destination texel
+                            let (lo, hi) = (center - scale * 0.5, center + scale * 0.5);
+                            (((s + 1) as f32).min(hi) - (s as f32).max(lo)).max(0.)
+                        } else {
+                            self.weight((s as f32 + 0.5 - center) / scale)
+                        };
+                        (weight.abs() > 1e-6)
+                            .then(|| (s.clamp(0, src_size as isize - 1) as usize, weight))
+                    })
+                    .collect();
+                let total: f32 = contributions.iter().map(|(_, w)| w).sum();
+                for (_, w) in &mut contributions {
+                    *w /= total;
+                }
+                contributions
+            })
+            .collect()
+    }
+}
+
+// Normalized (0 to 1 for `u8` channels), linear RGBA texels used while filtering.
+struct Texels {
+    width: usize,
+    height: usize,
+    data: Vec<[f32; CHANNELS]>,
+}
+
+impl Texels {
+    fn from_image<T: Channel>(image: &Image<T>, color_space: ColorSpace) -> Self {
+        let data = image
+            .data
+            .chunks_exact(CHANNELS)
+            .map(|texel| {
+                std::array::from_fn(|c| {
+                    let v = texel[c].to_f32() / T::ONE;
+                    if color_space == ColorSpace::Srgb && c != ALPHA_CHANNEL {
+                        srgb_to_linear(v)
+                    } else {
+                        v
+                    }
+                })
+            })
+            .collect();
+        Self {
+            width: image.width,
+            height: image.height,
+            data,
+        }
+    }
+
+    fn to_image<T: Channel>(&self, color_space: ColorSpace) -> Image<T> {
+        let mut data = Vec::with_capacity(self.data.len() * CHANNELS);
+        for texel in &self.data {
+            for (c, &v) in texel.iter().enumerate() {
+                // Windowed sinc filters have negative lobes that may ring below 0.
+                let v = v.max(0.);
+                let v = if color_space == ColorSpace::Srgb && c != ALPHA_CHANNEL {
+                    linear_to_srgb(v.min(1.))
+                } else {
+                    v
+                };
+                data.push(T::from_f32(v * T::ONE));
+            }
+        }
+        Image::new(self.width, self.height, data)
+    }
+
+    fn downsample(&self, filter: MipFilter, width: usize, height: usize) -> Self {
+        let columns = filter.contributions(self.width, width);
+        let rows = filter.contributions(self.height, height);
+        let filter_texels = |contributions: &[(usize, f32)], texel: &dyn Fn(usize) -> [f32; 4]| {
+            let mut v = [0.; CHANNELS];
+            for &(i, w) in contributions {
+                let t = texel(i);
+                for c in 0..CHANNELS {
+                    v[c] += t[c] * w;
+                }
+            }
+            v
+        };
+        let mut horizontal = Vec::with_capacity(width * self.height);
+        for y in 0..self.height {
+            for column in &columns {
+                horizontal.push(filter_texels(column, &|x| self.data[y * self.width + x]));
+            }
+        }
+        let mut data = Vec::with_capacity(width * height);
+        for row in &rows {
+            for x in 0..width {
+                data.push(filter_texels(row, &|y| horizontal[y * width + x]));
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+// Generates a full mip chain down to 1x1, starting with `image` (level 0).
+pub fn generate_mip_chain<T: Channel>(image: &Image<T>, options: MipmapOptions) -> Vec<Image<T>> {
+    let level_count = mip_level_count(image.width, image.height);
+    let mut levels = Vec::with_capacity(level_count);
+    levels.push(Image::new(image.width, image.height, image.data.clone()));
+    let mut texels = Texels::from_image(image, options.color_space);
+    for level in 1..level_count {
+        let (width, height) = mip_level_size((image.width, image.height), level);
+        texels = texels.downsample(options.filter, width, height);
+        levels.push(texels.to_image(options.color_space));
+    }
+    levels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solid_image(width: usize, height: usize, texel: [u8; 4]) -> Image<u8> {
+        Image::new(width, height, texel.repeat(width * height))
+    }
+
+    #[test]
+    fn test_mip_level_count_and_size() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(2048, 2048), 12);
+        assert_eq!(mip_level_count(5, 3), 3);
+        assert_eq!(mip_level_size((5, 3), 1), (2, 1));
+        assert_eq!(mip_level_size((5, 3), 2), (1, 1));
+    }
+
+    #[test]
+    fn test_generate_mip_chain_sizes() {
+        let levels = generate_mip_chain(&solid_image(8, 2, [0; 4]), MipmapOptions::default());
+        let sizes: Vec<_> = levels.iter().map(|l| (l.width, l.height)).collect();
+        assert_eq!(sizes, [(8, 2), (4, 1), (2, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_solid_color_is_preserved() {
+        for filter in [MipFilter::Box, MipFilter::Kaiser, MipFilter::Lanczos] {
+            for color_space in [ColorSpace::Linear, ColorSpace::Srgb] {
+                let texel = [10, 128, 250, 77];
+                let levels = generate_mip_chain(
+                    &solid_image(7, 5, texel),
+                    MipmapOptions {
+                        filter,
+                        color_space,
+                    },
+                );
+                for level in &levels[1..] {
+                    assert_eq!(level.data, texel.repeat(level.width * level.height));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_box_filter_gamma_correct() {
+        // Black and white texels
+        #[rustfmt::skip]
+        let image = Image::new(2, 1, vec![
+            0, 0, 0, 0,
+            255, 255, 255, 255,
+        ]);
+        let linear = generate_mip_chain(
+            &image,
+            MipmapOptions {
+                filter: MipFilter::Box,
+                color_space: ColorSpace::Linear,
+            },
+        );
+        assert_eq!(linear[1].data, [128, 128, 128, 128]);
+
+        // Averaging in linear space is brighter once re-encoded (sRGB 50% gray is ~188). Alpha is
+        // always averaged linearly.
+        let srgb = generate_mip_chain(
+            &image,
+            MipmapOptions {
+                filter: MipFilter::Box,
+                color_space: ColorSpace::Srgb,
+            },
+        );
+        assert_eq!(srgb[1].data, [188, 188, 188, 128]);
+    }
+
+    #[test]
+    fn test_box_filter_odd_size() {
+        // 3 -> 1: each source texel covers a third of the destination texel
+        let image = Image::new(3, 1, vec![0., 0., 0., 0., 3., 3., 3., 3., 6., 6., 6., 6.]);
+        let levels = generate_mip_chain(
+            &image,
+            MipmapOptions {
+                filter: MipFilter::Box,
+                color_space: ColorSpace::Linear,
+            },
+        );
+        assert_eq!(levels.len(), 2);
+        for v in &levels[1].data {
+            assert!((v - 3.).abs() < 1e-5, "{v}");
+        }
+    }
+
+    #[test]
+    fn test_windowed_sinc_filters() {
+        for filter in [MipFilter::Kaiser, MipFilter::Lanczos] {
+            assert_eq!(filter.weight(0.), 1.);
+            assert!(filter.weight(1.).abs() < 1e-6);
+            assert!(filter.weight(1.5) < 0.);
+            assert_eq!(filter.weight(3.), 0.);
+            for contributions in filter.contributions(16, 8) {
+                let total: f32 = contributions.iter().map(|(_, w)| w).sum();
+                assert!((total - 1.).abs() < 1e-5);
+            }
+        }
+    }
+}
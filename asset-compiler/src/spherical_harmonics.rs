@@ -0,0 +1,256 @@
+// Spherical harmonics (SH) irradiance from an environment (cube texture), a compact (9 RGB
+// coefficients) alternative to an irradiance cube (see `ibl`) for diffuse lighting (Ramamoorthi and
+// Hanrahan 2001, "An Efficient Representation for Irradiance Environment Maps"):
+//
+// 1. Radiance is projected onto the first 3 bands (l = 0, 1, 2) of the real SH basis (see
+//    `metal_types::SHIrradiance::basis()`), weighting every texel by the solid angle it covers.
+// 2. Projected radiance is convolved with the cosine lobe, by scaling each band (see
+//    `COSINE_LOBE_BANDS`), and divided by π (diffuse = albedo * irradiance).
+//
+// Bands above 2 contribute very little to irradiance (< 1% on average), so the (smooth) irradiance is
+// accurately represented even if the environment is not.
+//
+// Coefficients are stored as a 9 x 1 `PixelFormat::Rgba32Float` 2D asset (alpha is 0), byte for byte
+// the same as `metal_types::SHIrradiance`.
+use crate::{
+    equirectangular::{cube_face_direction, Image, CUBE_FACES},
+    ibl::Environment,
+    metadata::{AssetKind, Compression, Metadata, PixelFormat},
+    texture_2d,
+    texture_asset::{read_slice_level_bytes, write_level},
+};
+use metal_types::{float4, SHIrradiance, SH_IRRADIANCE_NUM_COEFFICIENTS};
+use std::{f64::consts::PI, path::Path};
+
+pub const NUM_COEFFICIENTS: usize = SH_IRRADIANCE_NUM_COEFFICIENTS as _;
+const CHANNELS: usize = 4; // RGBA
+const PIXEL_FORMAT: PixelFormat = PixelFormat::Rgba32Float;
+// Cosine lobe convolution divided by π, for each coefficient's band: 1, 2/3, and 1/4 (for bands 0, 1,
+// and 2).
+const COSINE_LOBE_BANDS: [f32; NUM_COEFFICIENTS] =
+    [1., 2. / 3., 2. / 3., 2. / 3., 0.25, 0.25, 0.25, 0.25, 0.25];
+
+// Solid angle of the cube face area from the face's center to (`x`, `y`), in face coordinates.
+#[inline]
+fn area_element(x: f64, y: f64) -> f64 {
+    (x * y).atan2((x * x + y * y + 1.).sqrt())
+}
+
+// Solid angle covered by texel (`x`, `y`) of a `size` x `size` cube face.
+fn texel_solid_angle(x: usize, y: usize, size: usize) -> f64 {
+    let texel_coord = |i: usize| 2. * i as f64 / size as f64 - 1.;
+    let (x0, y0, x1, y1) = (
+        texel_coord(x),
+        texel_coord(y),
+        texel_coord(x + 1),
+        texel_coord(y + 1),
+    );
+    area_element(x0, y0) - area_element(x0, y1) - area_element(x1, y0) + area_element(x1, y1)
+}
+
+// Projects linear RGB(A) radiance cube faces (ordered like `CUBE_FACE_ORDER`, all the same size and
+// square) onto the SH basis. Alpha is ignored.
+pub fn project_radiance(faces: [&Image<f32>; CUBE_FACES]) -> [[f32; 3]; NUM_COEFFICIENTS] {
+    let size = faces[0].width;
+    assert!(
+        size > 0
+            && faces
+                .iter()
+                .all(|face| (face.width, face.height) == (size, size)),
+        "Environment cube faces must be square and the same size"
+    );
+    let mut coefficients = [[0f64; 3]; NUM_COEFFICIENTS];
+    let mut total_solid_angle = 0.;
+    for (face, image) in faces.iter().enumerate() {
+        for y in 0..size {
+            let tc = 2. * (y as f32 + 0.5) / size as f32 - 1.;
+            for x in 0..size {
+                let sc = 2. * (x as f32 + 0.5) / size as f32 - 1.;
+                let d = cube_face_direction(face, sc, tc);
+                let length = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+                let basis = SHIrradiance::basis(d.map(|v| v / length));
+                let solid_angle = texel_solid_angle(x, y, size);
+                let texel = &image.data[(y * size + x) * CHANNELS..][..3];
+                for (c, basis) in coefficients.iter_mut().zip(basis) {
+                    for (c, &radiance) in c.iter_mut().zip(texel) {
+                        *c += radiance as f64 * basis as f64 * solid_angle;
+                    }
+                }
+                total_solid_angle += solid_angle;
+            }
+        }
+    }
+    // Texel solid angles sum to the sphere (4π), normalizing only corrects rounding.
+    let normalize = 4. * PI / total_solid_angle;
+    coefficients.map(|c| c.map(|c| (c * normalize) as f32))
+}
+
+// SH irradiance (divided by π) from projected radiance (see `project_radiance()`).
+pub fn convolve_irradiance(radiance: [[f32; 3]; NUM_COEFFICIENTS]) -> SHIrradiance {
+    let mut coefficients = radiance;
+    for (c, band) in coefficients.iter_mut().zip(COSINE_LOBE_BANDS) {
+        *c = c.map(|c| c * band);
+    }
+    SHIrradiance::new(coefficients)
+}
+
+pub fn sh_irradiance(environment: &Environment) -> SHIrradiance {
+    convolve_irradiance(project_radiance(environment.source_faces()))
+}
+
+// The asset is tiny (144 bytes) and read on the CPU (ex. to fill a constant buffer), so it is always
+// stored with `Compression::PortableLz4`.
+pub fn create_sh_irradiance_asset_dir<P: AsRef<Path>>(target_dir: P, environment: &Environment) {
+    let target_dir = target_dir.as_ref();
+    let compression = Compression::PortableLz4;
+    let bytes: Vec<u8> = sh_irradiance(environment)
+        .coefficients
+        .iter()
+        .flat_map(|c| c.xyzw)
+        .flat_map(f32::to_le_bytes)
+        .collect();
+    write_level(target_dir, texture_2d::SLICE, 0, &bytes, compression);
+    Metadata {
+        kind: AssetKind::SHIrradiance,
+        pixel_format: PIXEL_FORMAT,
+        compression,
+        width: NUM_COEFFICIENTS as _,
+        height: 1,
+        mip_level_count: 1,
+        faces: vec![],
+    }
+    .write(target_dir);
+}
+
+pub fn read_sh_irradiance_asset_dir<P: AsRef<Path>>(asset_dir: P) -> SHIrradiance {
+    let asset_dir = asset_dir.as_ref();
+    let metadata = Metadata::read(asset_dir, AssetKind::SHIrradiance);
+    assert!(
+        metadata.pixel_format == PIXEL_FORMAT
+            && (metadata.width, metadata.height) == (NUM_COEFFICIENTS as _, 1),
+        "Unsupported SH irradiance asset ({:?})",
+        asset_dir
+    );
+    let bytes = &read_slice_level_bytes(asset_dir, texture_2d::SLICE, &metadata)[0];
+    let values: Vec<f32> = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    SHIrradiance {
+        coefficients: std::array::from_fn(|i| float4 {
+            xyzw: std::array::from_fn(|channel| values[i * CHANNELS + channel]),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{analytic_cube_faces, temp_dir};
+
+    const TOLERANCE: f32 = 1e-3;
+
+    fn analytic_sh_irradiance(radiance: impl Fn([f32; 3]) -> [f32; 3]) -> SHIrradiance {
+        let faces = analytic_cube_faces(32, radiance);
+        convolve_irradiance(project_radiance(
+            [0, 1, 2, 3, 4, 5].map(|face| &faces[face]),
+        ))
+    }
+
+    fn assert_irradiance(sh: &SHIrradiance, normal: [f32; 3], expected: [f32; 3]) {
+        let actual = sh.irradiance(normal);
+        assert!(
+            actual
+                .iter()
+                .zip(&expected)
+                .all(|(a, e)| (a - e).abs() < TOLERANCE),
+            "normal {normal:?}: expected {expected:?}, actual {actual:?}"
+        );
+    }
+
+    const NORMALS: [[f32; 3]; 8] = [
+        [1., 0., 0.],
+        [-1., 0., 0.],
+        [0., 1., 0.],
+        [0., -1., 0.],
+        [0., 0., 1.],
+        [0., 0., -1.],
+        [0.57735026, 0.57735026, 0.57735026],
+        [0.8, -0.6, 0.],
+    ];
+
+    #[test]
+    fn test_texel_solid_angle() {
+        for size in [1, 2, 7, 32] {
+            let total: f64 = (0..size)
+                .flat_map(|y| (0..size).map(move |x| texel_solid_angle(x, y, size)))
+                .sum();
+            assert!((total * CUBE_FACES as f64 - 4. * PI).abs() < 1e-9);
+        }
+        // Texels at the center of a face cover more of the sphere than at the corners.
+        assert!(texel_solid_angle(1, 1, 4) > texel_solid_angle(0, 0, 4));
+        assert!((texel_solid_angle(0, 1, 4) - texel_solid_angle(1, 0, 4)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_constant_environment() {
+        let sh = analytic_sh_irradiance(|_| [1., 0.5, 0.]);
+        let coefficients = sh.rgb_coefficients();
+        // Only band 0: radiance * sqrt(4π)
+        let l00 = (4. * PI).sqrt() as f32;
+        assert!((coefficients[0][0] - l00).abs() < TOLERANCE);
+        assert!(coefficients[1..]
+            .iter()
+            .flatten()
+            .all(|c| c.abs() < TOLERANCE));
+        for n in NORMALS {
+            assert_irradiance(&sh, n, [1., 0.5, 0.]);
+        }
+    }
+
+    #[test]
+    fn test_linear_environment() {
+        // Bands 0 and 1: irradiance / π = a + (2/3) * b * n.y
+        let sh = analytic_sh_irradiance(|[_, y, _]| [1. + y, 0.5 - 0.5 * y, 0.]);
+        for n in NORMALS {
+            assert_irradiance(&sh, n, [1. + 2. / 3. * n[1], 0.5 - 1. / 3. * n[1], 0.]);
+        }
+    }
+
+    #[test]
+    fn test_quadratic_environment() {
+        // Bands 0 and 2: z^2 = 1/3 + (2/3) * P2(z), irradiance / π = 1/3 + (1/4) * (2/3) * P2(n.z)
+        let sh = analytic_sh_irradiance(|[_, _, z]| [z * z; 3]);
+        for n in NORMALS {
+            let expected = 1. / 3. + (3. * n[2] * n[2] - 1.) / 12.;
+            assert_irradiance(&sh, n, [expected; 3]);
+        }
+    }
+
+    #[test]
+    fn test_sky_environment() {
+        // Uniform sky above the horizon, black below. Irradiance only depends on bands 0 and 1 (the
+        // cosine lobe removes odd bands above 1), so it is exact: irradiance / π = (1 + n.y) / 2
+        let sh = analytic_sh_irradiance(|[_, y, _]| if y > 0. { [1., 2., 0.5] } else { [0.; 3] });
+        for n in NORMALS {
+            let e = (1. + n[1]) / 2.;
+            assert_irradiance(&sh, n, [e, 2. * e, 0.5 * e]);
+        }
+    }
+
+    #[test]
+    fn test_create_and_read() {
+        let asset_dir = temp_dir("sh-irradiance");
+        let environment = Environment::new(analytic_cube_faces(8, |[x, y, z]| {
+            [x.max(0.), y.max(0.), z.max(0.)]
+        }));
+        create_sh_irradiance_asset_dir(&asset_dir, &environment);
+        let actual = read_sh_irradiance_asset_dir(&asset_dir);
+        assert_eq!(
+            actual.rgb_coefficients(),
+            sh_irradiance(&environment).rgb_coefficients()
+        );
+        assert!(actual.coefficients.iter().all(|c| c.xyzw[3] == 0.));
+        std::fs::remove_dir_all(&asset_dir).expect("Failed to remove temp asset directory");
+    }
+}
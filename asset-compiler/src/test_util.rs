@@ -0,0 +1,45 @@
+// Helpers shared by tests.
+use crate::equirectangular::{cube_face_direction, Image};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Creates an empty directory in the system's temp directory, unique to `name`, the test process,
+// and the call (tests run in parallel and may share a `name`).
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "asset-compiler-{name}-{}-{}",
+        std::process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    // Left over from an earlier process that had the same id.
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).expect("Failed to remove stale temp directory");
+    }
+    std::fs::create_dir(&dir).expect("Failed to create temp directory");
+    dir
+}
+
+// Cube faces (RGBA, ordered like `CUBE_FACE_ORDER`) with `radiance(direction)` for each texel's
+// (normalized) direction.
+pub(crate) fn analytic_cube_faces(
+    size: usize,
+    radiance: impl Fn([f32; 3]) -> [f32; 3],
+) -> [Image<f32>; 6] {
+    [0, 1, 2, 3, 4, 5].map(|face| {
+        let mut data = vec![];
+        for y in 0..size {
+            let tc = 2. * (y as f32 + 0.5) / size as f32 - 1.;
+            for x in 0..size {
+                let sc = 2. * (x as f32 + 0.5) / size as f32 - 1.;
+                let d = cube_face_direction(face, sc, tc);
+                let length = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+                data.extend(radiance(d.map(|v| v / length)));
+                data.push(1.);
+            }
+        }
+        Image::new(size, size, data)
+    })
+}
@@ -0,0 +1,313 @@
+// 2D texture assets (ex. model material textures), see `texture_asset`.
+#[cfg(feature = "metal")]
+use crate::texture_asset::{asset_level_loads, load_levels};
+use crate::{
+    equirectangular::Image,
+    metadata::{AssetKind, Compression, Metadata, PixelFormat},
+    mipmap::{mip_level_count, ColorSpace, MipmapOptions},
+    texture_asset::{load_image_from_png, read_slice_levels, write_slice_levels},
+};
+#[cfg(feature = "metal")]
+use metal::*;
+use std::path::{Path, PathBuf};
+
+pub(crate) const SLICE: &str = "texture";
+// Hash of the texture file an asset was created from (see `create_texture_2d_asset_dir()`).
+const SOURCE_HASH_FILENAME: &str = "source.hash";
+const SUPPORTED_PIXEL_FORMAT: PixelFormat = PixelFormat::Rgba8Unorm;
+
+// Asset directory for a compiled texture file, next to the texture file (ex. `yoda/yoda-head.png` ->
+// `yoda/yoda-head.asset`).
+pub fn texture_asset_dir<P: AsRef<Path>>(texture_file: P) -> PathBuf {
+    texture_file.as_ref().with_extension("asset")
+}
+
+// Also records the texture file's hash, see `is_texture_2d_asset_dir_current()`.
+pub fn create_texture_2d_asset_dir<P: AsRef<Path>, P2: AsRef<Path>>(
+    target_dir: P,
+    texture_file: P2,
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
+) {
+    let (target_dir, texture_file) = (target_dir.as_ref(), texture_file.as_ref());
+    create_texture_2d_asset_dir_from_image(
+        target_dir,
+        &load_image_from_png(texture_file),
+        mipmaps,
+        compression,
+    );
+    let source_hash = build_hash::hash_path(texture_file)
+        .unwrap_or_else(|| panic!("Failed to hash texture file ({texture_file:?})"));
+    std::fs::write(
+        target_dir.join(SOURCE_HASH_FILENAME),
+        format!("{source_hash:016x}"),
+    )
+    .unwrap_or_else(|error| panic!("Failed to write asset source hash ({target_dir:?}): {error}"));
+}
+
+// Whether the asset was created from the current contents of `texture_file` (ex. not modified since
+// the asset was compiled). Assets created from an image in memory (see
+// `create_texture_2d_asset_dir_from_image()`) are never current.
+pub fn is_texture_2d_asset_dir_current<P: AsRef<Path>, P2: AsRef<Path>>(
+    asset_dir: P,
+    texture_file: P2,
+) -> bool {
+    let recorded_hash = std::fs::read_to_string(asset_dir.as_ref().join(SOURCE_HASH_FILENAME))
+        .ok()
+        .and_then(|hash| u64::from_str_radix(&hash, 16).ok());
+    recorded_hash.is_some() && recorded_hash == build_hash::hash_path(texture_file)
+}
+
+// Same as `create_texture_2d_asset_dir()`, but from an RGBA image already in memory.
+//
+// If `mipmaps` is specified, a full mip chain is generated (see `mipmap::generate_mip_chain()`),
+// otherwise only a single level is stored.
+pub fn create_texture_2d_asset_dir_from_image<P: AsRef<Path>>(
+    target_dir: P,
+    image: &Image<u8>,
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
+) {
+    let (width, height) = (image.width, image.height);
+    assert!(
+        width > 0 && height > 0,
+        "Width and Height of texture must not be 0"
+    );
+    assert!(
+        width <= u32::MAX as usize && height <= u32::MAX as usize,
+        "Texture is too large"
+    );
+    let mip_level_count = if mipmaps.is_some() {
+        mip_level_count(width, height)
+    } else {
+        1
+    };
+    let target_dir = target_dir.as_ref();
+    write_slice_levels(target_dir, SLICE, image, mipmaps, compression);
+    Metadata {
+        kind: AssetKind::Texture2D,
+        pixel_format: SUPPORTED_PIXEL_FORMAT,
+        compression,
+        width: width as _,
+        height: height as _,
+        mip_level_count: mip_level_count as _,
+        faces: vec![],
+    }
+    .write(target_dir);
+}
+
+// Reads all of a 2D texture asset's mip levels (ex. to inspect or verify assets).
+//
+// Assets compressed with `Compression::MetalIoLz4` can only be read with the `metal` feature.
+pub fn read_texture_2d_asset_dir<P: AsRef<Path>>(asset_dir: P) -> Vec<Image<u8>> {
+    let asset_dir = asset_dir.as_ref();
+    let metadata = Metadata::read(asset_dir, AssetKind::Texture2D);
+    read_slice_levels(asset_dir, SLICE, &metadata)
+}
+
+// Texture descriptor matching a 2D texture asset (pixel format, dimensions, and mip levels). Storage
+// and resource options are left to the caller (ex. allocating from a heap).
+#[cfg(feature = "metal")]
+pub fn texture_2d_asset_descriptor(metadata: &Metadata) -> TextureDescriptor {
+    assert_eq!(
+        metadata.kind,
+        AssetKind::Texture2D,
+        "Asset is not a 2D texture"
+    );
+    asset_2d_descriptor(metadata)
+}
+
+#[cfg(feature = "metal")]
+fn asset_2d_descriptor(metadata: &Metadata) -> TextureDescriptor {
+    let desc = TextureDescriptor::new();
+    desc.set_pixel_format(metadata.pixel_format.mtl_pixel_format());
+    desc.set_texture_type(MTLTextureType::D2);
+    desc.set_usage(MTLTextureUsage::ShaderRead);
+    desc.set_width(metadata.width as _);
+    desc.set_height(metadata.height as _);
+    desc.set_mipmap_level_count(metadata.mip_level_count as _);
+    desc
+}
+
+// Loads 2D texture assets into already allocated textures (see `texture_2d_asset_descriptor()`),
+// waiting until all are loaded.
+#[cfg(feature = "metal")]
+pub fn load_texture_2d_assets_into(device: &Device, textures: &[(&TextureRef, &Path)]) {
+    let loads: Vec<_> = textures
+        .iter()
+        .flat_map(|&(texture, asset_dir)| {
+            let metadata = Metadata::read(asset_dir, AssetKind::Texture2D);
+            asset_level_loads(texture, asset_dir, &metadata, &[SLICE])
+        })
+        .collect();
+    load_levels(device, &loads);
+}
+
+#[cfg(feature = "metal")]
+pub fn load_texture_2d_asset_dir<P: AsRef<Path>>(device: &Device, asset_dir: P) -> Texture {
+    load_2d_asset_dir(device, asset_dir.as_ref(), AssetKind::Texture2D)
+}
+
+// Same as `load_texture_2d_asset_dir()`, but for any kind of 2D asset (see `AssetKind::is_cube()`),
+// stored like 2D textures (single slice, see `SLICE`).
+#[cfg(feature = "metal")]
+pub(crate) fn load_2d_asset_dir(device: &Device, asset_dir: &Path, kind: AssetKind) -> Texture {
+    let metadata = Metadata::read(asset_dir, kind);
+    let texture = {
+        let desc = asset_2d_descriptor(&metadata);
+        desc.set_resource_options(MTLResourceOptions::StorageModePrivate);
+        device.new_texture(&desc)
+    };
+    texture.set_label(&asset_dir.to_string_lossy());
+    load_levels(
+        device,
+        &asset_level_loads(&texture, asset_dir, &metadata, &[SLICE]),
+    );
+    texture
+}
+
+// Every texture file referenced by a Wavefront material file (`.mtl`), deduplicated, with the color
+// space to generate its mip levels in: ambient and diffuse textures are sRGB color, everything else
+// (ex. specular, bump) is linear data. Textures referenced as both use the first reference.
+pub fn mtl_texture_files<P: AsRef<Path>>(mtl_file: P) -> Vec<(PathBuf, ColorSpace)> {
+    let mtl_file = mtl_file.as_ref();
+    let (materials, _) = tobj::load_mtl(mtl_file)
+        .unwrap_or_else(|error| panic!("Failed to load material file ({mtl_file:?}): {error}"));
+    let mtl_dir = mtl_file.parent().unwrap_or(Path::new(""));
+    let mut texture_files: Vec<(PathBuf, ColorSpace)> = vec![];
+    for material in &materials {
+        for (texture, color_space) in [
+            (&material.ambient_texture, ColorSpace::Srgb),
+            (&material.diffuse_texture, ColorSpace::Srgb),
+            (&material.specular_texture, ColorSpace::Linear),
+            (&material.normal_texture, ColorSpace::Linear),
+            (&material.shininess_texture, ColorSpace::Linear),
+            (&material.dissolve_texture, ColorSpace::Linear),
+        ] {
+            if texture.is_empty() {
+                continue;
+            }
+            let texture_file = mtl_dir.join(texture);
+            if !texture_files.iter().any(|(f, _)| f == &texture_file) {
+                texture_files.push((texture_file, color_space));
+            }
+        }
+    }
+    texture_files
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mipmap::generate_mip_chain, test_util::temp_dir};
+
+    #[test]
+    fn test_texture_asset_dir() {
+        assert_eq!(
+            texture_asset_dir("yoda/yoda-head.png"),
+            PathBuf::from("yoda/yoda-head.asset")
+        );
+    }
+
+    #[test]
+    fn test_read_portable() {
+        let asset_dir = temp_dir("read-portable");
+        let data = (0..5 * 3)
+            .flat_map(|i| [i as u8 * 16, 0, 255, 255])
+            .collect();
+        let image = Image::new(5, 3, data);
+        let mipmaps = MipmapOptions::default();
+        create_texture_2d_asset_dir_from_image(
+            &asset_dir,
+            &image,
+            Some(mipmaps),
+            Compression::PortableLz4,
+        );
+
+        let actual = read_texture_2d_asset_dir(&asset_dir);
+        let expected = generate_mip_chain(&image, mipmaps);
+        assert_eq!(actual.len(), 3);
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert_eq!(
+                (actual.width, actual.height, &actual.data),
+                (expected.width, expected.height, &expected.data)
+            );
+        }
+        std::fs::remove_dir_all(&asset_dir).expect("Failed to remove temp asset directory");
+    }
+
+    #[test]
+    fn test_is_texture_2d_asset_dir_current() {
+        let dir = temp_dir("current");
+        let texture_file = dir.join("texture.png");
+        let write_png = |data: &[u8]| {
+            let mut encoder =
+                png::Encoder::new(std::fs::File::create(&texture_file).unwrap(), 2, 1);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(data).unwrap();
+        };
+        write_png(&[255, 0, 0, 255, 0, 255, 0, 255]);
+
+        let asset_dir = dir.join("texture.asset");
+        std::fs::create_dir(&asset_dir).unwrap();
+        create_texture_2d_asset_dir(&asset_dir, &texture_file, None, Compression::PortableLz4);
+        assert!(is_texture_2d_asset_dir_current(&asset_dir, &texture_file));
+        assert_eq!(
+            read_texture_2d_asset_dir(&asset_dir)[0].data,
+            [255, 0, 0, 255, 0, 255, 0, 255]
+        );
+
+        // Modified after the asset was created.
+        write_png(&[0, 0, 255, 255, 0, 255, 0, 255]);
+        assert!(!is_texture_2d_asset_dir_current(&asset_dir, &texture_file));
+
+        // No recorded texture file.
+        let image_asset_dir = dir.join("image.asset");
+        std::fs::create_dir(&image_asset_dir).unwrap();
+        create_texture_2d_asset_dir_from_image(
+            &image_asset_dir,
+            &Image::new(1, 1, vec![0, 0, 0, 255]),
+            None,
+            Compression::PortableLz4,
+        );
+        assert!(!is_texture_2d_asset_dir_current(
+            &image_asset_dir,
+            &texture_file
+        ));
+        std::fs::remove_dir_all(&dir).expect("Failed to remove temp directory");
+    }
+
+    #[test]
+    fn test_mtl_texture_files() {
+        let dir = temp_dir("mtl");
+        let mtl_file = dir.join("model.mtl");
+        std::fs::write(
+            &mtl_file,
+            "\
+newmtl A
+map_Ka a.png
+map_Kd a.png
+map_Ks a-specular.png
+map_bump a-bump.png
+
+newmtl B
+Kd 1.0 1.0 1.0
+map_Ks b.png
+map_Kd a-bump.png
+",
+        )
+        .unwrap();
+        assert_eq!(
+            mtl_texture_files(&mtl_file),
+            [
+                (dir.join("a.png"), ColorSpace::Srgb),
+                (dir.join("a-specular.png"), ColorSpace::Linear),
+                (dir.join("a-bump.png"), ColorSpace::Linear),
+                (dir.join("b.png"), ColorSpace::Linear),
+            ]
+        );
+        std::fs::remove_dir_all(&dir).expect("Failed to remove temp directory");
+    }
+}
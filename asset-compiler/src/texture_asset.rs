@@ -0,0 +1,404 @@
+// Shared by texture assets (`cube_texture`, `texture_2d`).
+//
+// A texture asset directory contains the asset's metadata (see `metadata`) and a file for each slice's
+// (ex. cube face) mip level, named `{slice}_{level}` (ex. `posx_0`, `posx_1`, ...).
+use crate::{
+    container,
+    equirectangular::Image,
+    metadata::{Compression, Metadata, PixelFormat},
+    mipmap::{generate_mip_chain, MipmapOptions},
+};
+#[cfg(feature = "metal")]
+use metal::*;
+use std::path::Path;
+#[cfg(feature = "metal")]
+use std::path::PathBuf;
+
+#[cfg(feature = "metal")]
+const COMPRESSION_METHOD: MTLIOCompressionMethod = MTLIOCompressionMethod::lz4;
+const SUPPORTED_INPUT_PIXEL_FORMAT: png::ColorType = png::ColorType::Rgba;
+
+pub(crate) fn load_image_from_png<P: AsRef<Path>>(image_path: P) -> Image<u8> {
+    let image_path = image_path.as_ref();
+    let file = std::fs::File::open(image_path)
+        .unwrap_or_else(|error| panic!("Could not open input PNG file ({image_path:?}): {error}"));
+    let decoder = png::Decoder::new(file);
+    let mut reader = decoder
+        .read_info()
+        .expect("Failed to decode PNG information");
+    let info = reader.info();
+    assert_eq!(
+        info.color_type, SUPPORTED_INPUT_PIXEL_FORMAT,
+        "Invalid texture PNG color format ({:?}). Must be RGBA.",
+        image_path
+    );
+    let (width, height) = (info.width, info.height);
+    let mut buf = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut buf).unwrap_or_else(|error| {
+        panic!("Failed to load image data ({image_path:?}) into buffer: {error}")
+    });
+    Image::new(width as _, height as _, buf)
+}
+
+#[cfg(feature = "metal")]
+fn write_compressed_file(dest_file: &Path, bytes: &[u8]) {
+    let io = IOCompression::new(
+        &dest_file.to_string_lossy(),
+        COMPRESSION_METHOD,
+        IOCompression::default_chunk_size(),
+    );
+    io.append(bytes.as_ptr() as _, bytes.len() as _);
+    let io_flush_result = io.flush();
+    assert_eq!(
+        io_flush_result,
+        MTLIOCompressionStatus::complete,
+        "Failed to write compressed file"
+    );
+}
+
+// Writes a single mip level of `slice` (raw bytes, in the asset's pixel format).
+pub(crate) fn write_level(
+    target_dir: &Path,
+    slice: &str,
+    level: usize,
+    bytes: &[u8],
+    compression: Compression,
+) {
+    let dest_file = &target_dir.join(level_filename(slice, level));
+    match compression {
+        #[cfg(feature = "metal")]
+        Compression::MetalIoLz4 => write_compressed_file(dest_file, bytes),
+        #[cfg(not(feature = "metal"))]
+        Compression::MetalIoLz4 => {
+            panic!("Metal IO compressed assets can only be created with the `metal` feature")
+        }
+        Compression::PortableLz4 => container::write_file(dest_file, bytes),
+    }
+}
+
+fn level_filename(slice: &str, level: usize) -> String {
+    format!("{slice}_{level}")
+}
+
+// Writes `image` as `slice`'s first mip level. If `mipmaps` is specified, the rest of the mip chain is
+// generated and written as well (see `mipmap::generate_mip_chain()`).
+pub(crate) fn write_slice_levels(
+    target_dir: &Path,
+    slice: &str,
+    image: &Image<u8>,
+    mipmaps: Option<MipmapOptions>,
+    compression: Compression,
+) {
+    write_level(target_dir, slice, 0, &image.data, compression);
+    if let Some(options) = mipmaps {
+        for (level, image) in generate_mip_chain(image, options)
+            .iter()
+            .enumerate()
+            .skip(1)
+        {
+            write_level(target_dir, slice, level, &image.data, compression);
+        }
+    }
+}
+
+// Reads all of `slice`'s mip levels (raw bytes, in the asset's pixel format) into memory.
+//
+// Assets compressed with `Compression::MetalIoLz4` can only be read with the `metal` feature.
+pub(crate) fn read_slice_level_bytes(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+) -> Vec<Vec<u8>> {
+    read_slice_first_levels_bytes(asset_dir, slice, metadata, metadata.mip_level_count as _)
+}
+
+// Same as `read_slice_level_bytes()`, but only reads the first (full size) mip level.
+pub(crate) fn read_slice_first_level_bytes(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+) -> Vec<u8> {
+    read_slice_first_levels_bytes(asset_dir, slice, metadata, 1)
+        .pop()
+        .expect("Asset has no mip levels")
+}
+
+fn read_slice_first_levels_bytes(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+    level_count: usize,
+) -> Vec<Vec<u8>> {
+    let level_count = level_count.min(metadata.mip_level_count as _);
+    let levels: Vec<Vec<u8>> = match metadata.compression {
+        #[cfg(feature = "metal")]
+        Compression::MetalIoLz4 => {
+            read_metal_io_slice_levels(asset_dir, slice, metadata, level_count)
+        }
+        #[cfg(not(feature = "metal"))]
+        Compression::MetalIoLz4 => {
+            panic!("Metal IO compressed assets can only be read with the `metal` feature")
+        }
+        Compression::PortableLz4 => (0..level_count)
+            .map(|level| container::read_file(asset_dir.join(level_filename(slice, level))))
+            .collect(),
+    };
+    let bytes_per_pixel = metadata.pixel_format.bytes_per_pixel();
+    for (level, (bytes, (width, height))) in
+        levels.iter().zip(metadata.mip_level_sizes()).enumerate()
+    {
+        assert_eq!(
+            bytes.len(),
+            width * height * bytes_per_pixel,
+            "Asset's {slice} mip level {level} is invalid (size)"
+        );
+    }
+    levels
+}
+
+// Same as `read_slice_level_bytes()`, but as RGBA images. Only supports `PixelFormat::Rgba8Unorm`
+// assets.
+pub(crate) fn read_slice_levels(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+) -> Vec<Image<u8>> {
+    assert_eq!(
+        metadata.pixel_format,
+        PixelFormat::Rgba8Unorm,
+        "Unsupported asset's pixel format ({:?})",
+        asset_dir
+    );
+    read_slice_level_bytes(asset_dir, slice, metadata)
+        .into_iter()
+        .zip(metadata.mip_level_sizes())
+        .map(|(bytes, (width, height))| Image::new(width, height, bytes))
+        .collect()
+}
+
+// Same as `read_slice_levels()`, but only reads the first (full size) mip level.
+pub(crate) fn read_slice_first_level(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+) -> Image<u8> {
+    assert_eq!(
+        metadata.pixel_format,
+        PixelFormat::Rgba8Unorm,
+        "Unsupported asset's pixel format ({:?})",
+        asset_dir
+    );
+    Image::new(
+        metadata.width as _,
+        metadata.height as _,
+        read_slice_first_level_bytes(asset_dir, slice, metadata),
+    )
+}
+
+// Loads `slice`'s first `level_count` mip levels into a temporary CPU accessible texture with a Metal
+// IO command queue, then copies them out.
+#[cfg(feature = "metal")]
+fn read_metal_io_slice_levels(
+    asset_dir: &Path,
+    slice: &str,
+    metadata: &Metadata,
+    level_count: usize,
+) -> Vec<Vec<u8>> {
+    let device = Device::system_default().expect("Failed to access a Metal Device");
+    let texture = {
+        let desc = TextureDescriptor::new();
+        desc.set_pixel_format(metadata.pixel_format.mtl_pixel_format());
+        desc.set_texture_type(MTLTextureType::D2);
+        desc.set_resource_options(MTLResourceOptions::StorageModeShared);
+        desc.set_usage(MTLTextureUsage::ShaderRead);
+        desc.set_width(metadata.width as _);
+        desc.set_height(metadata.height as _);
+        desc.set_mipmap_level_count(metadata.mip_level_count as _);
+        device.new_texture(&desc)
+    };
+    let loads = asset_level_loads(&texture, asset_dir, metadata, &[slice]);
+    load_metal_io_levels(
+        &device,
+        &loads
+            .iter()
+            .filter(|load| load.level < level_count)
+            .collect::<Vec<_>>(),
+    );
+    let bytes_per_pixel = metadata.pixel_format.bytes_per_pixel();
+    metadata
+        .mip_level_sizes()
+        .into_iter()
+        .take(level_count)
+        .enumerate()
+        .map(|(level, (width, height))| {
+            let bytes_per_row = width * bytes_per_pixel;
+            let mut bytes = vec![0; height * bytes_per_row];
+            texture.get_bytes(
+                bytes.as_mut_ptr() as _,
+                bytes_per_row as _,
+                MTLRegion {
+                    origin: MTLOrigin { x: 0, y: 0, z: 0 },
+                    size: MTLSize {
+                        width: width as _,
+                        height: height as _,
+                        depth: 1,
+                    },
+                },
+                level as _,
+            );
+            bytes
+        })
+        .collect()
+}
+
+// A mip level of a texture's slice, loaded from an asset file.
+#[cfg(feature = "metal")]
+pub(crate) struct LevelLoad<'a> {
+    texture: &'a TextureRef,
+    slice: usize,
+    level: usize,
+    size: (usize, usize),
+    bytes_per_pixel: usize,
+    compression: Compression,
+    file: PathBuf,
+}
+
+// Every mip level of every slice (ordered like the texture's slices, ex. `CUBE_FACE_ORDER`) of an
+// asset.
+#[cfg(feature = "metal")]
+pub(crate) fn asset_level_loads<'a>(
+    texture: &'a TextureRef,
+    asset_dir: &Path,
+    metadata: &Metadata,
+    slices: &[&str],
+) -> Vec<LevelLoad<'a>> {
+    assert!(
+        texture.width() == metadata.width as _
+            && texture.height() == metadata.height as _
+            && texture.mipmap_level_count() == metadata.mip_level_count as _,
+        "Texture does not match the asset's dimensions ({:?})",
+        asset_dir
+    );
+    let mip_level_sizes = metadata.mip_level_sizes();
+    let mut loads = Vec::with_capacity(slices.len() * mip_level_sizes.len());
+    for (slice, slice_name) in slices.iter().enumerate() {
+        for (level, &size) in mip_level_sizes.iter().enumerate() {
+            loads.push(LevelLoad {
+                texture,
+                slice,
+                level,
+                size,
+                bytes_per_pixel: metadata.pixel_format.bytes_per_pixel(),
+                compression: metadata.compression,
+                file: asset_dir.join(level_filename(slice_name, level)),
+            });
+        }
+    }
+    loads
+}
+
+// Loads Metal IO compressed levels directly into their textures with a Metal IO command queue.
+#[cfg(feature = "metal")]
+fn load_metal_io_levels(device: &Device, loads: &[&LevelLoad]) {
+    let queue = device
+        .new_io_command_queue(&IOCommandQueueDescriptor::new())
+        .expect("Failed to create IO Command Queue");
+    let command_buffer = queue.new_command_buffer();
+    for load in loads {
+        let handle = device
+            .new_io_handle(
+                URL::new_with_string(&format!("file:///{}", load.file.to_string_lossy())),
+                COMPRESSION_METHOD,
+            )
+            .expect("Failed to get IO file handle");
+        let (width, height) = (load.size.0 as u64, load.size.1 as u64);
+        let source_bytes_per_row = width * load.bytes_per_pixel as u64;
+        command_buffer.load_texture(
+            load.texture,
+            load.slice as _,
+            load.level as _,
+            MTLSize {
+                width,
+                height,
+                depth: 1,
+            },
+            source_bytes_per_row,
+            height * source_bytes_per_row,
+            MTLOrigin { x: 0, y: 0, z: 0 },
+            &handle,
+            0,
+        );
+    }
+    command_buffer.commit();
+    command_buffer.wait_until_completed();
+    assert_eq!(
+        command_buffer.status(),
+        MTLIOStatus::complete,
+        "Failed to load texture asset."
+    );
+}
+
+// Decompresses portable levels on the CPU and blits them into their textures.
+#[cfg(feature = "metal")]
+fn load_portable_levels(device: &Device, loads: &[&LevelLoad]) {
+    let command_queue = device.new_command_queue();
+    let command_buffer = command_queue.new_command_buffer();
+    let encoder = command_buffer.new_blit_command_encoder();
+    // Kept alive until the blits complete.
+    let mut staging_buffers = Vec::with_capacity(loads.len());
+    for load in loads {
+        let bytes = container::read_file(&load.file);
+        let (width, height) = (load.size.0 as u64, load.size.1 as u64);
+        let source_bytes_per_row = width * load.bytes_per_pixel as u64;
+        assert_eq!(
+            bytes.len() as u64,
+            height * source_bytes_per_row,
+            "Texture asset's file is invalid (size): {:?}",
+            load.file
+        );
+        let buffer = device.new_buffer_with_data(
+            bytes.as_ptr() as _,
+            bytes.len() as _,
+            MTLResourceOptions::StorageModeShared,
+        );
+        encoder.copy_from_buffer_to_texture(
+            &buffer,
+            0,
+            source_bytes_per_row,
+            height * source_bytes_per_row,
+            MTLSize {
+                width,
+                height,
+                depth: 1,
+            },
+            load.texture,
+            load.slice as _,
+            load.level as _,
+            MTLOrigin { x: 0, y: 0, z: 0 },
+            MTLBlitOption::empty(),
+        );
+        staging_buffers.push(buffer);
+    }
+    encoder.end_encoding();
+    command_buffer.commit();
+    command_buffer.wait_until_completed();
+    assert_eq!(
+        command_buffer.status(),
+        MTLCommandBufferStatus::Completed,
+        "Failed to load texture asset."
+    );
+}
+
+// Loads all `loads` (possibly from different assets and textures), batching them by compression.
+#[cfg(feature = "metal")]
+pub(crate) fn load_levels(device: &Device, loads: &[LevelLoad]) {
+    let (metal_io_loads, portable_loads): (Vec<&LevelLoad>, Vec<&LevelLoad>) = loads
+        .iter()
+        .partition(|load| load.compression == Compression::MetalIoLz4);
+    if !metal_io_loads.is_empty() {
+        load_metal_io_levels(device, &metal_io_loads);
+    }
+    if !portable_loads.is_empty() {
+        load_portable_levels(device, &portable_loads);
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Rule {
+    pub targets: Vec<PathBuf>,
+    pub dependencies: Vec<PathBuf>,
+}
+
+#[derive(Default)]
+struct Parser {
+    rules: Vec<Rule>,
+    targets: Option<Vec<PathBuf>>,
+    words: Vec<PathBuf>,
+    word: String,
+}
+
+impl Parser {
+    fn end_word(&mut self) {
+        if !self.word.is_empty() {
+            let word = std::mem::take(&mut self.word);
+            // Order-only prerequisites separator (ex. `target: normal-deps | order-only-deps`)
+            if word != "|" {
+                self.words.push(PathBuf::from(word));
+            }
+        }
+    }
+
+    fn end_targets(&mut self) {
+        self.end_word();
+        self.targets = Some(std::mem::take(&mut self.words));
+    }
+
+    fn end_rule(&mut self) {
+        self.end_word();
+        let dependencies = std::mem::take(&mut self.words);
+        // Lines without a `:` (ex. blank lines) are not rules.
+        if let Some(targets) = self.targets.take() {
+            self.rules.push(Rule {
+                targets,
+                dependencies,
+            });
+        }
+    }
+}
+
+// Parses a Make-style dependency file (ex. generated by the Metal compiler using `-MM`, `-MMD`, or
+// `-MF`).
+//
+// Supports:
+// - Multiple rules, each with multiple targets (`a.o b.o: a.c a.h`)
+// - Line continuations (trailing `\`)
+// - Escaped spaces (`\ `), hashes (`\#`), and dollar signs (`$$`) in paths
+// - Comments (`# ...`)
+pub fn parse_depfile(contents: &str) -> Vec<Rule> {
+    let contents = contents.replace("\r\n", "\n");
+    let mut parser = Parser::default();
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let mut num_backslashes = 1;
+                while chars.next_if_eq(&'\\').is_some() {
+                    num_backslashes += 1;
+                }
+                match chars.peek() {
+                    // Backslashes preceding a space or hash are themselves escaped (`\\` -> `\`).
+                    // An odd number of backslashes escapes the space or hash.
+                    Some(' ' | '\t' | '#') => {
+                        for _ in 0..(num_backslashes / 2) {
+                            parser.word.push('\\');
+                        }
+                        if num_backslashes % 2 == 1 {
+                            parser.word.push(chars.next().unwrap());
+                        }
+                    }
+                    // Line continuation
+                    Some('\n') if num_backslashes % 2 == 1 => {
+                        for _ in 0..(num_backslashes - 1) {
+                            parser.word.push('\\');
+                        }
+                        chars.next();
+                        parser.end_word();
+                    }
+                    _ => {
+                        for _ in 0..num_backslashes {
+                            parser.word.push('\\');
+                        }
+                    }
+                }
+            }
+            '$' if chars.next_if_eq(&'$').is_some() => parser.word.push('$'),
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '\n' => parser.end_rule(),
+            ' ' | '\t' => parser.end_word(),
+            ':' if parser.targets.is_none()
+                && matches!(chars.peek(), None | Some(' ' | '\t' | '\n')) =>
+            {
+                parser.end_targets()
+            }
+            c => parser.word.push(c),
+        }
+    }
+    parser.end_rule();
+    parser.rules
+}
+
+// Returns the unique, canonicalized dependencies of all rules in a Make-style dependency file.
+// Module maps (ex. `include/metal/module.modulemap`) are excluded, as they are implicitly added by the
+// compiler and are not sources.
+//
+// IMPORTANT: Relative paths are resolved against the current working directory. This must match the
+// working directory of the compiler that generated the dependency file.
+pub fn dependencies(depfile_contents: &str) -> Vec<PathBuf> {
+    let mut deps = vec![];
+    for dep in parse_depfile(depfile_contents)
+        .into_iter()
+        .flat_map(|rule| rule.dependencies)
+        .filter(|dep| dep.extension().is_none_or(|ext| ext != "modulemap"))
+    {
+        let dep = dep
+            .canonicalize()
+            .unwrap_or_else(|e| panic!("Failed to canonicalize path to dependency {dep:?}: {e}"));
+        if !deps.contains(&dep) {
+            deps.push(dep);
+        }
+    }
+    deps
+}
+
+// Same as `dependencies()`, but reads the dependency file at `depfile_path`.
+pub fn read_dependencies<P: AsRef<Path>>(depfile_path: P) -> Vec<PathBuf> {
+    let depfile_path = depfile_path.as_ref();
+    dependencies(
+        &std::fs::read_to_string(depfile_path)
+            .unwrap_or_else(|e| panic!("Failed to read dependency file {depfile_path:?}: {e}")),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rule(targets: &[&str], dependencies: &[&str]) -> Rule {
+        Rule {
+            targets: targets.iter().map(PathBuf::from).collect(),
+            dependencies: dependencies.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_depfile() {
+        pretty_assertions::assert_eq!(
+            parse_depfile(
+                "\
+dependencies: /a/shaders.metal \\
+  /a/shader_bindings.h \\
+  /b/geometry.h
+"
+            ),
+            vec![rule(
+                &["dependencies"],
+                &["/a/shaders.metal", "/a/shader_bindings.h", "/b/geometry.h"]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_depfile_dependencies_on_first_line() {
+        pretty_assertions::assert_eq!(
+            parse_depfile("shaders.air: shaders.metal shader_bindings.h"),
+            vec![rule(
+                &["shaders.air"],
+                &["shaders.metal", "shader_bindings.h"]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_depfile_escapes() {
+        pretty_assertions::assert_eq!(
+            parse_depfile(
+                "\
+shaders.air: /My\\ Projects/shaders.metal \\
+  /My\\ Projects/\\#1.h /cost$$.h \\
+  /back\\\\slash.h /trailing\\\\\\ space.h C:\\dir\\file.h
+"
+            ),
+            vec![rule(
+                &["shaders.air"],
+                &[
+                    "/My Projects/shaders.metal",
+                    "/My Projects/#1.h",
+                    "/cost$.h",
+                    "/back\\\\slash.h",
+                    "/trailing\\ space.h",
+                    "C:\\dir\\file.h",
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_depfile_multiple_targets_and_rules() {
+        pretty_assertions::assert_eq!(
+            parse_depfile(
+                "\
+# Generated by the Metal compiler
+a.air b.air: a.metal \\
+  common.h
+\r
+b.air: b.h | order-only.h # trailing comment
+"
+            ),
+            vec![
+                rule(&["a.air", "b.air"], &["a.metal", "common.h"]),
+                rule(&["b.air"], &["b.h", "order-only.h"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .canonicalize()
+            .expect("Failed to canonicalize path to build-hash directory");
+        pretty_assertions::assert_eq!(
+            dependencies(
+                "\
+dependencies: Cargo.toml src/lib.rs \\
+  ./Cargo.toml /does/not/exist/include/metal/module.modulemap
+"
+            ),
+            vec![
+                manifest_dir.join("Cargo.toml"),
+                manifest_dir.join("src").join("lib.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_depfile_empty() {
+        pretty_assertions::assert_eq!(parse_depfile(""), vec![]);
+        pretty_assertions::assert_eq!(parse_depfile("\n\n# comment\n"), vec![]);
+        pretty_assertions::assert_eq!(parse_depfile("target:"), vec![rule(&["target"], &[])]);
+    }
+}
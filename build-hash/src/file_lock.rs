@@ -0,0 +1,115 @@
+use std::path::Path;
+#[cfg(not(unix))]
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::{fs::File, os::unix::io::AsRawFd};
+
+// Exclusive lock on a file, released when dropped.
+//
+// Used to serialize concurrent builds (ex. multiple projects' build scripts generating the same
+// outputs under `metal-types/src`), so only one build checks, generates, and saves at a time.
+//
+// On Unix this is an advisory `flock(2)` on the file itself. Elsewhere, a sibling `<path>.lock` file is
+// created exclusively (`create_new`) and removed when dropped. If a build is killed while holding it,
+// delete the `.lock` file to recover.
+#[cfg(unix)]
+pub struct FileLock(File);
+
+#[cfg(not(unix))]
+pub struct FileLock(PathBuf);
+
+#[cfg(unix)]
+impl FileLock {
+    // Blocks until the lock is acquired. Creates the file if it does not exist.
+    pub fn lock<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            // Contains the manifest (see `build_hash_with_outputs()`), read after locking.
+            .truncate(false)
+            .open(path)
+            .unwrap_or_else(|e| panic!("Failed to open {} to lock: {e}", path.to_string_lossy()));
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                return Self(file);
+            }
+            let error = std::io::Error::last_os_error();
+            if error.kind() != std::io::ErrorKind::Interrupted {
+                panic!("Failed to lock {}: {error}", path.to_string_lossy());
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        unsafe {
+            libc::flock(self.0.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl FileLock {
+    // Blocks until the lock is acquired.
+    pub fn lock<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let lock_path = PathBuf::from(lock_path);
+        loop {
+            match std::fs::File::options()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(_) => return Self(lock_path),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                Err(e) => panic!("Failed to lock {}: {e}", path.to_string_lossy()),
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let path = std::env::temp_dir().join(format!("build-hash-lock-{}", std::process::id()));
+        let events = Arc::new(Mutex::new(vec![]));
+        let lock = FileLock::lock(&path);
+        let waiter = {
+            let (path, events) = (path.clone(), events.clone());
+            thread::spawn(move || {
+                let _lock = FileLock::lock(&path);
+                events.lock().unwrap().push("locked by waiter");
+            })
+        };
+        thread::sleep(Duration::from_millis(50));
+        events.lock().unwrap().push("unlocked");
+        drop(lock);
+        waiter.join().unwrap();
+        assert_eq!(*events.lock().unwrap(), ["unlocked", "locked by waiter"]);
+        // Only created on Unix, where the file itself is locked.
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,44 +1,423 @@
+pub mod depfile;
+pub mod file_lock;
+pub mod manifest;
+pub mod stable_hasher;
+
+use file_lock::FileLock;
+use manifest::{Change, FileHash, Manifest};
+use stable_hasher::{hash_bytes, StableHasher};
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::HashSet,
     hash::{Hash, Hasher},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-fn hash_files<P: AsRef<Path>>(paths_to_hash: &[P]) -> u64 {
-    let mut hasher = DefaultHasher::new();
+// Path of `path` relative to `base` (ex. `../metal-types/src/a.h`), or `path` as-is if either can
+// not be resolved or they have no common root.
+fn relative_path(base: &Path, path: &Path) -> String {
+    let (base, resolved_path) = match (base.canonicalize(), path.canonicalize()) {
+        (Ok(base), Ok(path)) => (base, path),
+        _ => return path.to_string_lossy().into_owned(),
+    };
+    let common = base
+        .components()
+        .zip(resolved_path.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return path.to_string_lossy().into_owned();
+    }
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    for c in resolved_path.components().skip(common) {
+        relative.push(c);
+    }
+    relative.to_string_lossy().into_owned()
+}
+
+fn manifest_dir(cached_hash_path: &Path) -> &Path {
+    match cached_hash_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+fn sorted_dir_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed to read directory {}: {e}", dir.to_string_lossy()))
+        .map(|entry| entry.expect("Failed to read directory entry").path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn hash_dir(hasher: &mut StableHasher, dir: &Path, relative_dir: &Path) {
+    for entry in sorted_dir_entries(dir) {
+        let relative_path = relative_dir.join(entry.file_name().unwrap());
+        relative_path.to_string_lossy().hash(hasher);
+        if entry.is_dir() {
+            hash_dir(hasher, &entry, &relative_path);
+        } else {
+            hash_path(&entry).unwrap().hash(hasher);
+        }
+    }
+}
+
+// Hash of a file's contents, or of all file names and contents within a directory (recursively).
+// Returns `None` if nothing exists at `path`.
+pub fn hash_path<P: AsRef<Path>>(path: P) -> Option<u64> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        let mut hasher = StableHasher::new();
+        hash_dir(&mut hasher, path, Path::new(""));
+        Some(hasher.finish())
+    } else {
+        std::fs::read(path).ok().map(|bytes| hash_bytes(&bytes))
+    }
+}
+
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+fn files_in_dir(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in sorted_dir_entries(dir) {
+        if entry.is_dir() {
+            files_in_dir(&entry, files);
+        } else {
+            files.push(entry);
+        }
+    }
+}
+
+// Expands directories (recursively) and glob patterns (ex. `assets/cubemap/*.png`) into the files they
+// contain or match. Directories (and the directory containing a glob pattern's matches) are watched,
+// so adding or removing files reruns the build script.
+fn expand_inputs(paths_to_hash: &[&dyn AsRef<Path>]) -> Vec<PathBuf> {
+    let mut files = vec![];
     for path in paths_to_hash {
-        std::fs::read(path).unwrap().hash(&mut hasher);
-        println!("cargo:rerun-if-changed={}", path.as_ref().to_string_lossy());
+        let path = path.as_ref();
+        if is_glob_pattern(path) {
+            let base_dir: PathBuf = path
+                .components()
+                .take_while(|c| !is_glob_pattern(c.as_ref()))
+                .collect();
+            println!("cargo:rerun-if-changed={}", base_dir.to_string_lossy());
+            let matches: Vec<PathBuf> = glob::glob(&path.to_string_lossy())
+                .unwrap_or_else(|e| panic!("Invalid glob pattern {}: {e}", path.to_string_lossy()))
+                .map(|entry| entry.expect("Failed to read glob pattern match"))
+                .filter(|entry| entry.is_file())
+                .collect();
+            assert!(
+                !matches.is_empty(),
+                "No files match input pattern {}",
+                path.to_string_lossy()
+            );
+            files.extend(matches);
+        } else if path.is_dir() {
+            println!("cargo:rerun-if-changed={}", path.to_string_lossy());
+            files_in_dir(path, &mut files);
+        } else {
+            files.push(path.to_owned());
+        }
     }
-    hasher.finish()
+    let mut seen = HashSet::new();
+    files.retain(|file| seen.insert(file.clone()));
+    files
 }
 
-fn read_cached_hash<P: AsRef<Path>>(cached_hash_path: P) -> Option<u64> {
-    println!(
-        "cargo:rerun-if-changed={}",
-        cached_hash_path.as_ref().to_string_lossy()
-    );
-    if let Ok(hash) = std::fs::read(cached_hash_path) {
-        return Some(u64::from_ne_bytes(hash.try_into().unwrap()));
+fn hash_inputs<K: Hash>(
+    cached_hash_path: &Path,
+    paths_to_hash: &[&dyn AsRef<Path>],
+    key: &K,
+) -> Manifest {
+    let manifest_dir = manifest_dir(cached_hash_path);
+    let inputs = expand_inputs(paths_to_hash)
+        .iter()
+        .map(|path| {
+            println!("cargo:rerun-if-changed={}", path.to_string_lossy());
+            FileHash {
+                path: relative_path(manifest_dir, path),
+                hash: hash_bytes(&std::fs::read(path).unwrap_or_else(|e| {
+                    panic!("Failed to read {} to hash: {e}", path.to_string_lossy())
+                })),
+            }
+        })
+        .collect();
+    let mut hasher = StableHasher::new();
+    key.hash(&mut hasher);
+    Manifest {
+        key: hasher.finish(),
+        inputs,
+        outputs: vec![],
     }
-    None
 }
 
-fn save_hash<P: AsRef<Path>>(hash: u64, cached_hash_path: P) {
-    std::fs::write(cached_hash_path.as_ref(), hash.to_ne_bytes()).unwrap();
+fn hash_outputs(cached_hash_path: &Path, outputs: &Outputs) -> Vec<(String, Option<u64>)> {
+    let manifest_dir = manifest_dir(cached_hash_path);
+    outputs
+        .paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+            (relative_path(manifest_dir, path), hash_path(path))
+        })
+        .collect()
 }
 
+// What to do when an output no longer matches what was generated (ex. hand-edited).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModifiedOutput {
+    // Regenerate, discarding any modifications.
+    Regenerate,
+    // Fail the build, for outputs that should never be modified (ex. "GENERATED FILE. DO NOT MODIFY.").
+    // Revert the modifications or delete the output to regenerate.
+    Error,
+}
+
+// Files or directories generated by the `build_hash()` callback. Outputs are regenerated if missing
+// or modified (see `ModifiedOutput`).
+pub struct Outputs<'a> {
+    pub paths: &'a [&'a dyn AsRef<Path>],
+    pub modified: ModifiedOutput,
+}
+
+impl<'a> Outputs<'a> {
+    pub const NONE: Outputs<'static> = Outputs {
+        paths: &[],
+        modified: ModifiedOutput::Regenerate,
+    };
+}
+
+// Runs `f` to generate outputs, unless the contents of `paths_to_hash` are unchanged since the last
+// run. `paths_to_hash` may be files, directories (all files within, recursively), or glob patterns
+// (ex. `assets/cubemap/*.png`).
+#[inline]
 pub fn build_hash<P: AsRef<Path>, F: FnOnce()>(
     cached_hash_path: P,
     paths_to_hash: &[&dyn AsRef<Path>],
     f: F,
 ) {
-    let current_hash = hash_files(paths_to_hash);
-    if let Some(old_hash) = read_cached_hash(&cached_hash_path) {
-        if old_hash == current_hash {
-            return;
+    build_hash_with_key(cached_hash_path, paths_to_hash, &(), f)
+}
+
+// Same as `build_hash()`, but `key` is also hashed. Use this when the output depends on more than the
+// contents of `paths_to_hash` (ex. build options).
+//
+// `cached_hash_path` stores a manifest of hashes for each input (see `manifest::Manifest`). When
+// regenerating, the reasons (ex. which inputs changed) are logged to the build script output
+// (`cargo build -vv` or `target/*/build/*/output`).
+pub fn build_hash_with_key<P: AsRef<Path>, K: Hash, F: FnOnce()>(
+    cached_hash_path: P,
+    paths_to_hash: &[&dyn AsRef<Path>],
+    key: &K,
+    f: F,
+) {
+    build_hash_with_outputs(cached_hash_path, paths_to_hash, Outputs::NONE, key, f)
+}
+
+// Same as `build_hash_with_key()`, but also regenerates when `outputs` are missing or modified. Output
+// hashes are recorded in the manifest after `f` runs.
+//
+// Checking, generating, and saving the manifest is guarded by an advisory file lock on
+// `cached_hash_path`, so concurrent builds sharing outputs wait for each other instead of
+// interleaving writes.
+pub fn build_hash_with_outputs<P: AsRef<Path>, K: Hash, F: FnOnce()>(
+    cached_hash_path: P,
+    paths_to_hash: &[&dyn AsRef<Path>],
+    outputs: Outputs,
+    key: &K,
+    f: F,
+) {
+    let cached_hash_path = cached_hash_path.as_ref();
+    println!(
+        "cargo:rerun-if-changed={}",
+        cached_hash_path.to_string_lossy()
+    );
+    for path in outputs.paths {
+        println!("cargo:rerun-if-changed={}", path.as_ref().to_string_lossy());
+    }
+    let _lock = FileLock::lock(cached_hash_path);
+    let mut manifest = hash_inputs(cached_hash_path, paths_to_hash, key);
+    let previous = Manifest::read(cached_hash_path);
+    let mut changes = manifest.changes(previous.as_ref());
+    if let Some(previous) = &previous {
+        changes.extend(previous.output_changes(&hash_outputs(cached_hash_path, &outputs)));
+    }
+    if changes.is_empty() {
+        return;
+    }
+    if outputs.modified == ModifiedOutput::Error {
+        if let Some(Change::OutputModified(path)) = changes
+            .iter()
+            .find(|c| matches!(c, Change::OutputModified(_)))
+        {
+            panic!(
+                "Generated output {path} (relative to {}) was modified. Revert the modifications or delete it to regenerate.",
+                manifest_dir(cached_hash_path).to_string_lossy()
+            );
         }
     }
+    for change in &changes {
+        println!(
+            "build-hash: {} {change}",
+            cached_hash_path.to_string_lossy()
+        );
+    }
     f();
-    save_hash(current_hash, &cached_hash_path);
+    manifest.outputs = hash_outputs(cached_hash_path, &outputs)
+        .into_iter()
+        .map(|(path, hash)| FileHash {
+            hash: hash.unwrap_or_else(|| panic!("Expected output {path} to be generated")),
+            path,
+        })
+        .collect();
+    manifest.write(cached_hash_path);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_hash_regenerates_on_change() {
+        let dir = std::env::temp_dir().join(format!("build-hash-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("inputs")).unwrap();
+        let input = dir.join("inputs").join("a.txt");
+        let cached_hash_path = dir.join("hash");
+        std::fs::write(&input, "a").unwrap();
+
+        let mut generated = 0;
+        let mut build =
+            |key: u32| build_hash_with_key(&cached_hash_path, &[&input], &key, || generated += 1);
+        build(0);
+        build(0);
+        std::fs::write(&input, "b").unwrap();
+        build(0);
+        build(1);
+        assert_eq!(generated, 3);
+
+        let manifest = Manifest::read(&cached_hash_path).unwrap();
+        assert_eq!(
+            manifest.inputs,
+            vec![FileHash {
+                path: Path::new("inputs")
+                    .join("a.txt")
+                    .to_string_lossy()
+                    .into_owned(),
+                hash: hash_bytes(b"b"),
+            }]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_hash_regenerates_on_output_change() {
+        let dir =
+            std::env::temp_dir().join(format!("build-hash-test-outputs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("a.txt");
+        let output = dir.join("out");
+        let cached_hash_path = dir.join("hash");
+        std::fs::write(&input, "a").unwrap();
+
+        let generated = std::cell::Cell::new(0);
+        let build = |modified: ModifiedOutput| {
+            build_hash_with_outputs(
+                &cached_hash_path,
+                &[&input],
+                Outputs {
+                    paths: &[&output],
+                    modified,
+                },
+                &(),
+                || {
+                    generated.set(generated.get() + 1);
+                    std::fs::create_dir_all(output.join("sub")).unwrap();
+                    std::fs::write(output.join("sub").join("b.txt"), "generated").unwrap();
+                },
+            )
+        };
+        build(ModifiedOutput::Regenerate);
+        build(ModifiedOutput::Regenerate);
+        assert_eq!(generated.get(), 1);
+
+        std::fs::remove_dir_all(&output).unwrap();
+        build(ModifiedOutput::Regenerate);
+        assert_eq!(generated.get(), 2);
+
+        std::fs::write(output.join("sub").join("b.txt"), "modified").unwrap();
+        build(ModifiedOutput::Regenerate);
+        assert_eq!(generated.get(), 3);
+
+        std::fs::write(output.join("sub").join("c.txt"), "added").unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            build(ModifiedOutput::Error)
+        }));
+        assert!(result.is_err());
+        assert_eq!(generated.get(), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_hash_directory_and_glob_inputs() {
+        let dir =
+            std::env::temp_dir().join(format!("build-hash-test-inputs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("textures").join("nested")).unwrap();
+        std::fs::create_dir_all(dir.join("cubemap")).unwrap();
+        for path in [
+            "textures/b.png",
+            "textures/nested/a.png",
+            "cubemap/posx.png",
+            "cubemap/negx.png",
+            "cubemap/readme.txt",
+        ] {
+            std::fs::write(dir.join(path), path).unwrap();
+        }
+        let cached_hash_path = dir.join("hash");
+        let textures_dir = dir.join("textures");
+        let cubemap_pattern = dir.join("cubemap").join("*.png");
+
+        let generated = std::cell::Cell::new(0);
+        let build = || {
+            build_hash(
+                &cached_hash_path,
+                &[&textures_dir, &cubemap_pattern],
+                || generated.set(generated.get() + 1),
+            )
+        };
+        build();
+        let paths: Vec<String> = Manifest::read(&cached_hash_path)
+            .unwrap()
+            .inputs
+            .into_iter()
+            .map(|input| input.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "textures/b.png",
+                "textures/nested/a.png",
+                "cubemap/negx.png",
+                "cubemap/posx.png",
+            ]
+        );
+
+        build();
+        std::fs::write(dir.join("cubemap").join("readme.txt"), "changed").unwrap();
+        build();
+        assert_eq!(generated.get(), 1);
+
+        std::fs::write(dir.join("textures").join("nested").join("c.png"), "c").unwrap();
+        build();
+        std::fs::write(dir.join("cubemap").join("posy.png"), "posy").unwrap();
+        build();
+        assert_eq!(generated.get(), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,262 @@
+use std::{fmt::Display, path::Path};
+
+const HEADER: &str = "build-hash manifest v1 (FNV-1a 64)";
+
+// Hash of an input or output's contents, see `hash_path()`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FileHash {
+    // Path relative to the manifest's directory (if possible), so manifests are portable across
+    // machines (ex. different checkout locations).
+    pub path: String,
+    pub hash: u64,
+}
+
+// Records the hashes of all inputs (and the key) used to generate a build's outputs, and the hashes of
+// the outputs after they were generated.
+//
+// Stored as text, one entry per line:
+//
+//   build-hash manifest v1 (FNV-1a 64)
+//   key 0123456789abcdef
+//   input 0123456789abcdef src/shader_bindings.h
+//   input 0123456789abcdef ../metal-types/src/rust_bindgen_only_metal_types.h
+//   output 0123456789abcdef src/shader_bindings.rs
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Manifest {
+    pub key: u64,
+    pub inputs: Vec<FileHash>,
+    pub outputs: Vec<FileHash>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Change {
+    // No previous manifest or the previous manifest could not be parsed (ex. older format).
+    NoManifest,
+    Key,
+    Added(String),
+    Removed(String),
+    Modified(String),
+    OutputMissing(String),
+    // Output contents differ from when they were generated (ex. hand-edited).
+    OutputModified(String),
+    // Output was not recorded by the previous manifest (ex. newly declared output).
+    OutputUnrecorded(String),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::NoManifest => write!(f, "no previous manifest"),
+            Change::Key => write!(f, "key changed"),
+            Change::Added(path) => write!(f, "input added: {path}"),
+            Change::Removed(path) => write!(f, "input removed: {path}"),
+            Change::Modified(path) => write!(f, "input changed: {path}"),
+            Change::OutputMissing(path) => write!(f, "output missing: {path}"),
+            Change::OutputModified(path) => write!(f, "output modified: {path}"),
+            Change::OutputUnrecorded(path) => write!(f, "output not recorded: {path}"),
+        }
+    }
+}
+
+fn parse_hash(s: &str) -> Option<u64> {
+    u64::from_str_radix(s, 16).ok()
+}
+
+impl Manifest {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut lines = s.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let key = parse_hash(lines.next()?.strip_prefix("key ")?)?;
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for line in lines {
+            let (kind, line) = line.split_once(' ')?;
+            let (hash, path) = line.split_once(' ')?;
+            let file_hash = FileHash {
+                path: path.to_owned(),
+                hash: parse_hash(hash)?,
+            };
+            match kind {
+                "input" => inputs.push(file_hash),
+                "output" => outputs.push(file_hash),
+                _ => return None,
+            }
+        }
+        Some(Self {
+            key,
+            inputs,
+            outputs,
+        })
+    }
+
+    pub fn read<P: AsRef<Path>>(manifest_path: P) -> Option<Self> {
+        std::fs::read_to_string(manifest_path)
+            .ok()
+            .and_then(|s| Self::parse(&s))
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, manifest_path: P) {
+        let manifest_path = manifest_path.as_ref();
+        std::fs::write(manifest_path, self.to_string()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to write build-hash manifest {}: {e}",
+                manifest_path.to_string_lossy()
+            )
+        });
+    }
+
+    // Lists everything that differs from `previous`, in input order (removed inputs last).
+    pub fn changes(&self, previous: Option<&Manifest>) -> Vec<Change> {
+        let previous = match previous {
+            Some(previous) => previous,
+            None => return vec![Change::NoManifest],
+        };
+        let mut changes = vec![];
+        if self.key != previous.key {
+            changes.push(Change::Key);
+        }
+        for input in &self.inputs {
+            match previous.inputs.iter().find(|i| i.path == input.path) {
+                Some(prev) if prev.hash == input.hash => {}
+                Some(_) => changes.push(Change::Modified(input.path.clone())),
+                None => changes.push(Change::Added(input.path.clone())),
+            }
+        }
+        for prev in &previous.inputs {
+            if !self.inputs.iter().any(|i| i.path == prev.path) {
+                changes.push(Change::Removed(prev.path.clone()));
+            }
+        }
+        changes
+    }
+
+    // Lists outputs that are missing or differ from the outputs recorded by `self`. `outputs` are the
+    // declared outputs' paths and current hashes (`None` if missing).
+    pub fn output_changes(&self, outputs: &[(String, Option<u64>)]) -> Vec<Change> {
+        let mut changes = vec![];
+        for (path, hash) in outputs {
+            let recorded = self.outputs.iter().find(|o| &o.path == path);
+            match (hash, recorded) {
+                (None, _) => changes.push(Change::OutputMissing(path.clone())),
+                (Some(_), None) => changes.push(Change::OutputUnrecorded(path.clone())),
+                (Some(hash), Some(recorded)) if *hash != recorded.hash => {
+                    changes.push(Change::OutputModified(path.clone()))
+                }
+                _ => {}
+            }
+        }
+        changes
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "key {:016x}", self.key)?;
+        for FileHash { path, hash } in &self.inputs {
+            writeln!(f, "input {hash:016x} {path}")?;
+        }
+        for FileHash { path, hash } in &self.outputs {
+            writeln!(f, "output {hash:016x} {path}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn file(path: &str, hash: u64) -> FileHash {
+        FileHash {
+            path: path.to_owned(),
+            hash,
+        }
+    }
+
+    #[test]
+    fn test_to_string_and_parse() {
+        let manifest = Manifest {
+            key: 0xcbf29ce484222325,
+            inputs: vec![
+                file("src/shader_bindings.h", 1),
+                file("../metal-types/src/with space.h", u64::MAX),
+            ],
+            outputs: vec![file("src/shader_bindings.rs", 2)],
+        };
+        let s = manifest.to_string();
+        pretty_assertions::assert_eq!(
+            s,
+            "\
+build-hash manifest v1 (FNV-1a 64)
+key cbf29ce484222325
+input 0000000000000001 src/shader_bindings.h
+input ffffffffffffffff ../metal-types/src/with space.h
+output 0000000000000002 src/shader_bindings.rs
+"
+        );
+        assert_eq!(Manifest::parse(&s), Some(manifest));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(Manifest::parse(""), None);
+        // Previous format: a single native-endian u64
+        assert_eq!(Manifest::parse("\u{1}\0\0\0\0\0\0\0"), None);
+        assert_eq!(Manifest::parse(&format!("{HEADER}\nkey xyz\n")), None);
+        assert_eq!(
+            Manifest::parse(&format!("{HEADER}\nkey 0\nfile 0 a\n")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_changes() {
+        let previous = Manifest {
+            key: 0,
+            inputs: vec![file("a", 1), file("b", 2), file("c", 3)],
+            outputs: vec![],
+        };
+        assert_eq!(previous.changes(None), vec![Change::NoManifest]);
+        assert_eq!(previous.changes(Some(&previous)), vec![]);
+
+        let current = Manifest {
+            key: 1,
+            inputs: vec![file("c", 3), file("a", 10), file("d", 4)],
+            outputs: vec![],
+        };
+        assert_eq!(
+            current.changes(Some(&previous)),
+            vec![
+                Change::Key,
+                Change::Modified("a".to_owned()),
+                Change::Added("d".to_owned()),
+                Change::Removed("b".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_changes() {
+        let previous = Manifest {
+            key: 0,
+            inputs: vec![],
+            outputs: vec![file("a", 1), file("b", 2), file("c", 3)],
+        };
+        assert_eq!(
+            previous.output_changes(&[
+                ("a".to_owned(), Some(1)),
+                ("b".to_owned(), None),
+                ("c".to_owned(), Some(30)),
+                ("d".to_owned(), Some(4)),
+            ]),
+            vec![
+                Change::OutputMissing("b".to_owned()),
+                Change::OutputModified("c".to_owned()),
+                Change::OutputUnrecorded("d".to_owned()),
+            ]
+        );
+    }
+}
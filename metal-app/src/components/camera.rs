@@ -11,7 +11,6 @@ pub const DISTANCE_MOUSE_BUTTON: MouseButton = MouseButton::Right;
 const INITIAL_CAMERA_DISTANCE: f32 = 1.0;
 const N: f32 = 0.1;
 const F: f32 = 100000.0;
-const Z_RANGE: f32 = F - N;
 
 pub struct CameraUpdate {
     pub position_world: f32x4,
@@ -196,14 +195,9 @@ impl<const DRAG_SCALE: usize> Camera<DRAG_SCALE> {
 /// ```ignore
 /// m_orthographic * m_perspective;
 /// ```
+///
+/// See `f32x4x4::perspective()`.
 #[inline]
 pub fn calc_m_camera_to_projection(aspect_ratio: f32, fov: f32) -> f32x4x4 {
-    let sy = 1. / (fov / 2.).tan();
-    let sx = sy / aspect_ratio;
-    f32x4x4::new(
-        [sx, 0., 0., 0.],
-        [0., sy, 0., 0.],
-        [0., 0., F / Z_RANGE, -N * F / Z_RANGE],
-        [0., 0., 1., 0.],
-    )
+    f32x4x4::perspective(fov, aspect_ratio, N, F)
 }
@@ -13,6 +13,16 @@ bitflags! {
 }
 
 impl ShadingModeSelector {
+    // Every mode selectable by the user (see `on_event()`), useful to only precompile pipelines for
+    // these modes (see `metal_app::pipeline::PipelinePermutations::new_in_background()`).
+    pub const MODES: [Self; 5] = [
+        Self::DEFAULT,
+        Self::ONLY_NORMALS,
+        Self::HAS_AMBIENT,
+        Self::HAS_AMBIENT.union(Self::HAS_DIFFUSE),
+        Self::HAS_SPECULAR,
+    ];
+
     pub fn encode(
         &self,
         function_constants: FunctionConstantValues,
@@ -5,6 +5,7 @@ use crate::{
     typed_buffer::{TypedBuffer, TypedBufferSizer},
     MetalGPUAddress, DEFAULT_RESOURCE_OPTIONS,
 };
+use metal_types::Aabb;
 use std::{
     marker::PhantomData,
     ops::Deref,
@@ -16,6 +17,13 @@ pub struct MaxBounds {
     pub size: f32x4,
 }
 
+impl MaxBounds {
+    #[inline]
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_center_size(self.center, self.size)
+    }
+}
+
 pub struct GeometryToEncode {
     // TODO: Can we make this &str?
     pub name: String,
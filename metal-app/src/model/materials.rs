@@ -5,7 +5,18 @@ use crate::{
     typed_buffer::{TypedBuffer, TypedBufferSizer},
     MetalGPUAddress, DEFAULT_RESOURCE_OPTIONS,
 };
-use std::{collections::HashMap, ops::Deref, path::Path};
+use asset_compiler::{
+    metadata::{AssetKind, Metadata},
+    texture_2d::{
+        is_texture_2d_asset_dir_current, load_texture_2d_assets_into, texture_2d_asset_descriptor,
+        texture_asset_dir,
+    },
+};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
 type RGB32 = [f32; 3];
 
@@ -51,52 +62,71 @@ struct MaterialSource<'a> {
     texture_descriptor: TextureDescriptor,
     png_reader: Option<png::Reader<std::fs::File>>,
     load_texture_buffer_size: usize,
+    // Compiled texture asset (see `metal_build::build_model_texture_assets()`), loaded instead of
+    // decoding the PNG. Out of date assets (ex. PNG modified since the last build) are ignored.
+    asset_dir: Option<PathBuf>,
 }
 
 impl<'a> MaterialSource<'a> {
     fn new<'b, P: AsRef<Path>>(png_file_dir: P, key: MaterialSourceKey<'a>) -> Self {
-        let (width, height, load_texture_buffer_size, png_reader) = match key {
+        let mut asset_dir = None;
+        let (desc, load_texture_buffer_size, png_reader) = match key {
             MaterialSourceKey::PNG(png_file) => {
-                let decoder = png::Decoder::new(
-                    std::fs::File::open(png_file_dir.as_ref().join(png_file)).unwrap(),
-                );
-                let reader = decoder.read_info().unwrap();
-                let info = reader.info();
-                let load_texture_buffer_size = reader.output_buffer_size();
-                assert_eq!(
-                    info.color_type,
-                    png::ColorType::Rgba,
-                    r#"Unexpected PNG color format, expected RGBA.
+                let png_path = png_file_dir.as_ref().join(png_file);
+                let png_asset_dir = texture_asset_dir(&png_path);
+                if png_asset_dir.is_dir()
+                    && is_texture_2d_asset_dir_current(&png_asset_dir, &png_path)
+                {
+                    let desc = texture_2d_asset_descriptor(&Metadata::read(
+                        &png_asset_dir,
+                        AssetKind::Texture2D,
+                    ));
+                    asset_dir = Some(png_asset_dir);
+                    (desc, 0, None)
+                } else {
+                    let decoder = png::Decoder::new(std::fs::File::open(png_path).unwrap());
+                    let reader = decoder.read_info().unwrap();
+                    let info = reader.info();
+                    let load_texture_buffer_size = reader.output_buffer_size();
+                    assert_eq!(
+                        info.color_type,
+                        png::ColorType::Rgba,
+                        r#"Unexpected PNG color format, expected RGBA.
 Images with RGB color format (no alpha), can be preprocessed by running:
 > cargo run --bin png-add-alpha [path to image]
 "#
-                );
-                (
-                    info.width as _,
-                    info.height as _,
-                    load_texture_buffer_size,
-                    Some(reader),
-                )
+                    );
+                    (
+                        Self::rgba8_texture_descriptor(info.width as _, info.height as _),
+                        load_texture_buffer_size,
+                        Some(reader),
+                    )
+                }
             }
-            MaterialSourceKey::Color(_) => (1, 1, 0, None),
+            MaterialSourceKey::Color(_) => (Self::rgba8_texture_descriptor(1, 1), 0, None),
         };
-
-        let desc = TextureDescriptor::new();
-        desc.set_width(width);
-        desc.set_height(height);
-        desc.set_pixel_format(MTLPixelFormat::RGBA8Unorm);
         desc.set_storage_mode(MTLStorageMode::Shared);
-        desc.set_texture_type(MTLTextureType::D2);
-        desc.set_usage(MTLTextureUsage::ShaderRead);
         desc.set_resource_options(DEFAULT_RESOURCE_OPTIONS);
         Self {
             key,
             texture_descriptor: desc,
             png_reader,
             load_texture_buffer_size,
+            asset_dir,
         }
     }
 
+    #[inline]
+    fn rgba8_texture_descriptor(width: NSUInteger, height: NSUInteger) -> TextureDescriptor {
+        let desc = TextureDescriptor::new();
+        desc.set_width(width);
+        desc.set_height(height);
+        desc.set_pixel_format(MTLPixelFormat::RGBA8Unorm);
+        desc.set_texture_type(MTLTextureType::D2);
+        desc.set_usage(MTLTextureUsage::ShaderRead);
+        desc
+    }
+
     #[inline]
     fn size_and_padding(&self, device: &Device) -> (usize, usize) {
         let size_align = device.heap_texture_size_and_align(&self.texture_descriptor);
@@ -108,8 +138,18 @@ Images with RGB color format (no alpha), can be preprocessed by running:
 
     // `read_png_buffer` allows single allocation/deallocation of the temp buffer for reading
     // all a model's material PNG textures.
+    //
+    // Textures of compiled texture assets are only allocated, see `Materials::allocate_and_encode()`.
     #[inline]
     fn allocate_texture(&mut self, heap: &Heap, read_png_buffer: &mut [u8]) -> Texture {
+        if let (Some(asset_dir), MaterialSourceKey::PNG(png_file)) = (&self.asset_dir, self.key) {
+            let texture = heap
+                .new_texture(&self.texture_descriptor)
+                .expect(&format!("Failed to allocate texture for {asset_dir:?}"));
+            texture.set_label(png_file);
+            return texture;
+        }
+
         let tmp_color_label;
         let tmp_color_buf;
         let (buf, label): (&[u8], &str) = match self.key {
@@ -162,6 +202,7 @@ struct Material<'a> {
 
 pub struct Materials<'a, T: Sized + Copy + Clone> {
     arguments_sizer: TypedBufferSizer<T>,
+    device: Device,
     heap_size: usize,
     materials: Vec<Material<'a>>,
     max_load_texture_buffer_size: usize,
@@ -207,6 +248,7 @@ impl<'a, T: Sized + Copy + Clone> Materials<'a, T> {
         heap_size += arguments_sizer.heap_aligned_byte_size(device);
         Self {
             arguments_sizer,
+            device: device.to_owned(),
             heap_size,
             materials,
             max_load_texture_buffer_size,
@@ -257,6 +299,22 @@ impl<'a, T: Sized + Copy + Clone> Materials<'a, T> {
                 },
             );
         }
+
+        // Load all compiled texture assets at once.
+        let asset_textures: Vec<(&TextureRef, &Path)> = self
+            .sources
+            .iter()
+            .filter_map(|(key, source)| {
+                source
+                    .asset_dir
+                    .as_deref()
+                    .map(|asset_dir| (texture_cache[key].deref(), asset_dir))
+            })
+            .collect();
+        if !asset_textures.is_empty() {
+            load_texture_2d_assets_into(&self.device, &asset_textures);
+        }
+
         let textures = texture_cache.into_iter().map(|(_, tx)| tx).collect();
         MaterialResults {
             arguments_buffer,
@@ -14,35 +14,23 @@ pub enum AccelerationStructureUpdateStrategy {
     // Refit,
     Rebuild,
 }
-use metal_types::{f32x4x4, half3x3, TriNormals};
+use metal_types::{f32x4x4, half3x3, TriNormals, TriNormalsEncoding};
 use AccelerationStructureUpdateStrategy::*;
 
 const ACCELERATION_STRUCTURE_UPDATE_STRATEGY: AccelerationStructureUpdateStrategy = Rebuild;
 
-#[inline]
-fn to_half3x3(m: &MTLPackedFloat4x3) -> half3x3 {
-    half3x3 {
-        columns: [
-            [
-                half::f16::from_f32(m.columns[0].0).to_bits(),
-                half::f16::from_f32(m.columns[0].1).to_bits(),
-                half::f16::from_f32(m.columns[0].2).to_bits(),
-                half::f16::from_f32(0.).to_bits(),
-            ],
-            [
-                half::f16::from_f32(m.columns[1].0).to_bits(),
-                half::f16::from_f32(m.columns[1].1).to_bits(),
-                half::f16::from_f32(m.columns[1].2).to_bits(),
-                half::f16::from_f32(0.).to_bits(),
-            ],
-            [
-                half::f16::from_f32(m.columns[2].0).to_bits(),
-                half::f16::from_f32(m.columns[2].1).to_bits(),
-                half::f16::from_f32(m.columns[2].2).to_bits(),
-                half::f16::from_f32(0.).to_bits(),
-            ],
-        ],
-    }
+// Normal matrix, scaled so its largest component is 1. The normal matrix's components grow with
+// 1 / scale, overflowing half for small scales. Transformed normals are normalized (see
+// `interpolate_normal()` in tri_normals.h), so scaling keeps their direction.
+fn half_normal_matrix(m_model_to_world: f32x4x4) -> half3x3 {
+    let [c0, c1, c2, _] = m_model_to_world.normal_matrix().columns;
+    let columns = [c0, c1, c2].map(|[x, y, z, _]| [x, y, z]);
+    let max = columns
+        .iter()
+        .flatten()
+        .fold(0_f32, |max, v| max.max(v.abs()));
+    let inv_max = if max == 0. { 1. } else { 1. / max };
+    half3x3::from_columns(columns.map(|c| c.map(|v| v * inv_max)))
 }
 
 struct Draw {
@@ -74,7 +62,25 @@ impl ModelAccelerationStructure {
         Self::from_files(&[obj_file], device, cmd_queue, init_m_model_to_world)
     }
 
+    #[inline]
     pub fn from_files<P: AsRef<Path>>(
+        obj_files: &[P],
+        device: &DeviceRef,
+        cmd_queue: &CommandQueueRef,
+        init_m_model_to_world: impl FnMut(&MaxBounds, usize) -> f32x4x4,
+    ) -> Self {
+        Self::from_files_with_normals_encoding::<TriNormals, P>(
+            obj_files,
+            device,
+            cmd_queue,
+            init_m_model_to_world,
+        )
+    }
+
+    // Same as `from_files()`, but encodes normals (primitive data) with `N`.
+    // IMPORTANT: Shaders must read the primitive data using the matching Metal type (ex.
+    // `(device TriNormalsOct16 *) hit.primitive_data`).
+    pub fn from_files_with_normals_encoding<N: TriNormalsEncoding, P: AsRef<Path>>(
         obj_files: &[P],
         device: &DeviceRef,
         cmd_queue: &CommandQueueRef,
@@ -157,11 +163,10 @@ impl ModelAccelerationStructure {
                         });
                     },
                 );
-                let m_model_to_world: MTLPackedFloat4x3 =
-                    init_m_model_to_world(&geometry.max_bounds, i).into();
-                m_model_to_worlds[i] = m_model_to_world;
+                let m_model_to_world = init_m_model_to_world(&geometry.max_bounds, i);
+                m_model_to_worlds[i] = m_model_to_world.into();
                 m_normal_to_world_start_indices.push(m_normal_to_world_i);
-                let m_normal_to_world = to_half3x3(&m_model_to_world);
+                let m_normal_to_world = half_normal_matrix(m_model_to_world);
 
                 let normals = geometry_buffers.normals.get();
                 let indices = geometry_buffers.indices.get();
@@ -192,13 +197,12 @@ impl ModelAccelerationStructure {
                     {
                         // TODO: Create a *single* primitive data buffer
                         // - Should be able to use a TypedBufferSizer and allocate on the heap.
-                        let primitive_data_buffer: TypedBuffer<TriNormals> =
-                            TypedBuffer::with_capacity(
-                                "Normal Primitive Data",
-                                device.deref(),
-                                draw.triangle_count as _,
-                                MTLResourceOptions::StorageModeShared,
-                            );
+                        let primitive_data_buffer: TypedBuffer<N> = TypedBuffer::with_capacity(
+                            "Normal Primitive Data",
+                            device.deref(),
+                            draw.triangle_count as _,
+                            MTLResourceOptions::StorageModeShared,
+                        );
                         // TODO: How "bad" (<0 or >1) are the incoming normals?
                         // - More UNorm-able normals should improve performance (use half precision
                         //   arithmetic) and correctness (less error)
@@ -210,7 +214,7 @@ impl ModelAccelerationStructure {
                         let primitive_data = primitive_data_buffer.get_mut();
                         for i in 0..(draw.triangle_count as usize) {
                             primitive_data[i] =
-                                TriNormals::from_indexed_raw_normals(raw_normals, raw_indices, i);
+                                N::from_indexed_raw_normals(raw_normals, raw_indices, i);
                         }
                         tri_as_desc.set_primitive_data_buffer(Some(&primitive_data_buffer.raw));
                         tri_as_desc.set_primitive_data_buffer_offset(0);
@@ -312,8 +316,8 @@ impl ModelAccelerationStructure {
         model_to_world_matrix: f32x4x4,
         cmd_queue: &CommandQueueRef,
     ) {
-        let m = model_to_world_matrix.into();
-        self.m_model_to_worlds_buffer.get_mut()[i] = m;
+        self.m_model_to_worlds_buffer.get_mut()[i] = model_to_world_matrix.into();
+        let m_normal_to_world = half_normal_matrix(model_to_world_matrix);
         let m_normal_to_worlds = self.m_normal_to_worlds_buffer.get_mut();
         let start = self.m_normal_to_world_start_indices[i];
         let len = m_normal_to_worlds.len();
@@ -322,7 +326,7 @@ impl ModelAccelerationStructure {
             .get(i + 1)
             .unwrap_or(&len);
         for n_i in start..end {
-            m_normal_to_worlds[n_i] = to_half3x3(&m);
+            m_normal_to_worlds[n_i] = m_normal_to_world;
         }
         self.update(cmd_queue);
     }
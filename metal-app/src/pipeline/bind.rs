@@ -1,6 +1,10 @@
-use super::pipeline_function::PipelineFunctionType;
+use super::{
+    function_table::{IntersectionFunctionTableRef, VisibleFunctionTableRef},
+    pipeline_function::PipelineFunctionType,
+};
 use crate::typed_buffer::TypedBuffer;
 use metal::{AccelerationStructureRef, TextureRef};
+use metal_types::MetalLayout;
 
 /*
 TODO: Consider optimizing Binding API for consistent Bind Variant usage
@@ -46,12 +50,12 @@ impl<'a, T: Sized + Copy + Clone> AnyBind<T> for BindBuffer<'a, T> {
 }
 
 #[derive(Copy, Clone)]
-pub enum Bind<'a, T: Sized + Copy + Clone> {
+pub enum Bind<'a, T: MetalLayout> {
     Value(&'a T),
     Buffer(BindBuffer<'a, T>),
     Skip,
 }
-impl<'a, T: Sized + Copy + Clone> AnyBind<T> for Bind<'a, T> {
+impl<'a, T: MetalLayout> AnyBind<T> for Bind<'a, T> {
     #[inline]
     fn bind<F: PipelineFunctionType>(self, encoder: &F::CommandEncoder, index: usize) {
         use Bind::*;
@@ -63,12 +67,12 @@ impl<'a, T: Sized + Copy + Clone> AnyBind<T> for Bind<'a, T> {
     }
 }
 
-pub enum BindMany<'a, T: Sized + Copy + Clone> {
+pub enum BindMany<'a, T: MetalLayout> {
     Values(&'a [T]),
     Buffer(BindBuffer<'a, T>),
     Skip,
 }
-impl<'a, T: Sized + Copy + Clone> AnyBind<T> for BindMany<'a, T> {
+impl<'a, T: MetalLayout> AnyBind<T> for BindMany<'a, T> {
     #[inline]
     fn bind<F: PipelineFunctionType>(self, encoder: &F::CommandEncoder, index: usize) {
         use BindMany::*;
@@ -82,7 +86,7 @@ impl<'a, T: Sized + Copy + Clone> AnyBind<T> for BindMany<'a, T> {
 
 macro_rules! impl_bind_buffer_helpers {
     ($bind_ident:ident) => {
-        impl<'a, T: Sized + Copy + Clone> $bind_ident<'a, T> {
+        impl<'a, T: MetalLayout> $bind_ident<'a, T> {
             #[inline(always)]
             pub fn buffer(buffer: &'a TypedBuffer<T>) -> Self {
                 Self::buffer_and_offset(buffer, 0)
@@ -169,3 +173,55 @@ impl<'a> BindAccelerationStructure<'a> {
         }
     }
 }
+
+pub enum BindIntersectionFunctionTable<'a> {
+    IntersectionFunctionTable(&'a IntersectionFunctionTableRef),
+    Null,
+    Skip,
+}
+
+#[allow(non_snake_case)]
+pub fn BindIntersectionFunctionTable<'a>(
+    table: &'a IntersectionFunctionTableRef,
+) -> BindIntersectionFunctionTable<'a> {
+    BindIntersectionFunctionTable::IntersectionFunctionTable(table)
+}
+
+impl<'a> BindIntersectionFunctionTable<'a> {
+    #[inline]
+    pub fn bind<F: PipelineFunctionType>(self, encoder: &F::CommandEncoder, index: usize) {
+        use BindIntersectionFunctionTable::*;
+        match self {
+            IntersectionFunctionTable(table) => {
+                F::intersection_function_table(encoder, index, table)
+            }
+            Null => F::intersection_function_table_null(encoder, index),
+            Skip => {}
+        }
+    }
+}
+
+pub enum BindVisibleFunctionTable<'a> {
+    VisibleFunctionTable(&'a VisibleFunctionTableRef),
+    Null,
+    Skip,
+}
+
+#[allow(non_snake_case)]
+pub fn BindVisibleFunctionTable<'a>(
+    table: &'a VisibleFunctionTableRef,
+) -> BindVisibleFunctionTable<'a> {
+    BindVisibleFunctionTable::VisibleFunctionTable(table)
+}
+
+impl<'a> BindVisibleFunctionTable<'a> {
+    #[inline]
+    pub fn bind<F: PipelineFunctionType>(self, encoder: &F::CommandEncoder, index: usize) {
+        use BindVisibleFunctionTable::*;
+        match self {
+            VisibleFunctionTable(table) => F::visible_function_table(encoder, index, table),
+            Null => F::visible_function_table_null(encoder, index),
+            Skip => {}
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::pipeline_function::PipelineFunctionType;
+use super::{function_table::*, pipeline_function::PipelineFunctionType};
 use crate::typed_buffer::TypedBuffer;
 use metal::{ComputeCommandEncoderRef, ComputePipelineDescriptorRef, FunctionRef, TextureRef};
 
@@ -67,4 +67,30 @@ impl PipelineFunctionType for ComputeFunctionType {
     fn acceleration_structure_null(encoder: &Self::CommandEncoder, index: usize) {
         encoder.set_acceleration_structure(None, index as _);
     }
+
+    #[inline(always)]
+    fn intersection_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b IntersectionFunctionTableRef,
+    ) {
+        set_compute_intersection_function_table(encoder, index, Some(table));
+    }
+    #[inline(always)]
+    fn intersection_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        set_compute_intersection_function_table(encoder, index, None);
+    }
+
+    #[inline(always)]
+    fn visible_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b VisibleFunctionTableRef,
+    ) {
+        set_compute_visible_function_table(encoder, index, Some(table));
+    }
+    #[inline(always)]
+    fn visible_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        set_compute_visible_function_table(encoder, index, None);
+    }
 }
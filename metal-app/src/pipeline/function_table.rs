@@ -0,0 +1,174 @@
+// Intersection and Visible function tables (see `RenderPipeline::new_intersection_function_table()`
+// and `RenderPipeline::new_visible_function_table()`).
+//
+// Created, filled, and bound through the Objective-C API directly, rather than the `metal` crate's
+// wrappers, whose argument orders are inconsistent (ex. `set_acceleration_structure(accel, index)`
+// for compute, but `set_fragment_acceleration_structure(index, accel)` for render).
+// See https://developer.apple.com/documentation/metal/mtlintersectionfunctiontable
+use cocoa::base::nil;
+use foreign_types::{foreign_type, ForeignType, ForeignTypeRef};
+use metal::{
+    Array, ComputeCommandEncoderRef, Function, FunctionRef, MTLRenderStages, NSUInteger,
+    RenderCommandEncoderRef, RenderPipelineDescriptorRef, RenderPipelineStateRef,
+};
+use objc::runtime::Object;
+
+pub enum MTLIntersectionFunctionTable {}
+pub enum MTLVisibleFunctionTable {}
+
+unsafe fn release<T>(obj: *mut T) {
+    let _: () = msg_send![obj as *mut Object, release];
+}
+
+foreign_type! {
+    type CType = MTLIntersectionFunctionTable;
+    fn drop = release;
+    pub struct IntersectionFunctionTable;
+    pub struct IntersectionFunctionTableRef;
+}
+
+foreign_type! {
+    type CType = MTLVisibleFunctionTable;
+    fn drop = release;
+    pub struct VisibleFunctionTable;
+    pub struct VisibleFunctionTableRef;
+}
+
+unsafe impl Send for IntersectionFunctionTable {}
+unsafe impl Sync for IntersectionFunctionTable {}
+unsafe impl Send for VisibleFunctionTable {}
+unsafe impl Sync for VisibleFunctionTable {}
+
+// `-[MTLRenderPipelineDescriptor setFragmentLinkedFunctions:]`
+pub(crate) fn set_fragment_linked_functions(
+    pipeline_desc: &RenderPipelineDescriptorRef,
+    functions: &[&FunctionRef],
+) {
+    unsafe {
+        let linked_functions: *mut Object = msg_send![class!(MTLLinkedFunctions), linkedFunctions];
+        let functions = Array::<Function>::from_slice(functions);
+        let _: () = msg_send![linked_functions, setFunctions: functions.as_ptr() as *mut Object];
+        let _: () = msg_send![
+            pipeline_desc.as_ptr() as *mut Object,
+            setFragmentLinkedFunctions: linked_functions
+        ];
+    }
+}
+
+// `-[MTLRenderPipelineState functionHandleWithFunction:stage:]`
+fn function_handle(
+    pipeline: &RenderPipelineStateRef,
+    function: &FunctionRef,
+    stage: MTLRenderStages,
+) -> *mut Object {
+    let handle: *mut Object = unsafe {
+        msg_send![
+            pipeline.as_ptr() as *mut Object,
+            functionHandleWithFunction: function.as_ptr() as *mut Object
+            stage: stage.bits()
+        ]
+    };
+    assert!(
+        !handle.is_null(),
+        "Failed to get function handle (is the function linked to the pipeline?)"
+    );
+    handle
+}
+
+// `-[MTLIntersectionFunctionTable setFunction:atIndex:]` (or `MTLVisibleFunctionTable`), for every
+// function of the table (in order).
+fn set_functions(
+    table: *mut Object,
+    pipeline: &RenderPipelineStateRef,
+    functions: &[&FunctionRef],
+    stage: MTLRenderStages,
+) {
+    for (i, &function) in functions.iter().enumerate() {
+        let handle = function_handle(pipeline, function, stage);
+        unsafe {
+            let _: () = msg_send![table, setFunction: handle atIndex: i as NSUInteger];
+        }
+    }
+}
+
+// `-[MTLRenderPipelineState newIntersectionFunctionTableWithDescriptor:stage:]`
+pub(crate) fn new_intersection_function_table(
+    pipeline: &RenderPipelineStateRef,
+    functions: &[&FunctionRef],
+    stage: MTLRenderStages,
+) -> IntersectionFunctionTable {
+    unsafe {
+        let desc: *mut Object = msg_send![
+            class!(MTLIntersectionFunctionTableDescriptor),
+            intersectionFunctionTableDescriptor
+        ];
+        let _: () = msg_send![desc, setFunctionCount: functions.len() as NSUInteger];
+        let table: *mut MTLIntersectionFunctionTable = msg_send![
+            pipeline.as_ptr() as *mut Object,
+            newIntersectionFunctionTableWithDescriptor: desc
+            stage: stage.bits()
+        ];
+        assert!(
+            !table.is_null(),
+            "Failed to create intersection function table"
+        );
+        set_functions(table as _, pipeline, functions, stage);
+        IntersectionFunctionTable::from_ptr(table)
+    }
+}
+
+// `-[MTLRenderPipelineState newVisibleFunctionTableWithDescriptor:stage:]`
+pub(crate) fn new_visible_function_table(
+    pipeline: &RenderPipelineStateRef,
+    functions: &[&FunctionRef],
+    stage: MTLRenderStages,
+) -> VisibleFunctionTable {
+    unsafe {
+        let desc: *mut Object = msg_send![
+            class!(MTLVisibleFunctionTableDescriptor),
+            visibleFunctionTableDescriptor
+        ];
+        let _: () = msg_send![desc, setFunctionCount: functions.len() as NSUInteger];
+        let table: *mut MTLVisibleFunctionTable = msg_send![
+            pipeline.as_ptr() as *mut Object,
+            newVisibleFunctionTableWithDescriptor: desc
+            stage: stage.bits()
+        ];
+        assert!(!table.is_null(), "Failed to create visible function table");
+        set_functions(table as _, pipeline, functions, stage);
+        VisibleFunctionTable::from_ptr(table)
+    }
+}
+
+// Binds (or unbinds with `None`) a table to an encoder's buffer index.
+// ex. `-[MTLRenderCommandEncoder setFragmentIntersectionFunctionTable:atBufferIndex:]`
+macro_rules! set_function_table {
+    ($($fn_name:ident($encoder:ty, $table:ty) => $selector:ident;)*) => {$(
+        #[inline]
+        pub(crate) fn $fn_name(encoder: &$encoder, index: usize, table: Option<&$table>) {
+            let table = table.map_or(nil, |table| table.as_ptr() as *mut Object);
+            unsafe {
+                let _: () = msg_send![
+                    encoder.as_ptr() as *mut Object,
+                    $selector: table
+                    atBufferIndex: index as NSUInteger
+                ];
+            }
+        }
+    )*};
+}
+
+set_function_table! {
+    set_vertex_intersection_function_table(RenderCommandEncoderRef, IntersectionFunctionTableRef)
+        => setVertexIntersectionFunctionTable;
+    set_fragment_intersection_function_table(RenderCommandEncoderRef, IntersectionFunctionTableRef)
+        => setFragmentIntersectionFunctionTable;
+    set_vertex_visible_function_table(RenderCommandEncoderRef, VisibleFunctionTableRef)
+        => setVertexVisibleFunctionTable;
+    set_fragment_visible_function_table(RenderCommandEncoderRef, VisibleFunctionTableRef)
+        => setFragmentVisibleFunctionTable;
+    set_compute_intersection_function_table(ComputeCommandEncoderRef, IntersectionFunctionTableRef)
+        => setIntersectionFunctionTable;
+    set_compute_visible_function_table(ComputeCommandEncoderRef, VisibleFunctionTableRef)
+        => setVisibleFunctionTable;
+}
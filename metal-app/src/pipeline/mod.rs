@@ -1,12 +1,15 @@
 mod bind;
 mod compute_pipeline;
 pub mod function;
+pub mod function_table;
 mod pipeline_function;
+mod pipeline_permutations;
 mod render_pipeline;
 mod tesselation_render_pipeline;
 
 pub use bind::*;
 pub use compute_pipeline::*;
 pub use pipeline_function::*;
+pub use pipeline_permutations::*;
 pub use render_pipeline::*;
 pub use tesselation_render_pipeline::*;
@@ -1,6 +1,11 @@
-use super::{bind::Binds, function::Function};
+use super::{
+    bind::Binds,
+    function::Function,
+    function_table::{IntersectionFunctionTableRef, VisibleFunctionTableRef},
+};
 use crate::typed_buffer::TypedBuffer;
 use metal::{AccelerationStructureRef, FunctionRef, LibraryRef, TextureRef};
+use std::ops::Deref;
 
 pub trait PipelineFunctionType {
     type Descriptor;
@@ -31,6 +36,18 @@ pub trait PipelineFunctionType {
         accel_struct: &'b AccelerationStructureRef,
     );
     fn acceleration_structure_null(encoder: &Self::CommandEncoder, index: usize);
+    fn intersection_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b IntersectionFunctionTableRef,
+    );
+    fn intersection_function_table_null(encoder: &Self::CommandEncoder, index: usize);
+    fn visible_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b VisibleFunctionTableRef,
+    );
+    fn visible_function_table_null(encoder: &Self::CommandEncoder, index: usize);
 }
 
 pub trait PipelineFunction<F: PipelineFunctionType>: Function {
@@ -47,3 +64,45 @@ pub trait PipelineFunction<F: PipelineFunctionType>: Function {
         binds.bind::<F>(encoder);
     }
 }
+
+// Intersection and Visible functions are not set on a pipeline descriptor directly, but are linked to
+// a pipeline (see `RenderPipelineBuilder::fragment_linked_functions()`) and referenced by function
+// tables (see `RenderPipeline::new_intersection_function_table()` and
+// `RenderPipeline::new_visible_function_table()`).
+pub trait IntersectionFunction: Function {
+    #[inline]
+    fn get_intersection_function(&self, library: &LibraryRef) -> LinkedIntersectionFunction {
+        LinkedIntersectionFunction(self.get_function(library))
+    }
+}
+
+pub trait VisibleFunction: Function {
+    #[inline]
+    fn get_visible_function(&self, library: &LibraryRef) -> LinkedVisibleFunction {
+        LinkedVisibleFunction(self.get_function(library))
+    }
+}
+
+// Metal functions typed by the function table they can be added to, so a Visible function can not be
+// added to an Intersection function table (or vice versa). The same instance must be linked to the
+// pipeline and added to the table.
+pub struct LinkedIntersectionFunction(metal::Function);
+pub struct LinkedVisibleFunction(metal::Function);
+
+impl Deref for LinkedIntersectionFunction {
+    type Target = FunctionRef;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for LinkedVisibleFunction {
+    type Target = FunctionRef;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,257 @@
+use crate::debug_time;
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc,
+};
+
+// Enumerates every function constant permutation of a shader function.
+// Generated by `metal_build` (see `metal_build::BuildOptions::max_function_permutations`).
+pub trait FunctionPermutations: Sized {
+    const PERMUTATIONS: usize;
+
+    fn from_permutation_index(index: usize) -> Self;
+    fn permutation_index(&self) -> usize;
+}
+
+// Called from both the render thread (see `PipelinePermutations::get()`) and the background thread
+// (see `PipelinePermutations::new_in_background()`), possibly at the same time.
+type CreatePipeline<V, F, P> = Arc<dyn Fn(V, F) -> P + Send + Sync>;
+
+// Cache of pipelines (`P`) for every permutation of the Vertex (`V`) and Fragment (`F`) functions'
+// function constants. Switching between permutations (ex. changing the shading mode) is instant,
+// instead of recreating the pipeline.
+pub struct PipelinePermutations<V, F, P> {
+    background: Option<Receiver<(usize, P)>>,
+    create_pipeline: CreatePipeline<V, F, P>,
+    pipelines: Vec<Option<P>>,
+    // Permutations (by index) the background thread will create, but have not been received yet.
+    queued: Vec<bool>,
+}
+
+impl<V, F, P> PipelinePermutations<V, F, P>
+where
+    V: FunctionPermutations + 'static,
+    F: FunctionPermutations + 'static,
+    P: Send + 'static,
+{
+    const PERMUTATIONS: usize = V::PERMUTATIONS * F::PERMUTATIONS;
+
+    #[inline]
+    fn permutation_index(vertex_function: &V, fragment_function: &F) -> usize {
+        vertex_function.permutation_index() * F::PERMUTATIONS
+            + fragment_function.permutation_index()
+    }
+
+    #[inline]
+    fn from_permutation_index(index: usize) -> (V, F) {
+        (
+            V::from_permutation_index(index / F::PERMUTATIONS),
+            F::from_permutation_index(index % F::PERMUTATIONS),
+        )
+    }
+
+    // Creates every permutation up front.
+    pub fn new(create_pipeline: impl Fn(V, F) -> P + Send + Sync + 'static) -> Self {
+        debug_time("PipelinePermutations", || {
+            let pipelines = (0..Self::PERMUTATIONS)
+                .map(|index| {
+                    let (vertex_function, fragment_function) = Self::from_permutation_index(index);
+                    Some(create_pipeline(vertex_function, fragment_function))
+                })
+                .collect();
+            Self {
+                background: None,
+                create_pipeline: Arc::new(create_pipeline),
+                pipelines,
+                queued: vec![false; Self::PERMUTATIONS],
+            }
+        })
+    }
+
+    // Creates the `precompile` permutations (ex. only those reachable by the user, in the order they
+    // are likely to be used) in a background thread. Getting a permutation that is queued, waits for
+    // the background thread to create it, otherwise it is created immediately (see `get()`).
+    pub fn new_in_background(
+        precompile: impl IntoIterator<Item = (V, F)>,
+        create_pipeline: impl Fn(V, F) -> P + Send + Sync + 'static,
+    ) -> Self {
+        let create_pipeline: CreatePipeline<V, F, P> = Arc::new(create_pipeline);
+        let mut queued = vec![false; Self::PERMUTATIONS];
+        let precompile: Vec<usize> = precompile
+            .into_iter()
+            .map(|(vertex_function, fragment_function)| {
+                Self::permutation_index(&vertex_function, &fragment_function)
+            })
+            .filter(|&index| !std::mem::replace(&mut queued[index], true))
+            .collect();
+        let (sender, receiver) = channel();
+        let background_create_pipeline = create_pipeline.clone();
+        std::thread::spawn(move || {
+            for index in precompile {
+                let (vertex_function, fragment_function) = Self::from_permutation_index(index);
+                let pipeline = background_create_pipeline(vertex_function, fragment_function);
+                // Stop if the PipelinePermutations was dropped.
+                if sender.send((index, pipeline)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            background: Some(receiver),
+            create_pipeline,
+            pipelines: (0..Self::PERMUTATIONS).map(|_| None).collect(),
+            queued,
+        }
+    }
+
+    // Stores the pipelines created by the background thread so far. If the `index` permutation is
+    // queued, waits until the background thread creates it, instead of creating it twice.
+    fn receive_background(&mut self, index: usize) {
+        while let Some(receiver) = &self.background {
+            let (received_index, pipeline) = if self.queued[index] {
+                match receiver.recv() {
+                    Ok(received) => received,
+                    // The background thread stopped (ex. panicked), remaining permutations are
+                    // created as needed.
+                    Err(_) => {
+                        self.background = None;
+                        self.queued.fill(false);
+                        break;
+                    }
+                }
+            } else {
+                match receiver.try_recv() {
+                    Ok(received) => received,
+                    Err(_) => break,
+                }
+            };
+            self.queued[received_index] = false;
+            self.pipelines[received_index] = Some(pipeline);
+        }
+    }
+
+    pub fn get(&mut self, vertex_function: &V, fragment_function: &F) -> &P {
+        let index = Self::permutation_index(vertex_function, fragment_function);
+        self.receive_background(index);
+        let create_pipeline = &self.create_pipeline;
+        self.pipelines[index].get_or_insert_with(|| {
+            let (vertex_function, fragment_function) = Self::from_permutation_index(index);
+            create_pipeline(vertex_function, fragment_function)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{sync::Mutex, thread, time::Duration};
+
+    // Function with `N` bool function constants.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Function<const N: usize>(usize);
+
+    impl<const N: usize> FunctionPermutations for Function<N> {
+        const PERMUTATIONS: usize = 1 << N;
+
+        fn from_permutation_index(index: usize) -> Self {
+            Self(index)
+        }
+
+        fn permutation_index(&self) -> usize {
+            self.0
+        }
+    }
+
+    type Vertex = Function<1>;
+    type Fragment = Function<2>;
+    type Pipeline = (Vertex, Fragment);
+
+    // Creates pipelines (the functions they were created with), counting how many times each
+    // permutation was created.
+    fn counting_create_pipeline(
+        delay: Duration,
+    ) -> (
+        Arc<Mutex<Vec<usize>>>,
+        impl Fn(Vertex, Fragment) -> Pipeline,
+    ) {
+        let counts = Arc::new(Mutex::new(vec![0; 8]));
+        let create_counts = counts.clone();
+        (counts, move |vertex_function, fragment_function| {
+            thread::sleep(delay);
+            let index = PipelinePermutations::<Vertex, Fragment, Pipeline>::permutation_index(
+                &vertex_function,
+                &fragment_function,
+            );
+            create_counts.lock().unwrap()[index] += 1;
+            (vertex_function, fragment_function)
+        })
+    }
+
+    #[test]
+    fn test_permutation_index() {
+        type Permutations = PipelinePermutations<Vertex, Fragment, Pipeline>;
+        assert_eq!(Permutations::PERMUTATIONS, 8);
+        for index in 0..Permutations::PERMUTATIONS {
+            let (vertex_function, fragment_function) = Permutations::from_permutation_index(index);
+            assert_eq!(
+                Permutations::permutation_index(&vertex_function, &fragment_function),
+                index
+            );
+        }
+        assert_eq!(
+            Permutations::from_permutation_index(5),
+            (Function(1), Function(1))
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let (counts, create_pipeline) = counting_create_pipeline(Duration::ZERO);
+        let mut permutations = PipelinePermutations::new(create_pipeline);
+        assert_eq!(*counts.lock().unwrap(), [1; 8]);
+        for index in 0..8 {
+            let (vertex_function, fragment_function) = (Function(index / 4), Function(index % 4));
+            assert_eq!(
+                *permutations.get(&vertex_function, &fragment_function),
+                (vertex_function, fragment_function)
+            );
+        }
+        assert_eq!(*counts.lock().unwrap(), [1; 8]);
+    }
+
+    #[test]
+    fn test_new_in_background() {
+        let (counts, create_pipeline) = counting_create_pipeline(Duration::from_millis(20));
+        let mut permutations = PipelinePermutations::new_in_background(
+            // Duplicates are only created once.
+            [
+                (Function(0), Function(3)),
+                (Function(1), Function(2)),
+                (Function(0), Function(3)),
+                (Function(1), Function(0)),
+            ],
+            create_pipeline,
+        );
+
+        // Queued, but not created yet: waits for the background thread.
+        assert_eq!(
+            *permutations.get(&Function(1), &Function(0)),
+            (Function(1), Function(0))
+        );
+        assert_eq!(counts.lock().unwrap().iter().sum::<usize>(), 3);
+
+        // Not queued: created immediately.
+        assert_eq!(
+            *permutations.get(&Function(0), &Function(1)),
+            (Function(0), Function(1))
+        );
+        for index in 0..8 {
+            let (vertex_function, fragment_function) = (Function(index / 4), Function(index % 4));
+            assert_eq!(
+                *permutations.get(&vertex_function, &fragment_function),
+                (vertex_function, fragment_function)
+            );
+        }
+        assert_eq!(*counts.lock().unwrap(), [1; 8]);
+    }
+}
@@ -1,5 +1,12 @@
 use super::{
-    bind::Binds, function, pipeline_function::*, TesselationRenderPass, TesselationRenderPipeline,
+    bind::Binds,
+    function,
+    function_table::{
+        self, IntersectionFunctionTable, IntersectionFunctionTableRef, VisibleFunctionTable,
+        VisibleFunctionTableRef,
+    },
+    pipeline_function::*,
+    TesselationRenderPass, TesselationRenderPipeline,
 };
 use crate::{debug_time, set_tesselation_factor_buffer, typed_buffer::TypedBuffer};
 use metal::*;
@@ -306,6 +313,30 @@ impl PipelineFunctionType for VertexFunctionType {
     fn acceleration_structure_null(encoder: &Self::CommandEncoder, index: usize) {
         encoder.set_vertex_acceleration_structure(index as _, None);
     }
+    #[inline]
+    fn intersection_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b IntersectionFunctionTableRef,
+    ) {
+        function_table::set_vertex_intersection_function_table(encoder, index, Some(table));
+    }
+    #[inline]
+    fn intersection_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        function_table::set_vertex_intersection_function_table(encoder, index, None);
+    }
+    #[inline]
+    fn visible_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b VisibleFunctionTableRef,
+    ) {
+        function_table::set_vertex_visible_function_table(encoder, index, Some(table));
+    }
+    #[inline]
+    fn visible_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        function_table::set_vertex_visible_function_table(encoder, index, None);
+    }
 }
 
 pub struct FragmentFunctionType;
@@ -370,6 +401,30 @@ impl PipelineFunctionType for FragmentFunctionType {
     fn acceleration_structure_null(encoder: &Self::CommandEncoder, index: usize) {
         encoder.set_fragment_acceleration_structure(index as _, None);
     }
+    #[inline]
+    fn intersection_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b IntersectionFunctionTableRef,
+    ) {
+        function_table::set_fragment_intersection_function_table(encoder, index, Some(table));
+    }
+    #[inline]
+    fn intersection_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        function_table::set_fragment_intersection_function_table(encoder, index, None);
+    }
+    #[inline]
+    fn visible_function_table<'a, 'b>(
+        encoder: &'a Self::CommandEncoder,
+        index: usize,
+        table: &'b VisibleFunctionTableRef,
+    ) {
+        function_table::set_fragment_visible_function_table(encoder, index, Some(table));
+    }
+    #[inline]
+    fn visible_function_table_null(encoder: &Self::CommandEncoder, index: usize) {
+        function_table::set_fragment_visible_function_table(encoder, index, None);
+    }
 }
 
 pub struct NoBinds;
@@ -414,14 +469,41 @@ macro_rules! into_mtl_data_type {
 }
 
 into_mtl_data_type!(bool, MTLDataType::Bool);
-into_mtl_data_type!(metal_types::float, MTLDataType::Float);
+into_mtl_data_type!(i8, MTLDataType::Char);
+into_mtl_data_type!(u8, MTLDataType::UChar);
+into_mtl_data_type!(i16, MTLDataType::Short);
+into_mtl_data_type!(u16, MTLDataType::UShort);
+into_mtl_data_type!(i32, MTLDataType::Int);
+into_mtl_data_type!(u32, MTLDataType::UInt);
+into_mtl_data_type!(metal_types::f16, MTLDataType::Half);
+into_mtl_data_type!(f32, MTLDataType::Float);
+into_mtl_data_type!(metal_types::bool2, MTLDataType::Bool2);
+into_mtl_data_type!(metal_types::bool3, MTLDataType::Bool3);
+into_mtl_data_type!(metal_types::bool4, MTLDataType::Bool4);
+into_mtl_data_type!(metal_types::char2, MTLDataType::Char2);
+into_mtl_data_type!(metal_types::char3, MTLDataType::Char3);
+into_mtl_data_type!(metal_types::char4, MTLDataType::Char4);
+into_mtl_data_type!(metal_types::uchar2, MTLDataType::UChar2);
+into_mtl_data_type!(metal_types::uchar3, MTLDataType::UChar3);
+into_mtl_data_type!(metal_types::uchar4, MTLDataType::UChar4);
+into_mtl_data_type!(metal_types::short2, MTLDataType::Short2);
+into_mtl_data_type!(metal_types::short3, MTLDataType::Short3);
+into_mtl_data_type!(metal_types::short4, MTLDataType::Short4);
+into_mtl_data_type!(metal_types::ushort2, MTLDataType::UShort2);
+into_mtl_data_type!(metal_types::ushort3, MTLDataType::UShort3);
+into_mtl_data_type!(metal_types::ushort4, MTLDataType::UShort4);
+into_mtl_data_type!(metal_types::int2, MTLDataType::Int2);
+into_mtl_data_type!(metal_types::int3, MTLDataType::Int3);
+into_mtl_data_type!(metal_types::int4, MTLDataType::Int4);
+into_mtl_data_type!(metal_types::uint2, MTLDataType::UInt2);
+into_mtl_data_type!(metal_types::uint3, MTLDataType::UInt3);
+into_mtl_data_type!(metal_types::uint4, MTLDataType::UInt4);
+into_mtl_data_type!(metal_types::half2, MTLDataType::Half2);
+into_mtl_data_type!(metal_types::half3, MTLDataType::Half3);
+into_mtl_data_type!(metal_types::half4, MTLDataType::Half4);
 into_mtl_data_type!(metal_types::float2, MTLDataType::Float2);
-// into_mtl_data_type!(metal_types::float3, MTLDataType::Float3);
+into_mtl_data_type!(metal_types::float3, MTLDataType::Float3);
 into_mtl_data_type!(metal_types::float4, MTLDataType::Float4);
-into_mtl_data_type!(metal_types::uint, MTLDataType::UInt);
-into_mtl_data_type!(metal_types::int, MTLDataType::Int);
-into_mtl_data_type!(metal_types::ushort, MTLDataType::UShort);
-into_mtl_data_type!(metal_types::short, MTLDataType::Short);
 
 // TODO: Consider metal-build generating {Render/Compute/Mesh}Pipeline related helpers
 // - Currently, users stil need define RenderPipeline<...> and apply the write combination of NUM_COLOR_ATTACHMENTS, FCS, VS, FS
@@ -620,6 +702,63 @@ impl ResourceUsage for TextureUsage<'_> {
     }
 }
 
+// Pipeline descriptor of a `RenderPipeline` not yet created, see `RenderPipeline::builder()`.
+pub struct RenderPipelineBuilder<
+    const NUM_COLOR_ATTACHMENTS: usize,
+    V: PipelineFunction<VertexFunctionType>,
+    F: PipelineFunction<FragmentFunctionType>,
+    DS: DepthStencilKind,
+> {
+    pipeline_desc: RenderPipelineDescriptor,
+    _pipeline: PhantomData<(V, F, DS)>,
+}
+
+impl<
+        const NUM_COLOR_ATTACHMENTS: usize,
+        V: PipelineFunction<VertexFunctionType>,
+        F: PipelineFunction<FragmentFunctionType>,
+        DS: DepthStencilKind,
+    > RenderPipelineBuilder<NUM_COLOR_ATTACHMENTS, V, F, DS>
+{
+    // Links additional functions (ex. Intersection and Visible functions) to the fragment function,
+    // allowing them to be referenced by function tables (see
+    // `RenderPipeline::new_intersection_function_table()` and
+    // `RenderPipeline::new_visible_function_table()`).
+    //
+    // Only the fragment stage is supported: functions can not be linked to the vertex function, and
+    // compute pipelines have no equivalent yet. Binding tables (see `BindIntersectionFunctionTable`
+    // and `BindVisibleFunctionTable`) works for any stage.
+    pub fn fragment_linked_functions(
+        self,
+        intersection_functions: &[&LinkedIntersectionFunction],
+        visible_functions: &[&LinkedVisibleFunction],
+    ) -> Self {
+        let functions: Vec<&FunctionRef> = intersection_functions
+            .iter()
+            .map(|&f| &**f)
+            .chain(visible_functions.iter().map(|&f| &**f))
+            .collect();
+        if !functions.is_empty() {
+            function_table::set_fragment_linked_functions(&self.pipeline_desc, &functions);
+        }
+        self
+    }
+
+    pub fn build(self, device: &DeviceRef) -> RenderPipeline<NUM_COLOR_ATTACHMENTS, V, F, DS> {
+        debug_time("RenderPipeline", || {
+            let pipeline = device
+                .new_render_pipeline_state(&self.pipeline_desc)
+                .expect("Failed to create pipeline state");
+            RenderPipeline {
+                pipeline,
+                _vertex_function: PhantomData,
+                _fragment_function: PhantomData,
+                _depth_stencil_kind: PhantomData,
+            }
+        })
+    }
+}
+
 pub struct RenderPipeline<
     const NUM_COLOR_ATTACHMENTS: usize,
     V: PipelineFunction<VertexFunctionType>,
@@ -639,6 +778,7 @@ impl<
         DS: DepthStencilKind,
     > RenderPipeline<NUM_COLOR_ATTACHMENTS, V, F, DS>
 {
+    #[inline]
     pub fn new(
         label: &str,
         device: &DeviceRef,
@@ -648,30 +788,76 @@ impl<
         fragment_function: F,
         depth_stencil_kind: DS,
     ) -> Self {
-        debug_time("RenderPipeline", || {
-            let pipeline_desc = RenderPipelineDescriptor::new();
-            pipeline_desc.set_label(label);
-
-            for i in 0..NUM_COLOR_ATTACHMENTS {
-                let desc = pipeline_desc
-                    .color_attachments()
-                    .object_at(i as u64)
-                    .expect("Failed to access color attachment on pipeline descriptor");
-                Color::setup_pipeline(colors[i], &desc);
-            }
-            depth_stencil_kind.setup_pipeline(&pipeline_desc);
-            vertex_function.setup_pipeline(library, &pipeline_desc);
-            fragment_function.setup_pipeline(library, &pipeline_desc);
-            let pipeline = device
-                .new_render_pipeline_state(&pipeline_desc)
-                .expect("Failed to create pipeline state");
-            Self {
-                pipeline,
-                _vertex_function: PhantomData,
-                _fragment_function: PhantomData,
-                _depth_stencil_kind: PhantomData,
-            }
-        })
+        Self::builder(
+            label,
+            library,
+            colors,
+            vertex_function,
+            fragment_function,
+            depth_stencil_kind,
+        )
+        .build(device)
+    }
+
+    // Same as `new()`, but allows configuring the pipeline further (ex. linking functions) before
+    // it is created, see `RenderPipelineBuilder`.
+    pub fn builder(
+        label: &str,
+        library: &LibraryRef,
+        colors: [ColorPipelineDesc; NUM_COLOR_ATTACHMENTS],
+        vertex_function: V,
+        fragment_function: F,
+        depth_stencil_kind: DS,
+    ) -> RenderPipelineBuilder<NUM_COLOR_ATTACHMENTS, V, F, DS> {
+        let pipeline_desc = RenderPipelineDescriptor::new();
+        pipeline_desc.set_label(label);
+
+        for i in 0..NUM_COLOR_ATTACHMENTS {
+            let desc = pipeline_desc
+                .color_attachments()
+                .object_at(i as u64)
+                .expect("Failed to access color attachment on pipeline descriptor");
+            Color::setup_pipeline(colors[i], &desc);
+        }
+        depth_stencil_kind.setup_pipeline(&pipeline_desc);
+        vertex_function.setup_pipeline(library, &pipeline_desc);
+        fragment_function.setup_pipeline(library, &pipeline_desc);
+        RenderPipelineBuilder {
+            pipeline_desc,
+            _pipeline: PhantomData,
+        }
+    }
+
+    // Creates a table of Intersection functions (in order of `functions`) for the fragment function,
+    // the only stage supported (see `RenderPipelineBuilder::fragment_linked_functions()`).
+    // IMPORTANT: Functions must be linked to the pipeline (see
+    // `RenderPipelineBuilder::fragment_linked_functions()`).
+    pub fn new_intersection_function_table(
+        &self,
+        functions: &[&LinkedIntersectionFunction],
+    ) -> IntersectionFunctionTable {
+        let functions: Vec<&FunctionRef> = functions.iter().map(|&f| &**f).collect();
+        function_table::new_intersection_function_table(
+            &self.pipeline,
+            &functions,
+            MTLRenderStages::Fragment,
+        )
+    }
+
+    // Creates a table of Visible functions (in order of `functions`) for the fragment function, the
+    // only stage supported (see `RenderPipelineBuilder::fragment_linked_functions()`).
+    // IMPORTANT: Functions must be linked to the pipeline (see
+    // `RenderPipelineBuilder::fragment_linked_functions()`).
+    pub fn new_visible_function_table(
+        &self,
+        functions: &[&LinkedVisibleFunction],
+    ) -> VisibleFunctionTable {
+        let functions: Vec<&FunctionRef> = functions.iter().map(|&f| &**f).collect();
+        function_table::new_visible_function_table(
+            &self.pipeline,
+            &functions,
+            MTLRenderStages::Fragment,
+        )
     }
 
     #[inline]
@@ -60,6 +60,7 @@ impl UserEvent {
     pub const KEY_CODE_UP: c_ushort = 126;
     pub const KEY_CODE_SPACEBAR: c_ushort = 49;
     pub const KEY_CODE_P: c_ushort = 35;
+    pub const KEY_CODE_I: c_ushort = 34;
 }
 
 #[inline]
@@ -0,0 +1,244 @@
+use regex::Regex;
+use std::{
+    fmt::Display,
+    process::{Command, Output},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum Severity {
+    Note,
+    Remark,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    #[inline]
+    fn is_error(self) -> bool {
+        self >= Self::Error
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(
+            match self {
+                Self::Note => "note",
+                Self::Remark => "remark",
+                Self::Warning => "warning",
+                Self::Error => "error",
+                Self::Fatal => "fatal error",
+            },
+            f,
+        )
+    }
+}
+
+#[derive(PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            file,
+            line,
+            column,
+            severity,
+            message,
+        } = self;
+        write!(f, "{file}:{line}:{column}: {severity}: {message}")
+    }
+}
+
+// Parses the (text, non-colored) diagnostics output of the Metal compiler (Clang).
+//
+// Each diagnostic starts with a `file:line:column: severity: message` line, and is followed by the
+// offending source line and a caret line (ex. `    ^~~~~`). Everything else (source excerpts,
+// fix-it hints, "In file included from ...", "N errors generated.") is ignored.
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let diagnostic_regex = Regex::new(
+        r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?P<column>\d+): (?P<severity>note|remark|warning|error|fatal error): (?P<message>.*)$",
+    )
+    .expect("Failed to create regex for parsing diagnostics");
+    output
+        .lines()
+        .filter_map(|l| {
+            let caps = diagnostic_regex.captures(l)?;
+            Some(Diagnostic {
+                file: caps["file"].to_owned(),
+                line: caps["line"].parse().ok()?,
+                column: caps["column"].parse().ok()?,
+                severity: match &caps["severity"] {
+                    "note" => Severity::Note,
+                    "remark" => Severity::Remark,
+                    "warning" => Severity::Warning,
+                    "error" => Severity::Error,
+                    _ => Severity::Fatal,
+                },
+                message: caps["message"].to_owned(),
+            })
+        })
+        .collect()
+}
+
+// Summarizes the errors of a failed compilation (ex. "2 errors", followed by each error).
+fn summarize_errors(diagnostics: &[Diagnostic]) -> Option<String> {
+    let errors: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| d.severity.is_error())
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+    let mut summary = format!(
+        "{} error{}",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" }
+    );
+    for e in errors {
+        summary.push_str(&format!("\n    {e}"));
+    }
+    Some(summary)
+}
+
+// Runs a Metal compiler command, re-emitting any diagnostics as Cargo warnings (shown when building
+// the crate). If the command fails, panics with a summary of the errors.
+pub fn run_metal_command(command: &mut Command) -> Output {
+    let out = command
+        .arg("-fno-color-diagnostics")
+        .output()
+        .expect(&format!("Failed to run command {command:?}"));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let diagnostics = parse_diagnostics(&stderr);
+    for d in &diagnostics {
+        println!("cargo:warning={d}");
+    }
+    if !out.status.success() {
+        match summarize_errors(&diagnostics) {
+            Some(summary) => panic!("Failed to compile Metal shaders, {summary}"),
+            None => panic!(
+                r#"
+    stdout: {}
+    stderr: {stderr}
+    "#,
+                String::from_utf8_lossy(&out.stdout),
+            ),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COMPILER_OUTPUT: &'static str =
+        include_str!("../test_src/diagnostics/compiler_output.txt");
+    const SHADERS_FILE: &'static str =
+        "/Users/pwong/projects/uou-interactive-graphics/proj-3-shading/src/shaders.metal";
+
+    #[test]
+    fn test_parse_diagnostics() {
+        pretty_assertions::assert_eq!(
+            parse_diagnostics(COMPILER_OUTPUT),
+            vec![
+                Diagnostic {
+                    file: "/Users/pwong/projects/uou-interactive-graphics/proj-3-shading/src/shader_bindings.h".to_owned(),
+                    line: 14,
+                    column: 11,
+                    severity: Severity::Warning,
+                    message: "unused variable 'unused_scale' [-Wunused-variable]".to_owned(),
+                },
+                Diagnostic {
+                    file: SHADERS_FILE.to_owned(),
+                    line: 42,
+                    column: 12,
+                    severity: Severity::Error,
+                    message: "use of undeclared identifier 'nromal'; did you mean 'normal'?".to_owned(),
+                },
+                Diagnostic {
+                    file: SHADERS_FILE.to_owned(),
+                    line: 38,
+                    column: 12,
+                    severity: Severity::Note,
+                    message: "'normal' declared here".to_owned(),
+                },
+                Diagnostic {
+                    file: SHADERS_FILE.to_owned(),
+                    line: 57,
+                    column: 5,
+                    severity: Severity::Error,
+                    message: "no matching function for call to 'shade_phong_blinn'".to_owned(),
+                },
+                Diagnostic {
+                    file: "/Users/pwong/projects/uou-interactive-graphics/metal-shaders/src/shading.h".to_owned(),
+                    line: 20,
+                    column: 1,
+                    severity: Severity::Note,
+                    message: "candidate function not viable: requires 5 arguments, but 4 were provided".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostics_fatal_error() {
+        pretty_assertions::assert_eq!(
+            parse_diagnostics(
+                "\
+shaders.metal:3:10: fatal error: 'shader_bindings.h' file not found
+#include \"shader_bindings.h\"
+         ^~~~~~~~~~~~~~~~~~~
+1 error generated.
+"
+            ),
+            vec![Diagnostic {
+                file: "shaders.metal".to_owned(),
+                line: 3,
+                column: 10,
+                severity: Severity::Fatal,
+                message: "'shader_bindings.h' file not found".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostics_no_diagnostics() {
+        pretty_assertions::assert_eq!(parse_diagnostics(""), vec![]);
+        pretty_assertions::assert_eq!(
+            parse_diagnostics("dependencies: shaders.metal \\\n  shader_bindings.h\n"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        pretty_assertions::assert_eq!(
+            parse_diagnostics(COMPILER_OUTPUT)[1].to_string(),
+            format!("{SHADERS_FILE}:42:12: error: use of undeclared identifier 'nromal'; did you mean 'normal'?")
+        );
+    }
+
+    #[test]
+    fn test_summarize_errors() {
+        pretty_assertions::assert_eq!(
+            summarize_errors(&parse_diagnostics(COMPILER_OUTPUT)),
+            Some(format!(
+                "\
+2 errors
+    {SHADERS_FILE}:42:12: error: use of undeclared identifier 'nromal'; did you mean 'normal'?
+    {SHADERS_FILE}:57:5: error: no matching function for call to 'shade_phong_blinn'"
+            ))
+        );
+        pretty_assertions::assert_eq!(summarize_errors(&parse_diagnostics("")), None);
+    }
+}
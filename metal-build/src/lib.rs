@@ -1,24 +1,52 @@
 #![feature(assert_matches)]
+mod diagnostics;
 mod shader_function_bindings;
+mod texture_assets;
+
+pub use texture_assets::build_model_texture_assets;
 
 use bindgen::{
     callbacks::{DeriveTrait, ImplementsTrait, ParseCallbacks},
     CargoCallbacks,
 };
+use build_hash::{depfile, ModifiedOutput, Outputs};
+use diagnostics::run_metal_command;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
+use std::process::Command;
 use std::{env, fs};
 
 const METAL_BUILD_MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
 
+#[derive(Clone, Copy, Default, Hash)]
+pub struct BuildOptions {
+    // Generate a `FunctionPermutations` implementation for each Vertex, Fragment, and Compute shader
+    // function with at most this many function constant permutations. Only functions referencing
+    // exclusively bool function constants are considered (2^N permutations).
+    //
+    // Enums can not be enumerated: function constants can only be scalar or vector types (Spec:
+    // 5.8.1 Specifying a Function Constant), so an enum is passed as its integer value, with nothing
+    // relating it back to the enum's variants. Express each variant as a bool function constant
+    // instead (ex. `metal_app::components::ShadingModeSelector`).
+    //
+    // Keep this as small as possible, `metal_app::pipeline::PipelinePermutations` may create a
+    // pipeline for every permutation. Defaults to 0, no `FunctionPermutations` implementations are
+    // generated.
+    pub max_function_permutations: usize,
+}
+
+#[inline]
 pub fn build() {
+    build_with_options(BuildOptions::default())
+}
+
+pub fn build_with_options(options: BuildOptions) {
     let metal_shaders_file = PathBuf::from("src")
         .join("shaders.metal")
         .canonicalize()
         .expect("Failed to canonicalize path to shaders.metal");
     compile_shaders(&metal_shaders_file);
-    generate_rust_shader_bindings(&metal_shaders_file);
+    generate_rust_shader_bindings(&metal_shaders_file, options);
 }
 
 #[derive(Debug)]
@@ -41,9 +69,14 @@ impl ParseCallbacks for BlocklistedTypeImplementsTrait {
             Some(ImplementsTrait::No)
         }
     }
+    // Verifies Rust and Metal layouts match, required to bind values and buffers (see
+    // `metal_types::MetalLayout`).
+    fn add_derives(&self, _name: &str) -> Vec<String> {
+        vec!["MetalLayout".into()]
+    }
 }
 
-fn generate_rust_shader_bindings<P: AsRef<Path>>(metal_shaders_file: P) {
+fn generate_rust_shader_bindings<P: AsRef<Path>>(metal_shaders_file: P, options: BuildOptions) {
     let src_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
     let shader_bindings_header_file = src_dir.join("shader_bindings.h");
     let rust_bindgen_only_metal_types_header_file = Path::new(METAL_BUILD_MANIFEST_DIR)
@@ -52,7 +85,8 @@ fn generate_rust_shader_bindings<P: AsRef<Path>>(metal_shaders_file: P) {
         .join("src")
         .join("rust_bindgen_only_metal_types.h");
 
-    build_hash::build_hash(
+    let shader_bindings_path = src_dir.join("shader_bindings.rs");
+    build_hash::build_hash_with_outputs(
         src_dir.join("shader_bindings_rs_hash"),
         &(if shader_bindings_header_file.exists() {
             vec![
@@ -66,8 +100,12 @@ fn generate_rust_shader_bindings<P: AsRef<Path>>(metal_shaders_file: P) {
                 &metal_shaders_file,
             ]
         }),
+        Outputs {
+            paths: &[&shader_bindings_path],
+            modified: ModifiedOutput::Error,
+        },
+        &options,
         || {
-            let shader_bindings_path = src_dir.join("shader_bindings.rs");
             let mut shader_bindings_file = fs::File::options()
                 .write(true)
                 .truncate(true)
@@ -118,46 +156,14 @@ use metal_app::{metal::*, metal_types::*, pipeline::*};
             shader_function_bindings::generate_shader_function_bindings(
                 &metal_shaders_file,
                 &mut shader_bindings_file,
+                options.max_function_permutations,
             );
         },
     );
 }
 
-fn get_shader_deps(shader_path: &str) -> Vec<PathBuf> {
-    let Output { stdout, .. } = run_command(
-        Command::new("xcrun")
-            .args(&[
-                "-sdk",
-                "macosx",
-                "metal",
-                "-std=metal3.0",
-                shader_path,
-                "-MM",
-            ])
-            .env_clear(),
-    );
-    let mut deps = vec![];
-    for l in std::str::from_utf8(&stdout)
-        .expect("Failed to read dependencies output")
-        .lines()
-        .filter(|l| l.starts_with("  ") && !l.contains("include/metal/module.modulemap"))
-    {
-        let dep = l.trim_end_matches(" \\").trim_start_matches(' ');
-        let dep = PathBuf::from(dep).canonicalize().expect(&format!(
-            "Failed to canonicalize path to dependency {dep:?}"
-        ));
-        if !deps.contains(&dep) {
-            deps.push(dep);
-        }
-    }
-    deps
-}
-
 fn compile_shaders<P: AsRef<Path>>(metal_shaders_file: P) {
     let metal_shaders_file = metal_shaders_file.as_ref().to_string_lossy();
-    for dep in get_shader_deps(&metal_shaders_file) {
-        println!("cargo:rerun-if-changed={}", dep.to_string_lossy());
-    }
 
     // Compile Metal Shaders into the following:
     // - shaders.air         Metal IR (AIR) used to create Metal binary (metallib)
@@ -173,7 +179,7 @@ fn compile_shaders<P: AsRef<Path>>(metal_shaders_file: P) {
     let tmp_deps_path = format!("{out_dir}/shaders.dat");
     let shaders_air_path = format!("{out_dir}/shaders.air");
     let shaders_metallib_path = format!("{out_dir}/shaders.metallib");
-    run_command(Command::new("xcrun").args(&[
+    run_metal_command(Command::new("xcrun").args(&[
         "-sdk",
         "macosx",
         "metal",
@@ -198,7 +204,12 @@ fn compile_shaders<P: AsRef<Path>>(metal_shaders_file: P) {
         "-o",
         &shaders_air_path,
     ]));
-    run_command(Command::new("xcrun").args(&[
+    // Reuse the dependencies file (shaders.dat) generated while compiling, to rerun the build when any
+    // shader source or header changes.
+    for dep in depfile::read_dependencies(&tmp_deps_path) {
+        println!("cargo:rerun-if-changed={}", dep.to_string_lossy());
+    }
+    run_metal_command(Command::new("xcrun").args(&[
         "-sdk",
         "macosx",
         "metal",
@@ -222,7 +233,9 @@ fn compile_shaders<P: AsRef<Path>>(metal_shaders_file: P) {
     ]));
 }
 
-fn run_command(command: &mut Command) -> Output {
+// Only used by release builds (see `compile_shaders()`).
+#[cfg_attr(debug_assertions, allow(dead_code))]
+fn run_command(command: &mut Command) -> std::process::Output {
     let out = command
         .output()
         .expect(&format!("Failed to run command {command:?}"));
@@ -251,7 +264,26 @@ mod test {
             .canonicalize()
             .expect("Failed to canonicalize path to test_src/deps directory");
         let shader_file = shader_dir.join("shaders.metal");
-        let mut deps: Vec<PathBuf> = get_shader_deps(&shader_file.to_string_lossy());
+        let out_dir = env::temp_dir().join("metal-build-test-deps");
+        fs::create_dir_all(&out_dir).expect("Failed to create temporary output directory");
+        let deps_path = out_dir.join("shaders.dat");
+        // Same dependency file options as `compile_shaders()`.
+        run_metal_command(Command::new("xcrun").args(&[
+            "-sdk",
+            "macosx",
+            "metal",
+            "-c",
+            "-MMD",
+            "-MT",
+            "dependencies",
+            "-MF",
+            &deps_path.to_string_lossy(),
+            "-std=metal3.0",
+            &shader_file.to_string_lossy(),
+            "-o",
+            &out_dir.join("shaders.air").to_string_lossy(),
+        ]));
+        let mut deps: Vec<PathBuf> = depfile::read_dependencies(&deps_path);
         deps.sort();
         let deps: Vec<String> = deps
             .into_iter()
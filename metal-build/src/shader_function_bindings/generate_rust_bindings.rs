@@ -1,6 +1,9 @@
 use super::{
     generate_metal_ast::generate_metal_ast,
-    parse_metal_ast::{parse_shader_functions_from_reader, Binds, Function, FunctionConstant},
+    metal_to_rust_types::{metal_to_rust_function_constant_type, metal_to_rust_type},
+    parse_metal_ast::{
+        parse_shader_functions_from_reader, Binds, Function, FunctionConstant, FunctionType,
+    },
 };
 use std::{
     borrow::Cow,
@@ -24,15 +27,20 @@ fn escape_name(name: &str) -> Cow<str> {
     }
 }
 
-pub fn generate_shader_function_bindings<P: AsRef<Path>, W: Write>(shader_file: P, writer: &mut W) {
+pub fn generate_shader_function_bindings<P: AsRef<Path>, W: Write>(
+    shader_file: P,
+    writer: &mut W,
+    max_function_permutations: usize,
+) {
     generate_metal_ast(shader_file, |stdout| {
-        generate_shader_function_bindings_from_reader(stdout, writer)
+        generate_shader_function_bindings_from_reader(stdout, writer, max_function_permutations)
     });
 }
 
 pub fn generate_shader_function_bindings_from_reader<R: Read, W: Write>(
     shader_file_reader: R,
     writer: &mut W,
+    max_function_permutations: usize,
 ) {
     let mut w = |s: &str| {
         writer
@@ -72,6 +80,9 @@ pub struct {fn_name}_binds<'c> {{"#
                         ..
                     } => {
                         let rust_shader_bind_name = escape_name(&name);
+                        let data_type = metal_to_rust_type(data_type).unwrap_or_else(|e| {
+                            panic!("Failed to generate binding for buffer `{name}` of shader function `{fn_name}`. {e}")
+                        });
                         w(&format!(
                             r#"
     pub {rust_shader_bind_name}: {bind_type}<'c, {data_type}>,"#
@@ -91,6 +102,20 @@ pub struct {fn_name}_binds<'c> {{"#
     pub {rust_shader_bind_name}: BindAccelerationStructure<'c>,"#
                         ));
                     }
+                    IntersectionFunctionTable { name, .. } => {
+                        let rust_shader_bind_name = escape_name(&name);
+                        w(&format!(
+                            r#"
+    pub {rust_shader_bind_name}: BindIntersectionFunctionTable<'c>,"#
+                        ));
+                    }
+                    VisibleFunctionTable { name, .. } => {
+                        let rust_shader_bind_name = escape_name(&name);
+                        w(&format!(
+                            r#"
+    pub {rust_shader_bind_name}: BindVisibleFunctionTable<'c>,"#
+                        ));
+                    }
                 }
             }
             w(&format!(
@@ -124,6 +149,20 @@ impl Binds for {fn_name}_binds<'_> {{
         {rust_shader_bind_name}: BindAccelerationStructure::Skip,"#
                         ));
                     }
+                    IntersectionFunctionTable { name, .. } => {
+                        let rust_shader_bind_name = escape_name(name);
+                        w(&format!(
+                            r#"
+        {rust_shader_bind_name}: BindIntersectionFunctionTable::Skip,"#
+                        ));
+                    }
+                    VisibleFunctionTable { name, .. } => {
+                        let rust_shader_bind_name = escape_name(name);
+                        w(&format!(
+                            r#"
+        {rust_shader_bind_name}: BindVisibleFunctionTable::Skip,"#
+                        ));
+                    }
                 }
             }
             w(r#"
@@ -135,7 +174,9 @@ impl Binds for {fn_name}_binds<'_> {{
                 match bind {
                     Buffer { name, index, .. }
                     | Texture { name, index, .. }
-                    | AccelerationStructure { name, index } => {
+                    | AccelerationStructure { name, index }
+                    | IntersectionFunctionTable { name, index }
+                    | VisibleFunctionTable { name, index } => {
                         let rust_shader_bind_name = escape_name(name);
                         w(&format!(
                             r#"
@@ -163,6 +204,9 @@ pub struct {rust_shader_name}"#
                 let FunctionConstant {
                     name, data_type, ..
                 } = &fn_consts[usize::from(fn_const_ref)];
+                let data_type = metal_to_rust_function_constant_type(data_type).unwrap_or_else(|e| {
+                    panic!("Failed to generate binding for function constant `{name}` (referenced by shader function `{fn_name}`). {e}")
+                });
                 w(&format!(
                     r#"
     pub {name}: {data_type},"#
@@ -189,6 +233,9 @@ impl metal_app::pipeline::function::Function for {rust_shader_name} {{
                     data_type,
                     index,
                 } = &fn_consts[usize::from(fn_const_ref)];
+                let data_type = metal_to_rust_function_constant_type(data_type).unwrap_or_else(|e| {
+                    panic!("Failed to generate binding for function constant `{name}` (referenced by shader function `{fn_name}`). {e}")
+                });
                 w(&format!(
                     r#"
         fcv.set_constant_value_at_index((&self.{name} as *const _) as _, {data_type}::MTL_DATA_TYPE, {index});"#
@@ -198,12 +245,98 @@ impl metal_app::pipeline::function::Function for {rust_shader_name} {{
         Some(fcv)
     }"#);
         }
-        w(&format!(
-            r#"
+        match shader_type {
+            FunctionType::Vertex | FunctionType::Fragment | FunctionType::Compute => {
+                w(&format!(
+                    r#"
 }}
 impl PipelineFunction<{shader_type_titlecase}FunctionType> for {rust_shader_name} {{}}
 "#
-        ));
+                ));
+                // Only functions referencing exclusively bool function constants have an
+                // enumerable (2^N) permutation space.
+                let fn_consts_referenced: Vec<&FunctionConstant> = referenced_function_constants
+                    .iter()
+                    .map(|r| &fn_consts[usize::from(r)])
+                    .collect();
+                let permutations = 1_usize.checked_shl(fn_consts_referenced.len() as _);
+                if fn_consts_referenced.iter().all(|c| c.data_type == "bool")
+                    && matches!(permutations, Some(p) if p <= max_function_permutations)
+                {
+                    let permutations = permutations.unwrap();
+                    let (index_arg, from_permutation_index) = if fn_consts_referenced.is_empty() {
+                        ("_index", "Self".to_owned())
+                    } else {
+                        let mut fields = String::from("Self {");
+                        for (bit, FunctionConstant { name, .. }) in
+                            fn_consts_referenced.iter().enumerate()
+                        {
+                            // Bit 0 is written without a shift (clippy::identity_op).
+                            let mask = if bit == 0 {
+                                "1".to_owned()
+                            } else {
+                                format!("(1 << {bit})")
+                            };
+                            fields.push_str(&format!(
+                                r#"
+            {name}: (index & {mask}) != 0,"#
+                            ));
+                        }
+                        fields.push_str(
+                            r#"
+        }"#,
+                        );
+                        ("index", fields)
+                    };
+                    let permutation_index = if fn_consts_referenced.is_empty() {
+                        "0".to_owned()
+                    } else {
+                        let terms: Vec<String> = fn_consts_referenced
+                            .iter()
+                            .enumerate()
+                            .map(|(bit, FunctionConstant { name, .. })| {
+                                if bit == 0 {
+                                    format!("(self.{name} as usize)")
+                                } else {
+                                    format!("((self.{name} as usize) << {bit})")
+                                }
+                            })
+                            .collect();
+                        // Wrap like rustfmt once the expression exceeds the line width.
+                        let one_line = terms.join(" | ");
+                        if "        ".len() + one_line.len() <= 100 {
+                            one_line
+                        } else {
+                            terms.join("\n            | ")
+                        }
+                    };
+                    w(&format!(
+                        r#"impl FunctionPermutations for {rust_shader_name} {{
+    const PERMUTATIONS: usize = {permutations};
+    #[inline]
+    fn from_permutation_index({index_arg}: usize) -> Self {{
+        {from_permutation_index}
+    }}
+    #[inline]
+    fn permutation_index(&self) -> usize {{
+        {permutation_index}
+    }}
+}}
+"#
+                    ));
+                }
+            }
+            // Intersection and Visible functions are not part of a pipeline, but are linked to one
+            // and referenced from function tables.
+            FunctionType::Intersection | FunctionType::Visible => {
+                w(&format!(
+                    r#"
+}}
+impl {shader_type_titlecase}Function for {rust_shader_name} {{}}
+"#
+                ));
+            }
+        }
     }
 }
 
@@ -224,7 +357,7 @@ mod test {
 
 #[allow(non_camel_case_types)]
 pub struct test_vertex_binds<'c> {
-    pub buf0: BindMany<'c, float>,
+    pub buf0: BindMany<'c, f32>,
     pub buf1: Bind<'c, float2>,
     pub buf2: BindMany<'c, float3>,
     pub accelerationStructure: BindAccelerationStructure<'c>,
@@ -276,7 +409,7 @@ impl PipelineFunction<VertexFunctionType> for test_vertex {}
 
 #[allow(non_camel_case_types)]
 pub struct test_fragment_binds<'c> {
-    pub buf0: BindMany<'c, float>,
+    pub buf0: BindMany<'c, f32>,
     pub buf1: Bind<'c, float2>,
     pub accelerationStructure: BindAccelerationStructure<'c>,
     pub buf2: BindMany<'c, float3>,
@@ -312,8 +445,8 @@ impl Binds for test_fragment_binds<'_> {
 
 #[allow(non_camel_case_types)]
 pub struct test_fragment {
-    pub A_Float: float,
-    pub A_Uint: uint,
+    pub A_Float: f32,
+    pub A_Uint: u32,
 }
 impl metal_app::pipeline::function::Function for test_fragment {
     const FUNCTION_NAME: &'static str = "test_fragment";
@@ -321,8 +454,8 @@ impl metal_app::pipeline::function::Function for test_fragment {
     #[inline]
     fn get_function_constants(&self) -> Option<FunctionConstantValues> {
         let fcv = FunctionConstantValues::new();
-        fcv.set_constant_value_at_index((&self.A_Float as *const _) as _, float::MTL_DATA_TYPE, 1);
-        fcv.set_constant_value_at_index((&self.A_Uint as *const _) as _, uint::MTL_DATA_TYPE, 3);
+        fcv.set_constant_value_at_index((&self.A_Float as *const _) as _, f32::MTL_DATA_TYPE, 1);
+        fcv.set_constant_value_at_index((&self.A_Uint as *const _) as _, u32::MTL_DATA_TYPE, 3);
         Some(fcv)
     }
 }
@@ -335,7 +468,7 @@ impl PipelineFunction<FragmentFunctionType> for test_fragment {}
                 .expect("Failed to canonicalize path to test_src/deps directory");
             let shader_file = shader_dir.join("shaders.metal");
             let mut actual = Vec::<u8>::new();
-            generate_shader_function_bindings(shader_file, &mut actual);
+            generate_shader_function_bindings(shader_file, &mut actual, 0);
             let actual = unsafe { std::str::from_utf8_unchecked(&actual) };
 
             pretty_assertions::assert_eq!(actual, expected);
@@ -347,8 +480,20 @@ impl PipelineFunction<FragmentFunctionType> for test_fragment {}
         use crate::shader_function_bindings::parse_metal_ast::BindType;
 
         fn test(input: &[u8], expected: &str) {
+            test_with_max_function_permutations(input, expected, 0);
+        }
+
+        fn test_with_max_function_permutations(
+            input: &[u8],
+            expected: &str,
+            max_function_permutations: usize,
+        ) {
             let mut actual = Vec::<u8>::new();
-            generate_shader_function_bindings_from_reader(input, &mut actual);
+            generate_shader_function_bindings_from_reader(
+                input,
+                &mut actual,
+                max_function_permutations,
+            );
             let actual = unsafe { std::str::from_utf8_unchecked(&actual) };
             pretty_assertions::assert_eq!(actual, expected);
         }
@@ -395,6 +540,7 @@ impl PipelineFunction<VertexFunctionType> for test {}
                 multiplicity: &'static str,
                 address_space: &'static str,
                 data_type: &'static str,
+                rust_data_type: &'static str,
                 bind_index: u8,
                 bind_type: BindType,
                 // TODO: Implement buffer immutability (use const generic, like buffer index)
@@ -407,6 +553,7 @@ impl PipelineFunction<VertexFunctionType> for test {}
                 multiplicity,
                 address_space,
                 data_type,
+                rust_data_type,
                 bind_index,
                 bind_type,
                 immutable: _,
@@ -417,6 +564,7 @@ impl PipelineFunction<VertexFunctionType> for test {}
                     multiplicity: "*",
                     address_space: "device",
                     data_type: "uint",
+                    rust_data_type: "u32",
                     bind_index: 0,
                     bind_type: BindType::Many,
                     immutable: false,
@@ -427,6 +575,7 @@ impl PipelineFunction<VertexFunctionType> for test {}
                     multiplicity: "&",
                     address_space: "device",
                     data_type: "TestStruct",
+                    rust_data_type: "TestStruct",
                     bind_index: 1,
                     bind_type: BindType::One,
                     immutable: false,
@@ -437,6 +586,7 @@ impl PipelineFunction<VertexFunctionType> for test {}
                     multiplicity: "*",
                     address_space: "const constant",
                     data_type: "float4",
+                    rust_data_type: "float4",
                     bind_index: 2,
                     bind_type: BindType::Many,
                     immutable: true,
@@ -447,16 +597,51 @@ impl PipelineFunction<VertexFunctionType> for test {}
                     multiplicity: "&",
                     address_space: "const constant",
                     data_type: "float4x4",
+                    rust_data_type: "float4x4",
                     bind_index: 3,
                     bind_type: BindType::One,
                     immutable: true,
                 },
+                Setup {
+                    fn_name: "test5",
+                    bind_name: "buf_f",
+                    multiplicity: "*",
+                    address_space: "device",
+                    data_type: "atomic_uint",
+                    rust_data_type: "u32",
+                    bind_index: 4,
+                    bind_type: BindType::Many,
+                    immutable: false,
+                },
+                Setup {
+                    fn_name: "test6",
+                    bind_name: "buf_g",
+                    multiplicity: "&",
+                    address_space: "const constant",
+                    data_type: "array<float, 4>",
+                    rust_data_type: "[f32; 4]",
+                    bind_index: 5,
+                    bind_type: BindType::One,
+                    immutable: true,
+                },
+                Setup {
+                    fn_name: "test7",
+                    bind_name: "buf_h",
+                    multiplicity: "*",
+                    address_space: "const constant",
+                    data_type: "packed_half3",
+                    rust_data_type: "packed_half3",
+                    bind_index: 6,
+                    bind_type: BindType::Many,
+                    immutable: true,
+                },
                 Setup {
                     fn_name: RUST_KEYWORDS[0],
                     bind_name: "in",
                     multiplicity: "&",
                     address_space: "const constant",
                     data_type: "float4x4",
+                    rust_data_type: "float4x4",
                     bind_index: 3,
                     bind_type: BindType::One,
                     immutable: true,
@@ -491,7 +676,7 @@ TranslationUnitDecl 0x14d8302e8 <<invalid sloc>> <invalid sloc>
 
 #[allow(non_camel_case_types)]
 pub struct {fn_name}_binds<'c> {{
-    pub {rust_shader_bind_name}: {bind_type}<'c, {data_type}>,
+    pub {rust_shader_bind_name}: {bind_type}<'c, {rust_data_type}>,
 }}
 impl Binds for {fn_name}_binds<'_> {{
     const SKIP: Self = Self {{
@@ -517,6 +702,276 @@ impl PipelineFunction<VertexFunctionType> for {rust_shader_name} {{}}
             }
         }
 
+        #[test]
+        fn test_function_permutations() {
+            /*
+            constant constexpr bool  A_Bool  [[function_constant(0)]];
+            constant constexpr bool  B_Bool  [[function_constant(1)]];
+            constant constexpr bool  C_Bool  [[function_constant(2)]];
+            constant constexpr float A_Float [[function_constant(3)]];
+
+            [[vertex]]   float4 test_vertex() { return 0; }
+            [[fragment]] float4 test_fragment() { return A_Bool && B_Bool ? 1 : 0; }
+            [[kernel]]   void test_too_many() { if (A_Bool && B_Bool && C_Bool) {} }
+            [[fragment]] float4 test_not_bool() { return A_Float; }
+            */
+            test_with_max_function_permutations(
+                b"\
+TranslationUnitDecl 0x1598302e8 <<invalid sloc>> <invalid sloc>
+|-TypedefDecl 0x159874860 <<invalid sloc>> <invalid sloc> implicit __metal_intersection_query_t '__metal_intersection_query_t'
+| `-BuiltinType 0x159830f20 '__metal_intersection_query_t'
+|-ImportDecl 0x1598748f0 <<built-in>:1:1> col:1 implicit metal_types
+|-UsingDirectiveDecl 0x159931f50 <line:3:1, col:17> col:17 Namespace 0x1598749f0 'metal'
+|-VarDecl 0x159931ff0 <line:5:1, col:26> col:26 used A_Bool 'const constant bool' constexpr
+| `-MetalFunctionConstantAttr 0x159932050 <col:36, col:55>
+|   `-IntegerLiteral 0x159931fa0 <col:54> 'int' 0
+|-VarDecl 0x159932118 <line:6:1, col:26> col:26 used B_Bool 'const constant bool' constexpr
+| `-MetalFunctionConstantAttr 0x159932178 <col:36, col:55>
+|   `-IntegerLiteral 0x1599320b8 <col:54> 'int' 1
+|-VarDecl 0x159932440 <line:7:1, col:26> col:26 used C_Bool 'const constant bool' constexpr
+| `-MetalFunctionConstantAttr 0x1599324a0 <col:36, col:55>
+|   `-IntegerLiteral 0x1599323d0 <col:54> 'int' 2
+|-VarDecl 0x159932720 <line:8:1, col:26> col:26 used A_Float 'const constant float' constexpr
+| `-MetalFunctionConstantAttr 0x159932780 <col:36, col:55>
+|   `-IntegerLiteral 0x1599326a8 <col:54> 'int' 3
+|-FunctionDecl 0x159946f68 <line:10:14, col:49> col:21 test_vertex 'float4 ()'
+| |-CompoundStmt 0x159947248 <col:35, col:49>
+| | `-ReturnStmt 0x159947230 <col:37, col:44>
+| |   `-ImplicitCastExpr 0x159947218 <col:44> 'float4':'float __attribute__((ext_vector_type(4)))' <VectorSplat>
+| |     `-ImplicitCastExpr 0x159947200 <col:44> 'float' <IntegralToFloating>
+| |       `-IntegerLiteral 0x1599471d0 <col:44> 'int' 0
+| `-MetalVertexAttr 0x159947008 <col:3>
+|-FunctionDecl 0x159947280 <line:11:14, col:73> col:21 test_fragment 'float4 ()'
+| |-CompoundStmt 0x159947598 <col:37, col:73>
+| | `-ReturnStmt 0x159947580 <col:39, col:68>
+| |   `-ImplicitCastExpr 0x159947568 <col:46, col:68> 'float4':'float __attribute__((ext_vector_type(4)))' <VectorSplat>
+| |     `-ImplicitCastExpr 0x159947550 <col:46, col:68> 'float' <IntegralToFloating>
+| |       `-ConditionalOperator 0x159947520 <col:46, col:68> 'int'
+| |         |-BinaryOperator 0x1599474b8 <col:46, col:56> 'bool' '&&'
+| |         | |-ImplicitCastExpr 0x1599473c0 <col:46> 'bool' <LValueToRValue>
+| |         | | `-DeclRefExpr 0x159947378 <col:46> 'const constant bool' lvalue Var 0x159931ff0 'A_Bool' 'const constant bool'
+| |         | `-ImplicitCastExpr 0x159947460 <col:56> 'bool' <LValueToRValue>
+| |         |   `-DeclRefExpr 0x159947418 <col:56> 'const constant bool' lvalue Var 0x159932118 'B_Bool' 'const constant bool'
+| |         |-IntegerLiteral 0x1599474e0 <col:65> 'int' 1
+| |         `-IntegerLiteral 0x159947500 <col:69> 'int' 0
+| `-MetalFragmentAttr 0x159947320 <col:3>
+|-FunctionDecl 0x159947600 <line:12:14, col:76> col:19 test_too_many 'void ()'
+| |-CompoundStmt 0x159947900 <col:35, col:76>
+| | `-IfStmt 0x1599478e0 <col:37, col:74>
+| |   |-BinaryOperator 0x1599478b8 <col:41, col:61> 'bool' '&&'
+| |   | |-BinaryOperator 0x159947868 <col:41, col:51> 'bool' '&&'
+| |   | | |-ImplicitCastExpr 0x159947820 <col:41> 'bool' <LValueToRValue>
+| |   | | | `-DeclRefExpr 0x159947800 <col:41> 'const constant bool' lvalue Var 0x159931ff0 'A_Bool' 'const constant bool'
+| |   | | `-ImplicitCastExpr 0x159947848 <col:51> 'bool' <LValueToRValue>
+| |   | |   `-DeclRefExpr 0x159947828 <col:51> 'const constant bool' lvalue Var 0x159932118 'B_Bool' 'const constant bool'
+| |   | `-ImplicitCastExpr 0x159947898 <col:61> 'bool' <LValueToRValue>
+| |   |   `-DeclRefExpr 0x159947878 <col:61> 'const constant bool' lvalue Var 0x159932440 'C_Bool' 'const constant bool'
+| |   `-CompoundStmt 0x1599478d0 <col:72, col:74>
+| `-MetalKernelAttr 0x1599476a0 <col:3>
+|-FunctionDecl 0x159947a00 <line:13:14, col:58> col:21 test_not_bool 'float4 ()'
+| |-CompoundStmt 0x159947c00 <col:37, col:58>
+| | `-ReturnStmt 0x159947be0 <col:39, col:46>
+| |   `-ImplicitCastExpr 0x159947bc0 <col:46> 'float4':'float __attribute__((ext_vector_type(4)))' <VectorSplat>
+| |     `-ImplicitCastExpr 0x159947ba0 <col:46> 'float' <LValueToRValue>
+| |       `-DeclRefExpr 0x159947b80 <col:46> 'const constant float' lvalue Var 0x159932720 'A_Float' 'const constant float'
+| `-MetalFragmentAttr 0x159947aa0 <col:3>
+`-<undeserialized declarations>
+",
+                r#"
+/****************
+ Shader functions
+*****************/
+
+#[allow(non_camel_case_types)]
+pub struct test_vertex;
+impl metal_app::pipeline::function::Function for test_vertex {
+    const FUNCTION_NAME: &'static str = "test_vertex";
+    type Binds<'c> = NoBinds;
+}
+impl PipelineFunction<VertexFunctionType> for test_vertex {}
+impl FunctionPermutations for test_vertex {
+    const PERMUTATIONS: usize = 1;
+    #[inline]
+    fn from_permutation_index(_index: usize) -> Self {
+        Self
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        0
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub struct test_fragment {
+    pub A_Bool: bool,
+    pub B_Bool: bool,
+}
+impl metal_app::pipeline::function::Function for test_fragment {
+    const FUNCTION_NAME: &'static str = "test_fragment";
+    type Binds<'c> = NoBinds;
+    #[inline]
+    fn get_function_constants(&self) -> Option<FunctionConstantValues> {
+        let fcv = FunctionConstantValues::new();
+        fcv.set_constant_value_at_index((&self.A_Bool as *const _) as _, bool::MTL_DATA_TYPE, 0);
+        fcv.set_constant_value_at_index((&self.B_Bool as *const _) as _, bool::MTL_DATA_TYPE, 1);
+        Some(fcv)
+    }
+}
+impl PipelineFunction<FragmentFunctionType> for test_fragment {}
+impl FunctionPermutations for test_fragment {
+    const PERMUTATIONS: usize = 4;
+    #[inline]
+    fn from_permutation_index(index: usize) -> Self {
+        Self {
+            A_Bool: (index & 1) != 0,
+            B_Bool: (index & (1 << 1)) != 0,
+        }
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        (self.A_Bool as usize) | ((self.B_Bool as usize) << 1)
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub struct test_too_many {
+    pub A_Bool: bool,
+    pub B_Bool: bool,
+    pub C_Bool: bool,
+}
+impl metal_app::pipeline::function::Function for test_too_many {
+    const FUNCTION_NAME: &'static str = "test_too_many";
+    type Binds<'c> = NoBinds;
+    #[inline]
+    fn get_function_constants(&self) -> Option<FunctionConstantValues> {
+        let fcv = FunctionConstantValues::new();
+        fcv.set_constant_value_at_index((&self.A_Bool as *const _) as _, bool::MTL_DATA_TYPE, 0);
+        fcv.set_constant_value_at_index((&self.B_Bool as *const _) as _, bool::MTL_DATA_TYPE, 1);
+        fcv.set_constant_value_at_index((&self.C_Bool as *const _) as _, bool::MTL_DATA_TYPE, 2);
+        Some(fcv)
+    }
+}
+impl PipelineFunction<ComputeFunctionType> for test_too_many {}
+
+#[allow(non_camel_case_types)]
+pub struct test_not_bool {
+    pub A_Float: f32,
+}
+impl metal_app::pipeline::function::Function for test_not_bool {
+    const FUNCTION_NAME: &'static str = "test_not_bool";
+    type Binds<'c> = NoBinds;
+    #[inline]
+    fn get_function_constants(&self) -> Option<FunctionConstantValues> {
+        let fcv = FunctionConstantValues::new();
+        fcv.set_constant_value_at_index((&self.A_Float as *const _) as _, f32::MTL_DATA_TYPE, 3);
+        Some(fcv)
+    }
+}
+impl PipelineFunction<FragmentFunctionType> for test_not_bool {}
+"#,
+                4,
+            );
+        }
+
+        #[test]
+        fn test_function_tables() {
+            test(
+                b"\
+TranslationUnitDecl 0x13a8302e8 <<invalid sloc>> <invalid sloc>
+|-TypedefDecl 0x13a875660 <<invalid sloc>> <invalid sloc> implicit __metal_intersection_query_t '__metal_intersection_query_t'
+| `-BuiltinType 0x13a830f20 '__metal_intersection_query_t'
+|-ImportDecl 0x13a8756f0 <<built-in>:1:1> col:1 implicit metal_types
+|-UsingDirectiveDecl 0x13a934150 <line:2:1, col:17> col:17 Namespace 0x13a875828 'metal'
+|-UsingDirectiveDecl 0x13a9341d0 <line:3:1, col:17> col:17 Namespace 0x13a934228 'raytracing'
+|-FunctionDecl 0x13a846898 <line:6:1, line:8:1> line:6:6 test_intersection 'bool (float2)'
+| |-ParmVarDecl 0x13a846700 <col:24, col:31> col:31 used barycentric_coord 'float2':'float __attribute__((ext_vector_type(2)))'
+| | `-MetalBaryCoordAttr 0x13a846768 <col:51>
+| |-CompoundStmt 0x13a846a80 <col:72, line:8:1>
+| | `-ReturnStmt 0x13a846a68 <line:7:5, col:35>
+| |   `-BinaryOperator 0x13a846a48 <col:12, col:35> 'bool' '>'
+| |     |-ImplicitCastExpr 0x13a846a18 <col:12, col:30> 'float' <LValueToRValue>
+| |     | `-ExtVectorElementExpr 0x13a8469d0 <col:12, col:30> 'float' lvalue vectorcomponent x
+| |     |   `-DeclRefExpr 0x13a8469a8 <col:12> 'float2':'float __attribute__((ext_vector_type(2)))' lvalue ParmVar 0x13a846700 'barycentric_coord' 'float2':'float __attribute__((ext_vector_type(2)))'
+| |     `-FloatingLiteral 0x13a8469f8 <col:35> 'float' 5.000000e-01
+| `-MetalIntersectionAttr 0x13a846a08 <line:5:3, col:40> Triangle TriangleData
+|-FunctionDecl 0x13a846c10 <line:11:1, col:41> col:7 test_visible 'float (float)'
+| |-ParmVarDecl 0x13a846b10 <col:20, col:26> col:26 used x 'float'
+| |-CompoundStmt 0x13a846d08 <col:29, col:41>
+| | `-ReturnStmt 0x13a846cf0 <col:31, col:38>
+| |   `-ImplicitCastExpr 0x13a846cd8 <col:38> 'float' <LValueToRValue>
+| |     `-DeclRefExpr 0x13a846cb8 <col:38> 'float' lvalue ParmVar 0x13a846b10 'x' 'float'
+| `-MetalVisibleAttr 0x13a846b50 <line:10:3>
+|-FunctionDecl 0x13a847658 <line:14:1, line:20:1> line:14:7 test 'half4 (metal::raytracing::primitive_acceleration_structure, metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>, metal::visible_function_table<float (float)>)'
+| |-ParmVarDecl 0x13a847130 <line:15:5, col:49> col:49 accel_struct 'metal::raytracing::primitive_acceleration_structure':'metal::raytracing::_acceleration_structure<>'
+| | `-MetalBufferIndexAttr 0x13a847548 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a847100 <col:69> 'int' 0
+| |-ParmVarDecl 0x13a8471d0 <line:16:5, col:49> col:49 i_fns 'metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>':'metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>'
+| | `-MetalBufferIndexAttr 0x13a8475a8 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a8471a0 <col:69> 'int' 1
+| |-ParmVarDecl 0x13a8472d0 <line:17:5, col:49> col:49 v_fns 'metal::visible_function_table<float (float)>':'metal::visible_function_table<float (float)>'
+| | `-MetalBufferIndexAttr 0x13a847608 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a8472a0 <col:69> 'int' 2
+| |-CompoundStmt 0x13a8477c0 <line:18:3, line:20:1>
+| | `-ReturnStmt 0x13a8477a8 <line:19:5, col:12>
+| |   `-ImplicitCastExpr 0x13a847790 <col:12> 'half4':'half __attribute__((ext_vector_type(4)))' <VectorSplat>
+| |     `-ImplicitCastExpr 0x13a847778 <col:12> 'half' <IntegralToFloating>
+| |       `-IntegerLiteral 0x13a847758 <col:12> 'int' 0
+| `-MetalFragmentAttr 0x13a847700 <line:13:3>
+`-<undeserialized declarations>
+",
+                r#"
+/****************
+ Shader functions
+*****************/
+
+#[allow(non_camel_case_types)]
+pub struct test_intersection;
+impl metal_app::pipeline::function::Function for test_intersection {
+    const FUNCTION_NAME: &'static str = "test_intersection";
+    type Binds<'c> = NoBinds;
+}
+impl IntersectionFunction for test_intersection {}
+
+#[allow(non_camel_case_types)]
+pub struct test_visible;
+impl metal_app::pipeline::function::Function for test_visible {
+    const FUNCTION_NAME: &'static str = "test_visible";
+    type Binds<'c> = NoBinds;
+}
+impl VisibleFunction for test_visible {}
+
+#[allow(non_camel_case_types)]
+pub struct test_binds<'c> {
+    pub accel_struct: BindAccelerationStructure<'c>,
+    pub i_fns: BindIntersectionFunctionTable<'c>,
+    pub v_fns: BindVisibleFunctionTable<'c>,
+}
+impl Binds for test_binds<'_> {
+    const SKIP: Self = Self {
+        accel_struct: BindAccelerationStructure::Skip,
+        i_fns: BindIntersectionFunctionTable::Skip,
+        v_fns: BindVisibleFunctionTable::Skip,
+    };
+
+    #[inline(always)]
+    fn bind<F: PipelineFunctionType>(self, encoder: &F::CommandEncoder) {
+        self.accel_struct.bind::<F>(encoder, 0);
+        self.i_fns.bind::<F>(encoder, 1);
+        self.v_fns.bind::<F>(encoder, 2);
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub struct test;
+impl metal_app::pipeline::function::Function for test {
+    const FUNCTION_NAME: &'static str = "test";
+    type Binds<'c> = test_binds<'c>;
+}
+impl PipelineFunction<FragmentFunctionType> for test {}
+"#,
+            );
+        }
+
         #[test]
         fn test_bind_texture() {
             let fn_name = "test7";
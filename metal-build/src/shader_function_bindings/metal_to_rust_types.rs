@@ -0,0 +1,431 @@
+use std::fmt::Display;
+
+/*
+Maps Metal Shading Language (MSL) types, as they appear in the Metal (Clang) AST, to the Rust types
+used in the generated shader function bindings.
+
+- Scalars map to Rust primitives (ex. `float` -> `f32`, `uint` -> `u32`, `half` -> `f16`)
+- Vectors and matrices map to the `metal-types` type of the same name (ex. `float4`,
+  `packed_half3`, `float4x4`). Aliases (`simd_float4x4`, `vector_float4`, `vec<float, 4>`,
+  `matrix<float, 4, 4>`) are normalized to the canonical MSL name first.
+- Atomics map to the Rust type of the underlying scalar (ex. `atomic_uint` -> `u32`). The CPU only
+  ever initializes or reads back these buffers, so the plain (same size and alignment) type is used.
+- Arrays (`metal::array<T, N>`) map to `[T; N]`.
+- Anything else that looks like a user defined type (ex. `ProjectedSpace`) is passed through as-is,
+  expected to be generated by bindgen from `shader_bindings.h` or provided by `metal-types`.
+
+Everything else (ex. `double`, `bfloat4`, `long2`) has no Rust equivalent and is an error.
+*/
+
+// Spec: 2.1 Scalar Data Types / Table 2.1. Metal scalar data types
+// (MSL Name, Rust Type)
+const SCALAR_TYPES: &[(&'static str, &'static str)] = &[
+    ("bool", "bool"),
+    ("char", "i8"),
+    ("int8_t", "i8"),
+    ("signed char", "i8"),
+    ("uchar", "u8"),
+    ("uint8_t", "u8"),
+    ("unsigned char", "u8"),
+    ("short", "i16"),
+    ("int16_t", "i16"),
+    ("ushort", "u16"),
+    ("uint16_t", "u16"),
+    ("unsigned short", "u16"),
+    ("int", "i32"),
+    ("int32_t", "i32"),
+    ("uint", "u32"),
+    ("uint32_t", "u32"),
+    ("unsigned int", "u32"),
+    ("long", "i64"),
+    ("int64_t", "i64"),
+    ("ulong", "u64"),
+    ("uint64_t", "u64"),
+    ("half", "f16"),
+    ("float", "f32"),
+];
+
+// Scalar types with a vector (and packed vector) form. Only vectors and matrices with a matching
+// `metal-types` type are supported (see `metal-types/src/rust_bindgen_only_metal_types.h`).
+const VECTOR_SCALAR_TYPES: &[&'static str] = &[
+    "bool", "char", "uchar", "short", "ushort", "int", "uint", "long", "ulong", "half", "float",
+    "double", "bfloat",
+];
+
+// Spec: 2.15 Atomic Data Types
+const ATOMIC_SCALAR_TYPES: &[&'static str] = &["int", "uint", "bool", "ulong", "float"];
+
+// Scalar types a function constant (or vector function constant) can be, those with a
+// `metal_app::pipeline::HasMTLDataType` implementation. Notably missing 64-bit integers (`long`,
+// `ulong`), metal-app has no `MTLDataType` for them.
+const FUNCTION_CONSTANT_SCALAR_TYPES: &[&'static str] = &[
+    "bool", "char", "uchar", "short", "ushort", "int", "uint", "half", "float",
+];
+
+#[derive(PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct UnsupportedMetalType(pub String);
+
+impl Display for UnsupportedMetalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unsupported Metal type `{}`, there is no equivalent Rust type (see metal-build's `metal_to_rust_types.rs` for supported types)",
+            self.0
+        )
+    }
+}
+
+#[derive(PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct UnsupportedFunctionConstantType(pub String);
+
+impl Display for UnsupportedFunctionConstantType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unsupported function constant type `{}`, only {} scalars and vectors are supported (see metal-build's `metal_to_rust_types.rs`)",
+            self.0,
+            FUNCTION_CONSTANT_SCALAR_TYPES.join("/")
+        )
+    }
+}
+
+#[inline]
+fn scalar_to_rust_type(metal_type: &str) -> Option<&'static str> {
+    SCALAR_TYPES
+        .iter()
+        .find(|(name, _)| *name == metal_type)
+        .map(|&(_, rust_type)| rust_type)
+}
+
+// Splits a template type (ex. `array<float, 4>`) into it's name and arguments
+// (ex. `("array", ["float", "4"])`), respecting nested template arguments.
+fn split_template(metal_type: &str) -> Option<(&str, Vec<&str>)> {
+    let (name, args) = metal_type.strip_suffix('>')?.split_once('<')?;
+    let mut depth = 0;
+    let mut start = 0;
+    let mut split_args = vec![];
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                split_args.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split_args.push(args[start..].trim());
+    Some((name.trim(), split_args))
+}
+
+// Whether the type name is of the form of a vector or matrix type name (ex. `packed_float3`,
+// `half3x3`), regardless of whether it is a valid or supported vector or matrix type.
+fn is_vector_or_matrix(metal_type: &str) -> bool {
+    let name = metal_type.strip_prefix("packed_").unwrap_or(metal_type);
+    match name.find(|c: char| c.is_ascii_digit()) {
+        Some(i) => {
+            let (scalar, dims) = name.split_at(i);
+            VECTOR_SCALAR_TYPES.contains(&scalar)
+                && dims.chars().all(|c| c.is_ascii_digit() || c == 'x')
+        }
+        None => false,
+    }
+}
+
+// Normalizes aliases of vector and matrix types to their canonical MSL name.
+// Example: `simd_float4x4`, `matrix_float4x4` and `matrix<float, 4, 4>` -> `float4x4`
+fn normalize_vector_or_matrix_alias(metal_type: &str) -> Option<String> {
+    if let Some((name, args)) = split_template(metal_type) {
+        return match (name, &args[..]) {
+            ("vec", &[scalar, n]) => Some(format!("{scalar}{n}")),
+            ("packed_vec", &[scalar, n]) => Some(format!("packed_{scalar}{n}")),
+            ("matrix", &[scalar, cols, rows]) => Some(format!("{scalar}{cols}x{rows}")),
+            _ => None,
+        };
+    }
+    for (prefix, replacement) in [
+        ("simd_packed_", "packed_"),
+        ("simd_", ""),
+        ("vector_", ""),
+        ("matrix_", ""),
+    ] {
+        if let Some(name) = metal_type.strip_prefix(prefix) {
+            return Some(format!("{replacement}{name}"));
+        }
+    }
+    None
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub fn metal_to_rust_type(metal_type: &str) -> Result<String, UnsupportedMetalType> {
+    let unsupported = || UnsupportedMetalType(metal_type.to_owned());
+    let normalized = metal_type.replace("metal::", "");
+    let metal_type_name = normalized.trim();
+
+    // Scalars
+    if let Some(rust_type) = scalar_to_rust_type(metal_type_name) {
+        return Ok(rust_type.to_owned());
+    }
+
+    // Atomics
+    if let Some(scalar) = metal_type_name.strip_prefix("atomic_").or_else(|| {
+        split_template(metal_type_name)
+            .filter(|(name, args)| *name == "atomic" && args.len() == 1)
+            .map(|(_, args)| args[0])
+    }) {
+        return ATOMIC_SCALAR_TYPES
+            .contains(&scalar)
+            .then(|| scalar_to_rust_type(scalar))
+            .flatten()
+            .map(str::to_owned)
+            .ok_or_else(unsupported);
+    }
+
+    // Arrays
+    if let Some(("array", args)) = split_template(metal_type_name) {
+        if let &[element_type, len] = &args[..] {
+            let len: usize = len.parse().map_err(|_| unsupported())?;
+            let element_type = metal_to_rust_type(element_type).map_err(|_| unsupported())?;
+            return Ok(format!("[{element_type}; {len}]"));
+        }
+        return Err(unsupported());
+    }
+
+    // Vectors and Matrices
+    let canonical_name = normalize_vector_or_matrix_alias(metal_type_name)
+        .filter(|name| is_vector_or_matrix(name))
+        .unwrap_or_else(|| metal_type_name.to_owned());
+    if is_vector_or_matrix(&canonical_name) {
+        return if metal_types::TYPES.contains(&canonical_name.as_str()) {
+            Ok(canonical_name)
+        } else {
+            Err(unsupported())
+        };
+    }
+
+    // User defined types (ex. structs and enums defined in `shader_bindings.h`)
+    if is_identifier(metal_type_name)
+        && !matches!(
+            metal_type_name,
+            "double" | "bfloat" | "size_t" | "ptrdiff_t"
+        )
+    {
+        return Ok(metal_type_name.to_owned());
+    }
+    Err(unsupported())
+}
+
+// Same as `metal_to_rust_type()`, but for function constants: only scalars and (non-packed) vectors
+// (Spec: 5.8.1 Specifying a Function Constant) with a `metal_app::pipeline::HasMTLDataType`
+// implementation (see `FUNCTION_CONSTANT_SCALAR_TYPES`).
+pub fn metal_to_rust_function_constant_type(
+    metal_type: &str,
+) -> Result<String, UnsupportedFunctionConstantType> {
+    let unsupported = || UnsupportedFunctionConstantType(metal_type.to_owned());
+    // Atomics map to the plain scalar type (see `metal_to_rust_type()`)
+    if metal_type.replace("metal::", "").trim().starts_with("atomic") {
+        return Err(unsupported());
+    }
+    let rust_type = metal_to_rust_type(metal_type).map_err(|_| unsupported())?;
+    let is_supported = FUNCTION_CONSTANT_SCALAR_TYPES.iter().any(|&scalar| {
+        scalar_to_rust_type(scalar) == Some(&rust_type)
+            || (rust_type.len() == scalar.len() + 1
+                && rust_type.starts_with(scalar)
+                && matches!(rust_type.as_bytes()[scalar.len()], b'2'..=b'4'))
+    });
+    if is_supported {
+        Ok(rust_type)
+    } else {
+        Err(unsupported())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_maps(metal_type: &str, expected_rust_type: &str) {
+        pretty_assertions::assert_eq!(
+            metal_to_rust_type(metal_type),
+            Ok(expected_rust_type.to_owned()),
+            "Metal type: {metal_type}"
+        );
+    }
+
+    fn assert_unsupported(metal_type: &str) {
+        pretty_assertions::assert_eq!(
+            metal_to_rust_type(metal_type),
+            Err(UnsupportedMetalType(metal_type.to_owned())),
+            "Metal type: {metal_type}"
+        );
+    }
+
+    #[test]
+    fn test_scalars() {
+        for (metal_type, expected) in [
+            ("bool", "bool"),
+            ("char", "i8"),
+            ("uchar", "u8"),
+            ("short", "i16"),
+            ("ushort", "u16"),
+            ("int", "i32"),
+            ("uint", "u32"),
+            ("unsigned int", "u32"),
+            ("long", "i64"),
+            ("ulong", "u64"),
+            ("half", "f16"),
+            ("float", "f32"),
+            ("metal::uint", "u32"),
+        ] {
+            assert_maps(metal_type, expected);
+        }
+        for metal_type in ["double", "bfloat", "size_t", "ptrdiff_t"] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        for scalar in [
+            "bool", "char", "uchar", "short", "ushort", "int", "uint", "half", "float",
+        ] {
+            for n in 2..=4 {
+                let vector = format!("{scalar}{n}");
+                assert_maps(&vector, &vector);
+                assert_maps(&format!("metal::{vector}"), &vector);
+                assert_maps(&format!("vec<{scalar}, {n}>"), &vector);
+                if scalar != "bool" {
+                    let packed = format!("packed_{scalar}{n}");
+                    assert_maps(&packed, &packed);
+                    assert_maps(&format!("packed_vec<{scalar}, {n}>"), &packed);
+                }
+            }
+        }
+        assert_maps("simd_float4", "float4");
+        assert_maps("simd_packed_float4", "packed_float4");
+        assert_maps("vector_uint2", "uint2");
+        for metal_type in [
+            "packed_bool3",
+            "long2",
+            "ulong4",
+            "double3",
+            "bfloat4",
+            "float5",
+        ] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_matrices() {
+        for scalar in ["half", "float"] {
+            for cols in 2..=4 {
+                for rows in 2..=4 {
+                    let matrix = format!("{scalar}{cols}x{rows}");
+                    assert_maps(&matrix, &matrix);
+                    assert_maps(&format!("simd_{matrix}"), &matrix);
+                    assert_maps(&format!("matrix_{matrix}"), &matrix);
+                    assert_maps(&format!("matrix<{scalar}, {cols}, {rows}>"), &matrix);
+                }
+            }
+        }
+        for metal_type in ["int3x3", "double4x4", "float4x5", "float1x4"] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_atomics() {
+        for (metal_type, expected) in [
+            ("atomic_int", "i32"),
+            ("atomic_uint", "u32"),
+            ("atomic_bool", "bool"),
+            ("atomic_ulong", "u64"),
+            ("atomic_float", "f32"),
+            ("atomic<uint>", "u32"),
+            ("metal::atomic<float>", "f32"),
+        ] {
+            assert_maps(metal_type, expected);
+        }
+        for metal_type in ["atomic_half", "atomic<float4>", "atomic_"] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_arrays() {
+        for (metal_type, expected) in [
+            ("array<float, 4>", "[f32; 4]"),
+            ("metal::array<float,4>", "[f32; 4]"),
+            ("array<float4, 2>", "[float4; 2]"),
+            ("array<metal::array<uint, 3>, 2>", "[[u32; 3]; 2]"),
+            ("array<ProjectedSpace, 6>", "[ProjectedSpace; 6]"),
+            ("array<atomic_uint, 8>", "[u32; 8]"),
+        ] {
+            assert_maps(metal_type, expected);
+        }
+        for metal_type in ["array<double, 4>", "array<float, N>", "array<float>"] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_user_defined_types() {
+        for metal_type in [
+            "ProjectedSpace",
+            "TestStruct",
+            "_private_struct",
+            "float_pair",
+        ] {
+            assert_maps(metal_type, metal_type);
+        }
+        for metal_type in ["my_namespace::MyStruct", "texture2d<half>", "2d_struct"] {
+            assert_unsupported(metal_type);
+        }
+    }
+
+    #[test]
+    fn test_function_constant_types() {
+        for (metal_type, expected) in [
+            ("bool", "bool"),
+            ("uchar", "u8"),
+            ("unsigned int", "u32"),
+            ("half", "f16"),
+            ("float", "f32"),
+            ("float4", "float4"),
+            ("vec<ushort, 2>", "ushort2"),
+            ("metal::bool3", "bool3"),
+        ] {
+            pretty_assertions::assert_eq!(
+                metal_to_rust_function_constant_type(metal_type),
+                Ok(expected.to_owned()),
+                "Metal type: {metal_type}"
+            );
+        }
+        for metal_type in [
+            "long",
+            "ulong",
+            "uint64_t",
+            "packed_float3",
+            "float4x4",
+            "array<float, 4>",
+            "atomic_uint",
+            "ShadingMode",
+            "double",
+        ] {
+            pretty_assertions::assert_eq!(
+                metal_to_rust_function_constant_type(metal_type),
+                Err(UnsupportedFunctionConstantType(metal_type.to_owned())),
+                "Metal type: {metal_type}"
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 mod generate_metal_ast;
 mod generate_rust_bindings;
+mod metal_to_rust_types;
 mod parse_metal_ast;
 
 pub use generate_rust_bindings::generate_shader_function_bindings;
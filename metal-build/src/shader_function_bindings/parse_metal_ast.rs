@@ -44,6 +44,14 @@ pub enum Binds {
         index: u8,
         name: String,
     },
+    IntersectionFunctionTable {
+        index: u8,
+        name: String,
+    },
+    VisibleFunctionTable {
+        index: u8,
+        name: String,
+    },
 }
 impl Binds {
     pub const INVALID_INDEX: u8 = u8::MAX;
@@ -65,6 +73,8 @@ impl Binds {
             },
             Texture { name, .. } => Texture { index, name },
             AccelerationStructure { name, .. } => AccelerationStructure { index, name },
+            IntersectionFunctionTable { name, .. } => IntersectionFunctionTable { index, name },
+            VisibleFunctionTable { name, .. } => VisibleFunctionTable { index, name },
         }
     }
 }
@@ -75,6 +85,8 @@ pub enum FunctionType {
     Vertex,
     Fragment,
     Compute,
+    Intersection,
+    Visible,
 }
 
 impl FunctionType {
@@ -83,6 +95,8 @@ impl FunctionType {
             FunctionType::Vertex => "Vertex",
             FunctionType::Fragment => "Fragment",
             FunctionType::Compute => "Compute",
+            FunctionType::Intersection => "Intersection",
+            FunctionType::Visible => "Visible",
         }
     }
 }
@@ -274,7 +288,8 @@ pub fn parse_shader_functions_from_reader<R: Read>(
     // Example: | |-ParmVarDecl 0x14a132638 <line:10:5, col:29> col:29 yolo 'const constant packed_float4 *'
     // Example: | |-ParmVarDecl 0x116879d78 <line:7:5, col:21> col:21 tex0 'texture2d<half>':'metal::texture2d<half, metal::access::sample, void>'
     // Example: | |-ParmVarDecl 0x12614a0d0 <line:10:5, col:37> col:37 accelerationStructure 'metal::raytracing::instance_acceleration_structure':'metal::raytracing::_acceleration_structure<metal::raytracing::instancing>'
-    let rx_fn_param = Regex::new(r"^\| (?P<last_child>[`|])-ParmVarDecl 0x[0-9a-f]+ <(line|col)(:\d+)+, (line|col)(:\d+)+> (line|col)(:\d+)+( used)? (?P<name>\w+) '(?P<address_space>const constant |device |)(metal::)?(?P<data_type>\w[\w:<>, ]+)(?P<multiplicity> [*&]|)'").unwrap();
+    // Example: | |-ParmVarDecl 0x13a8460d0 <line:12:5, col:49> col:49 fns 'metal::visible_function_table<float (float)>':'metal::visible_function_table<float (float)>'
+    let rx_fn_param = Regex::new(r"^\| (?P<last_child>[`|])-ParmVarDecl 0x[0-9a-f]+ <(line|col)(:\d+)+, (line|col)(:\d+)+> (line|col)(:\d+)+( used)? (?P<name>\w+) '(?P<address_space>const constant |device |)(metal::)?(?P<data_type>\w[\w:<>, ()]+)(?P<multiplicity> [*&]|)'").unwrap();
 
     // Example: | | `-MetalBufferIndexAttr 0x14a132698 <col:36, col:44>
     let rx_fn_param_metal_buffer_texture_index_attr = Regex::new(
@@ -290,9 +305,12 @@ pub fn parse_shader_functions_from_reader<R: Read>(
 
     // Example: | `-MetalVertexAttr 0x14a132850 <line:8:3>
     // Example: | `-MetalFragmentAttr 0x14a132850 <line:8:3>
-    let rx_fn_metal_shader_type_attr =
-        Regex::new(r"^\| (?P<last_child>[`|])-Metal(?P<shader_type>Vertex|Fragment|Kernel)Attr ")
-            .unwrap();
+    // Example: | `-MetalIntersectionAttr 0x13a846a08 <line:8:3, col:40> Triangle TriangleData
+    // Example: | `-MetalVisibleAttr 0x13a846b50 <line:14:3>
+    let rx_fn_metal_shader_type_attr = Regex::new(
+        r"^\| (?P<last_child>[`|])-Metal(?P<shader_type>Vertex|Fragment|Kernel|Intersection|Visible)Attr ",
+    )
+    .unwrap();
 
     // Example: | `-
     let rx_fn_last_child = Regex::new(r"^\| `-").unwrap();
@@ -388,6 +406,8 @@ pub fn parse_shader_functions_from_reader<R: Read>(
                         "Vertex" => fun.shader_type = Some(FunctionType::Vertex),
                         "Fragment" => fun.shader_type = Some(FunctionType::Fragment),
                         "Kernel" => fun.shader_type = Some(FunctionType::Compute),
+                        "Intersection" => fun.shader_type = Some(FunctionType::Intersection),
+                        "Visible" => fun.shader_type = Some(FunctionType::Visible),
                         _ => panic!("Unexpected Metal function attribute ({shader_type})"),
                     }
                     // TODO: This may not be true for compute or object functions with parameterized attributes
@@ -433,6 +453,18 @@ pub fn parse_shader_functions_from_reader<R: Read>(
                                     index: Binds::INVALID_INDEX,
                                     name: info.name,
                                 }
+                            } else if data_type
+                                .starts_with("raytracing::intersection_function_table<")
+                            {
+                                Binds::IntersectionFunctionTable {
+                                    index: Binds::INVALID_INDEX,
+                                    name: info.name,
+                                }
+                            } else if data_type.starts_with("visible_function_table<") {
+                                Binds::VisibleFunctionTable {
+                                    index: Binds::INVALID_INDEX,
+                                    name: info.name,
+                                }
                             } else {
                                 Binds::Buffer {
                                     index: Binds::INVALID_INDEX,
@@ -909,6 +941,110 @@ TranslationUnitDecl 0x1428302e8 <<invalid sloc>> <invalid sloc>
             );
         }
 
+        #[test]
+        fn test_shader_with_function_tables() {
+            /*
+            #include <metal_stdlib>
+            using namespace metal;
+            using namespace raytracing;
+
+            [[intersection(triangle, triangle_data)]]
+            bool test_intersection(float2 barycentric_coord [[barycentric_coord]]) {
+                return barycentric_coord.x > 0.5;
+            }
+
+            [[visible]]
+            float test_visible(float x) { return x; }
+
+            [[fragment]]
+            half4 test(
+                primitive_acceleration_structure            accel_struct [[buffer(0)]],
+                intersection_function_table<triangle_data>  i_fns        [[buffer(1)]],
+                visible_function_table<float (float)>       v_fns        [[buffer(2)]]
+            ) {
+                return 0;
+            }
+            */
+            test(b"\
+TranslationUnitDecl 0x13a8302e8 <<invalid sloc>> <invalid sloc>
+|-TypedefDecl 0x13a875660 <<invalid sloc>> <invalid sloc> implicit __metal_intersection_query_t '__metal_intersection_query_t'
+| `-BuiltinType 0x13a830f20 '__metal_intersection_query_t'
+|-ImportDecl 0x13a8756f0 <<built-in>:1:1> col:1 implicit metal_types
+|-UsingDirectiveDecl 0x13a934150 <line:2:1, col:17> col:17 Namespace 0x13a875828 'metal'
+|-UsingDirectiveDecl 0x13a9341d0 <line:3:1, col:17> col:17 Namespace 0x13a934228 'raytracing'
+|-FunctionDecl 0x13a846898 <line:6:1, line:8:1> line:6:6 test_intersection 'bool (float2)'
+| |-ParmVarDecl 0x13a846700 <col:24, col:31> col:31 used barycentric_coord 'float2':'float __attribute__((ext_vector_type(2)))'
+| | `-MetalBaryCoordAttr 0x13a846768 <col:51>
+| |-CompoundStmt 0x13a846a80 <col:72, line:8:1>
+| | `-ReturnStmt 0x13a846a68 <line:7:5, col:35>
+| |   `-BinaryOperator 0x13a846a48 <col:12, col:35> 'bool' '>'
+| |     |-ImplicitCastExpr 0x13a846a18 <col:12, col:30> 'float' <LValueToRValue>
+| |     | `-ExtVectorElementExpr 0x13a8469d0 <col:12, col:30> 'float' lvalue vectorcomponent x
+| |     |   `-DeclRefExpr 0x13a8469a8 <col:12> 'float2':'float __attribute__((ext_vector_type(2)))' lvalue ParmVar 0x13a846700 'barycentric_coord' 'float2':'float __attribute__((ext_vector_type(2)))'
+| |     `-FloatingLiteral 0x13a8469f8 <col:35> 'float' 5.000000e-01
+| `-MetalIntersectionAttr 0x13a846a08 <line:5:3, col:40> Triangle TriangleData
+|-FunctionDecl 0x13a846c10 <line:11:1, col:41> col:7 test_visible 'float (float)'
+| |-ParmVarDecl 0x13a846b10 <col:20, col:26> col:26 used x 'float'
+| |-CompoundStmt 0x13a846d08 <col:29, col:41>
+| | `-ReturnStmt 0x13a846cf0 <col:31, col:38>
+| |   `-ImplicitCastExpr 0x13a846cd8 <col:38> 'float' <LValueToRValue>
+| |     `-DeclRefExpr 0x13a846cb8 <col:38> 'float' lvalue ParmVar 0x13a846b10 'x' 'float'
+| `-MetalVisibleAttr 0x13a846b50 <line:10:3>
+|-FunctionDecl 0x13a847658 <line:14:1, line:20:1> line:14:7 test 'half4 (metal::raytracing::primitive_acceleration_structure, metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>, metal::visible_function_table<float (float)>)'
+| |-ParmVarDecl 0x13a847130 <line:15:5, col:49> col:49 accel_struct 'metal::raytracing::primitive_acceleration_structure':'metal::raytracing::_acceleration_structure<>'
+| | `-MetalBufferIndexAttr 0x13a847548 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a847100 <col:69> 'int' 0
+| |-ParmVarDecl 0x13a8471d0 <line:16:5, col:49> col:49 i_fns 'metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>':'metal::raytracing::intersection_function_table<metal::raytracing::triangle_data>'
+| | `-MetalBufferIndexAttr 0x13a8475a8 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a8471a0 <col:69> 'int' 1
+| |-ParmVarDecl 0x13a8472d0 <line:17:5, col:49> col:49 v_fns 'metal::visible_function_table<float (float)>':'metal::visible_function_table<float (float)>'
+| | `-MetalBufferIndexAttr 0x13a847608 <col:62, col:70>
+| |   `-IntegerLiteral 0x13a8472a0 <col:69> 'int' 2
+| |-CompoundStmt 0x13a8477c0 <line:18:3, line:20:1>
+| | `-ReturnStmt 0x13a8477a8 <line:19:5, col:12>
+| |   `-ImplicitCastExpr 0x13a847790 <col:12> 'half4':'half __attribute__((ext_vector_type(4)))' <VectorSplat>
+| |     `-ImplicitCastExpr 0x13a847778 <col:12> 'half' <IntegralToFloating>
+| |       `-IntegerLiteral 0x13a847758 <col:12> 'int' 0
+| `-MetalFragmentAttr 0x13a847700 <line:13:3>
+`-<undeserialized declarations>
+",
+                [],
+                [
+                    Function {
+                        fn_name: "test_intersection".to_owned(),
+                        binds: vec![],
+                        shader_type: FunctionType::Intersection,
+                        referenced_function_constants: BTreeSet::new(),
+                    },
+                    Function {
+                        fn_name: "test_visible".to_owned(),
+                        binds: vec![],
+                        shader_type: FunctionType::Visible,
+                        referenced_function_constants: BTreeSet::new(),
+                    },
+                    Function {
+                        fn_name: "test".to_owned(),
+                        binds: vec![
+                            Binds::AccelerationStructure {
+                                index: 0,
+                                name: "accel_struct".to_owned(),
+                            },
+                            Binds::IntersectionFunctionTable {
+                                index: 1,
+                                name: "i_fns".to_owned(),
+                            },
+                            Binds::VisibleFunctionTable {
+                                index: 2,
+                                name: "v_fns".to_owned(),
+                            },
+                        ],
+                        shader_type: FunctionType::Fragment,
+                        referenced_function_constants: BTreeSet::new(),
+                    },
+                ],
+            );
+        }
+
         #[test]
         fn test_no_binds() {
             for path in ["line", "proj-2-transformations/src/shaders.metal"] {
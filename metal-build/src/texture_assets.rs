@@ -0,0 +1,82 @@
+use asset_compiler::{
+    metadata::Compression,
+    mipmap::MipmapOptions,
+    texture_2d::{create_texture_2d_asset_dir, mtl_texture_files, texture_asset_dir},
+};
+use build_hash::{ModifiedOutput, Outputs};
+use std::path::Path;
+
+// Compiles every texture referenced by a model's material file (`.mtl`) into a 2D texture asset next
+// to the texture (see `asset_compiler::texture_2d::texture_asset_dir()`). `metal_app::model` loads
+// these assets, instead of decoding the PNGs, when present.
+//
+// Assets are shared by every project using the model (ex. `common-assets`), so they are only compiled
+// once: later builds (waiting on the manifest's lock, see `build_hash::build_hash_with_outputs()`)
+// find them up to date.
+//
+// Missing textures are skipped with a build warning (the model will fail to load them at runtime).
+pub fn build_model_texture_assets<P: AsRef<Path>>(mtl_file: P) {
+    let mtl_file = mtl_file.as_ref();
+    let texture_files: Vec<_> = mtl_texture_files(mtl_file)
+        .into_iter()
+        .filter(|(texture_file, _)| {
+            let exists = texture_file.is_file();
+            if !exists {
+                println!(
+                    "cargo:warning=Skipping missing texture {:?} referenced by {:?}",
+                    texture_file, mtl_file
+                );
+            }
+            exists
+        })
+        .collect();
+    let mipmap_options: Vec<_> = texture_files
+        .iter()
+        .map(|&(_, color_space)| MipmapOptions {
+            color_space,
+            ..Default::default()
+        })
+        .collect();
+    let compression = Compression::default();
+    let asset_dirs: Vec<_> = texture_files
+        .iter()
+        .map(|(texture_file, _)| texture_asset_dir(texture_file))
+        .collect();
+    let mut inputs: Vec<&dyn AsRef<Path>> = vec![&mtl_file];
+    inputs.extend(texture_files.iter().map(|(f, _)| f as &dyn AsRef<Path>));
+    let outputs: Vec<&dyn AsRef<Path>> = asset_dirs.iter().map(|d| d as &dyn AsRef<Path>).collect();
+    build_hash::build_hash_with_outputs(
+        mtl_file.with_extension("textures_asset_hash"),
+        &inputs,
+        Outputs {
+            paths: &outputs,
+            modified: ModifiedOutput::Regenerate,
+        },
+        // Recreate the assets whenever the asset format or how they are compiled changes
+        &(
+            asset_compiler::metadata::VERSION,
+            asset_compiler::container::VERSION,
+            &mipmap_options,
+            compression,
+        ),
+        || {
+            for (((texture_file, _), asset_dir), &mipmap_options) in
+                texture_files.iter().zip(&asset_dirs).zip(&mipmap_options)
+            {
+                if asset_dir.is_dir() {
+                    std::fs::remove_dir_all(asset_dir)
+                        .expect("Unable to remove existing asset directory");
+                } else if asset_dir.exists() {
+                    std::fs::remove_file(asset_dir).expect("Unable to remove existing asset file");
+                }
+                std::fs::create_dir(asset_dir).expect("Failed to create asset directory");
+                create_texture_2d_asset_dir(
+                    asset_dir,
+                    texture_file,
+                    Some(mipmap_options),
+                    compression,
+                );
+            }
+        },
+    );
+}
@@ -0,0 +1,264 @@
+// `#[derive(MetalLayout)]`, see `metal_types::MetalLayout`.
+//
+// Structs are laid out according to the Metal Shading Language Specification (Version 2.4), where
+// each field is placed at the next offset aligned to the field's alignment, the struct's alignment
+// is the largest field alignment (or `#[repr(align(N))]`, the equivalent of `alignas(N)`), and the
+// struct's size is rounded up to the struct's alignment.
+//
+// Metal's vector and matrix types (ex. `float3`, `packed_float3`, `half3x3`), mirrored in Rust by
+// bindgen from `rust_bindgen_only_metal_types.h`, instead use the sizes and alignments listed by the
+// specification (see `msl_builtin_layout()`). These are the only layouts not derived from the
+// fields, so a Rust mirror missing its alignment (ex. `float3` without `#[repr(align(16))]`) fails to
+// compile.
+//
+// Each field's Rust offset (`offset_of!`), and the struct's Rust size and alignment, are checked
+// against the Metal layout computed from the fields' `METAL_SIZE` and `METAL_ALIGN`, so a struct
+// containing a field whose Rust and Metal layouts differ fails to compile.
+//
+// The generated code references `MetalLayout` unqualified, expecting the trait to be in scope
+// alongside the derive (ex. `use metal_types::MetalLayout;` or `use metal_app::metal_types::*;`).
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields, Lit, Meta,
+    NestedMeta,
+};
+
+#[derive(Default)]
+struct Repr {
+    c: bool,
+    packed: bool,
+    align: Option<usize>,
+    int: Option<syn::Ident>,
+}
+
+fn parse_repr(attrs: &[Attribute]) -> Result<Repr, Error> {
+    let mut repr = Repr::default();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("repr")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => continue,
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => match path.get_ident() {
+                    Some(ident) if ident == "C" => repr.c = true,
+                    Some(ident) if ident == "packed" => repr.packed = true,
+                    Some(ident)
+                        if ["u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64"]
+                            .iter()
+                            .any(|int| ident == int) =>
+                    {
+                        repr.int = Some(ident.clone())
+                    }
+                    _ => {}
+                },
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("packed") => {
+                    repr.packed = true
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("align") => {
+                    repr.align = match list.nested.first() {
+                        Some(NestedMeta::Lit(Lit::Int(align))) => Some(align.base10_parse()?),
+                        _ => return Err(Error::new_spanned(list, "Expected `align(N)`")),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(repr)
+}
+
+// Size and alignment of Metal vector and matrix types, see "Metal Shading Language Specification
+// (Version 2.4)":
+// - 2.2 Vector Data Types / Table 2.3. Size and alignment of vector data types
+// - 2.2.3 Packed Vector Types / Table 2.4. Size and alignment of packed vector data types
+// - 2.3 Matrix Data Types / Table 2.5. Size and alignment of matrix data types
+fn msl_builtin_layout(name: &str) -> Option<(usize, usize)> {
+    let (packed, name) = match name.strip_prefix("packed_") {
+        Some(name) => (true, name),
+        None => (false, name),
+    };
+    let (scalar, scalar_size, dims) = [
+        ("bool", 1),
+        ("char", 1),
+        ("uchar", 1),
+        ("short", 2),
+        ("ushort", 2),
+        ("int", 4),
+        ("uint", 4),
+        ("half", 2),
+        ("float", 4),
+    ]
+    .into_iter()
+    .find_map(|(scalar, size)| Some((scalar, size, name.strip_prefix(scalar)?)))?;
+    let dim = |d: &str| match d {
+        "2" => Some(2),
+        "3" => Some(3),
+        "4" => Some(4),
+        _ => None,
+    };
+    // Vectors of 3 components have the size and alignment of 4 components.
+    let vector = |n: usize| {
+        let size = scalar_size * if n == 2 { 2 } else { 4 };
+        (size, size)
+    };
+    match dims.split_once('x') {
+        None if packed => Some((scalar_size * dim(dims)?, scalar_size)),
+        None => Some(vector(dim(dims)?)),
+        // Matrices (`halfNxM` and `floatNxM`) are N columns of M component vectors.
+        Some((columns, rows)) if !packed && matches!(scalar, "half" | "float") => {
+            let (column_size, column_align) = vector(dim(rows)?);
+            Some((column_size * dim(columns)?, column_align))
+        }
+        Some(_) => None,
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let repr = parse_repr(&input.attrs)?;
+    if !repr.c {
+        return Err(Error::new(
+            Span::call_site(),
+            "MetalLayout requires `#[repr(C)]`, otherwise Rust may reorder fields",
+        ));
+    }
+    if repr.packed {
+        return Err(Error::new(
+            Span::call_site(),
+            "MetalLayout does not support `#[repr(packed)]`, use packed Metal types instead (ex. `packed_float3`)",
+        ));
+    }
+    let repr_align = repr.align.unwrap_or(1);
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let field_members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let index = syn::Index::from(i);
+                quote! { #index }
+            }
+        })
+        .collect();
+    let field_names: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => ident.to_string(),
+            None => i.to_string(),
+        })
+        .collect();
+    let metal_layout = match msl_builtin_layout(&name.to_string()) {
+        Some((size, align)) => quote! {
+            const METAL_SIZE: usize = #size;
+            const METAL_ALIGN: usize = #align;
+        },
+        None => quote! {
+            const METAL_SIZE: usize = {
+                let align = <Self as MetalLayout>::METAL_ALIGN;
+                let mut offset: usize = 0;
+                #(
+                    let field_align = <#field_types as MetalLayout>::METAL_ALIGN;
+                    offset = offset.next_multiple_of(field_align)
+                        + <#field_types as MetalLayout>::METAL_SIZE;
+                )*
+                offset.next_multiple_of(align)
+            };
+            const METAL_ALIGN: usize = {
+                let mut align: usize = #repr_align;
+                #(
+                    if <#field_types as MetalLayout>::METAL_ALIGN > align {
+                        align = <#field_types as MetalLayout>::METAL_ALIGN;
+                    }
+                )*
+                align
+            };
+        },
+    };
+    Ok(quote! {
+        unsafe impl MetalLayout for #name {
+            #metal_layout
+        }
+        #[allow(unused_assignments, unused_mut)]
+        const _: () = {
+            // Fields are placed by their Metal size and alignment, independently of Rust's layout.
+            let mut offset: usize = 0;
+            #(
+                let field_align = <#field_types as MetalLayout>::METAL_ALIGN;
+                offset = offset.next_multiple_of(field_align);
+                assert!(
+                    ::core::mem::offset_of!(#name, #field_members) == offset,
+                    concat!(
+                        "`", stringify!(#name), "::", #field_names,
+                        "` Rust offset does not match its Metal offset"
+                    )
+                );
+                offset += <#field_types as MetalLayout>::METAL_SIZE;
+            )*
+            assert!(
+                ::core::mem::size_of::<#name>() == <#name as MetalLayout>::METAL_SIZE,
+                concat!(
+                    "`", stringify!(#name),
+                    "` Rust size does not match its Metal size (mismatched padding)"
+                )
+            );
+            assert!(
+                ::core::mem::align_of::<#name>() == <#name as MetalLayout>::METAL_ALIGN,
+                concat!(
+                    "`", stringify!(#name),
+                    "` Rust alignment does not match its Metal alignment"
+                )
+            );
+        };
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &syn::DataEnum) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return Err(Error::new(
+            variant.span(),
+            "MetalLayout only supports enums without fields",
+        ));
+    }
+    let int = parse_repr(&input.attrs)?.int.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "MetalLayout requires enums to specify an integer representation (ex. `#[repr(u8)]`)",
+        )
+    })?;
+    Ok(quote! {
+        unsafe impl MetalLayout for #name {
+            const METAL_SIZE: usize = <#int as MetalLayout>::METAL_SIZE;
+            const METAL_ALIGN: usize = <#int as MetalLayout>::METAL_ALIGN;
+        }
+    })
+}
+
+#[proc_macro_derive(MetalLayout)]
+pub fn derive_metal_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    if !input.generics.params.is_empty() {
+        return Error::new_spanned(&input.generics, "MetalLayout does not support generics")
+            .to_compile_error()
+            .into();
+    }
+    match &input.data {
+        Data::Struct(data) => derive_struct(&input, &data.fields),
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(_) => Err(Error::new(
+            Span::call_site(),
+            "MetalLayout does not support unions",
+        )),
+    }
+    .unwrap_or_else(|e| e.to_compile_error())
+    .into()
+}
@@ -1,16 +1,19 @@
 use bindgen::{callbacks::ParseCallbacks, CargoCallbacks};
+use build_hash::{depfile, ModifiedOutput, Outputs};
 use std::{
     env,
     fmt::Debug,
     fs,
     io::Write,
     path::{Path, PathBuf},
-    process::{Command, Output},
+    process::Command,
+    sync::Mutex,
 };
 
 const METAL_BUILD_MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
 
 static mut ITEMS: Vec<String> = Vec::new();
+static INCLUDES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
 
 #[derive(Debug)]
 struct CollectItems {
@@ -33,6 +36,13 @@ pub fn is_constant_name(s: &str) -> bool {
 impl ParseCallbacks for CollectItems {
     fn include_file(&self, filename: &str) {
         self.cargo_callbacks.include_file(filename);
+        let include = Path::new(filename).canonicalize().unwrap_or_else(|e| {
+            panic!("Failed to canonicalize path to included file {filename}: {e}")
+        });
+        let mut includes = INCLUDES.lock().unwrap();
+        if !includes.contains(&include) {
+            includes.push(include);
+        }
     }
     fn item_name(&self, item_name: &str) -> Option<String> {
         if item_name != "root" && !is_constant_name(item_name) {
@@ -45,50 +55,94 @@ impl ParseCallbacks for CollectItems {
         }
         None
     }
+    // Verifies Rust and Metal layouts match, see `src/metal_layout.rs`.
+    fn add_derives(&self, _name: &str) -> Vec<String> {
+        vec!["MetalLayout".into()]
+    }
 }
 
-fn get_shader_deps(shader_path: &str) -> Vec<PathBuf> {
-    let Output { stdout, .. } = run_command(
-        Command::new("xcrun")
-            .args(&[
-                "-sdk",
-                "macosx",
-                "metal",
-                "-std=metal3.0",
-                shader_path,
-                "-MM",
-            ])
-            .env_clear(),
-    );
-    let mut deps = vec![];
-    for l in std::str::from_utf8(&stdout)
-        .expect("Failed to read dependencies output")
-        .lines()
-        .filter(|l| l.starts_with("  ") && !l.contains("include/metal/module.modulemap"))
-    {
-        let dep = l.trim_end_matches(" \\").trim_start_matches(' ');
-        let dep = PathBuf::from(dep).canonicalize().expect(&format!(
-            "Failed to canonicalize path to dependency {dep:?}"
-        ));
-        if !deps.contains(&dep) {
-            deps.push(dep);
+// Bindings are only regenerated where the Metal headers are developed and compiled: when targeting
+// macOS with the Metal compiler (`xcrun metal`, Xcode or the Command Line Tools) installed.
+fn can_generate_bindings() -> bool {
+    matches!(env::var("CARGO_CFG_TARGET_OS").as_deref(), Ok("macos"))
+        && Command::new("xcrun")
+            .args(["-sdk", "macosx", "--find", "metal"])
+            .output()
+            .map(|out| out.status.success())
+            .unwrap_or(false)
+}
+
+// Escapes a path for a Make-style dependency file (see `build_hash::depfile`).
+fn escape_depfile_path(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' => escaped.push('\\'),
+            '$' => escaped.push('$'),
+            _ => {}
         }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Writes the headers included while generating bindings as a Make-style dependency file.
+fn write_depfile(depfile_path: &Path, target: &str, deps: &[PathBuf]) {
+    let mut contents = format!("{}:", escape_depfile_path(target));
+    for dep in deps {
+        contents.push_str(&format!(" \\\n  {}", escape_depfile_path(&dep.to_string_lossy())));
     }
-    deps
+    contents.push('\n');
+    fs::write(depfile_path, contents).unwrap_or_else(|e| {
+        panic!("Failed to write dependency file {}: {e}", depfile_path.to_string_lossy())
+    });
 }
 
 // Verifies `rust_bindgen_only_metal_types_bindings.rs`.
 pub fn main() {
     // TODO: Figure out a way to keep this in-sync with lib.rs
     let src_dir = Path::new(METAL_BUILD_MANIFEST_DIR).join("src");
-    let header = src_dir.join("all_metal_types.h");
-    let mut deps = get_shader_deps(&concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders.metal"));
-    deps.push(header.clone());
+    if !can_generate_bindings() {
+        // Use the checked-in `all_metal_types.rs` and `all_metal_types_list.rs`, allowing
+        // metal-types (and crates depending on it, ex. asset-compiler) to build on any platform.
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+    let header = src_dir
+        .join("all_metal_types.h")
+        .canonicalize()
+        .expect("Failed to canonicalize path to all_metal_types.h");
+    // Headers included by `all_metal_types.h`, recorded while generating bindings (instead of
+    // separately scanning with `metal -MM`) in a dependency file kept in OUT_DIR. A header adding or
+    // removing an include must itself have changed, regenerating bindings and the dependency file.
+    // Removed headers are skipped, so they are reported as removed inputs. Without a dependency file
+    // (ex. a clean build), only `all_metal_types.h` is known and bindings are regenerated once.
+    let depfile_path = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"))
+        .join("all_metal_types.d");
+    let mut deps: Vec<PathBuf> = match fs::read_to_string(&depfile_path) {
+        Ok(contents) => depfile::parse_depfile(&contents)
+            .into_iter()
+            .flat_map(|rule| rule.dependencies)
+            .filter_map(|dep| dep.canonicalize().ok())
+            .collect(),
+        Err(_) => vec![],
+    };
+    if !deps.contains(&header) {
+        deps.push(header.clone());
+    }
     let deps_refs: Vec<&dyn AsRef<Path>> = deps.iter().map(|a| a as _).collect();
 
-    build_hash::build_hash(
+    build_hash::build_hash_with_outputs(
         src_dir.join("all_metal_types_h_hash"),
         &deps_refs[..],
+        Outputs {
+            paths: &[
+                &src_dir.join("all_metal_types.rs"),
+                &src_dir.join("all_metal_types_list.rs"),
+            ],
+            modified: ModifiedOutput::Error,
+        },
+        &(),
         || {
             let mut all_metal_types_file = fs::File::options()
             .write(true)
@@ -100,6 +154,7 @@ pub fn main() {
             all_metal_types_file
                 .write_all(
                     r#"#![allow(non_snake_case)]
+use crate::MetalLayout;
 "#
                     .as_bytes(),
                 )
@@ -127,6 +182,10 @@ pub fn main() {
             unsafe {
                 ITEMS.sort();
             }
+            let mut deps = INCLUDES.lock().unwrap().clone();
+            deps.sort();
+            deps.insert(0, header.clone());
+            write_depfile(&depfile_path, "all_metal_types.rs", &deps);
 
             let mut w = |s: &str| {
                 all_metal_types_file.write_all(s.as_bytes()).expect(
@@ -171,20 +230,3 @@ pub const TYPES: [&'static str; {num_items}] = [
         },
     );
 }
-
-fn run_command(command: &mut Command) -> Output {
-    let out = command
-        .output()
-        .expect(&format!("Failed to run command {command:?}"));
-    if !out.status.success() {
-        panic!(
-            r#"
-    stdout: {}
-    stderr: {}
-    "#,
-            String::from_utf8(out.stdout).unwrap(),
-            String::from_utf8(out.stderr).unwrap()
-        );
-    }
-    out
-}
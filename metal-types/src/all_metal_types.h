@@ -9,4 +9,5 @@
 #include "./model-space.h"
 #include "./projected-space.h"
 #include "./shading-mode.h"
+#include "./spherical-harmonics.h"
 #include "./tri_normals.h"
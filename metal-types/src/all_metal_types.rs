@@ -1,35 +1,242 @@
 #![allow(non_snake_case)]
+use crate::MetalLayout;
 /* automatically generated by rust-bindgen 0.60.1 */
 
 #[repr(C)]
-#[repr(align(8))]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct float2 {
-    pub xy: [f32; 2usize],
+#[repr(align(2))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct bool2 {
+    pub xy: [bool; 2usize],
 }
 #[test]
-fn bindgen_test_layout_float2() {
+fn bindgen_test_layout_bool2() {
     assert_eq!(
-        ::std::mem::size_of::<float2>(),
-        8usize,
-        concat!("Size of: ", stringify!(float2))
+        ::std::mem::size_of::<bool2>(),
+        2usize,
+        concat!("Size of: ", stringify!(bool2))
     );
     assert_eq!(
-        ::std::mem::align_of::<float2>(),
-        8usize,
-        concat!("Alignment of ", stringify!(float2))
+        ::std::mem::align_of::<bool2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(bool2))
     );
     fn test_field_xy() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<float2>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<bool2>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
             },
             0usize,
+            concat!("Offset of field: ", stringify!(bool2), "::", stringify!(xy))
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct bool3 {
+    pub xyz: [bool; 3usize],
+}
+#[test]
+fn bindgen_test_layout_bool3() {
+    assert_eq!(
+        ::std::mem::size_of::<bool3>(),
+        4usize,
+        concat!("Size of: ", stringify!(bool3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<bool3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(bool3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<bool3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(float2),
+                stringify!(bool3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct bool4 {
+    pub xyzw: [bool; 4usize],
+}
+#[test]
+fn bindgen_test_layout_bool4() {
+    assert_eq!(
+        ::std::mem::size_of::<bool4>(),
+        4usize,
+        concat!("Size of: ", stringify!(bool4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<bool4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(bool4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<bool4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(bool4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(2))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct char2 {
+    pub xy: [::std::os::raw::c_schar; 2usize],
+}
+#[test]
+fn bindgen_test_layout_char2() {
+    assert_eq!(
+        ::std::mem::size_of::<char2>(),
+        2usize,
+        concat!("Size of: ", stringify!(char2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<char2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(char2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<char2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!("Offset of field: ", stringify!(char2), "::", stringify!(xy))
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct char3 {
+    pub xyz: [::std::os::raw::c_schar; 3usize],
+}
+#[test]
+fn bindgen_test_layout_char3() {
+    assert_eq!(
+        ::std::mem::size_of::<char3>(),
+        4usize,
+        concat!("Size of: ", stringify!(char3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<char3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(char3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<char3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(char3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct char4 {
+    pub xyzw: [::std::os::raw::c_schar; 4usize],
+}
+#[test]
+fn bindgen_test_layout_char4() {
+    assert_eq!(
+        ::std::mem::size_of::<char4>(),
+        4usize,
+        concat!("Size of: ", stringify!(char4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<char4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(char4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<char4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(char4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(2))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uchar2 {
+    pub xy: [::std::os::raw::c_uchar; 2usize],
+}
+#[test]
+fn bindgen_test_layout_uchar2() {
+    assert_eq!(
+        ::std::mem::size_of::<uchar2>(),
+        2usize,
+        concat!("Size of: ", stringify!(uchar2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<uchar2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(uchar2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<uchar2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(uchar2),
                 "::",
                 stringify!(xy)
             )
@@ -38,34 +245,70 @@ fn bindgen_test_layout_float2() {
     test_field_xy();
 }
 #[repr(C)]
-#[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct float4 {
-    pub xyzw: [f32; 4usize],
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uchar3 {
+    pub xyz: [::std::os::raw::c_uchar; 3usize],
 }
 #[test]
-fn bindgen_test_layout_float4() {
+fn bindgen_test_layout_uchar3() {
     assert_eq!(
-        ::std::mem::size_of::<float4>(),
-        16usize,
-        concat!("Size of: ", stringify!(float4))
+        ::std::mem::size_of::<uchar3>(),
+        4usize,
+        concat!("Size of: ", stringify!(uchar3))
     );
     assert_eq!(
-        ::std::mem::align_of::<float4>(),
-        16usize,
-        concat!("Alignment of ", stringify!(float4))
+        ::std::mem::align_of::<uchar3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(uchar3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<uchar3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(uchar3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uchar4 {
+    pub xyzw: [::std::os::raw::c_uchar; 4usize],
+}
+#[test]
+fn bindgen_test_layout_uchar4() {
+    assert_eq!(
+        ::std::mem::size_of::<uchar4>(),
+        4usize,
+        concat!("Size of: ", stringify!(uchar4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<uchar4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(uchar4))
     );
     fn test_field_xyzw() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<float4>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<uchar4>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(float4),
+                stringify!(uchar4),
                 "::",
                 stringify!(xyzw)
             )
@@ -75,64 +318,69 @@ fn bindgen_test_layout_float4() {
 }
 #[repr(C)]
 #[repr(align(4))]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct half2 {
-    pub xy: [::std::os::raw::c_ushort; 2usize],
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct short2 {
+    pub xy: [::std::os::raw::c_short; 2usize],
 }
 #[test]
-fn bindgen_test_layout_half2() {
+fn bindgen_test_layout_short2() {
     assert_eq!(
-        ::std::mem::size_of::<half2>(),
+        ::std::mem::size_of::<short2>(),
         4usize,
-        concat!("Size of: ", stringify!(half2))
+        concat!("Size of: ", stringify!(short2))
     );
     assert_eq!(
-        ::std::mem::align_of::<half2>(),
+        ::std::mem::align_of::<short2>(),
         4usize,
-        concat!("Alignment of ", stringify!(half2))
+        concat!("Alignment of ", stringify!(short2))
     );
     fn test_field_xy() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<half2>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<short2>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
             },
             0usize,
-            concat!("Offset of field: ", stringify!(half2), "::", stringify!(xy))
+            concat!(
+                "Offset of field: ",
+                stringify!(short2),
+                "::",
+                stringify!(xy)
+            )
         );
     }
     test_field_xy();
 }
 #[repr(C)]
 #[repr(align(8))]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct half3 {
-    pub xyz: [::std::os::raw::c_ushort; 3usize],
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct short3 {
+    pub xyz: [::std::os::raw::c_short; 3usize],
 }
 #[test]
-fn bindgen_test_layout_half3() {
+fn bindgen_test_layout_short3() {
     assert_eq!(
-        ::std::mem::size_of::<half3>(),
+        ::std::mem::size_of::<short3>(),
         8usize,
-        concat!("Size of: ", stringify!(half3))
+        concat!("Size of: ", stringify!(short3))
     );
     assert_eq!(
-        ::std::mem::align_of::<half3>(),
+        ::std::mem::align_of::<short3>(),
         8usize,
-        concat!("Alignment of ", stringify!(half3))
+        concat!("Alignment of ", stringify!(short3))
     );
     fn test_field_xyz() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<half3>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<short3>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(half3),
+                stringify!(short3),
                 "::",
                 stringify!(xyz)
             )
@@ -142,33 +390,33 @@ fn bindgen_test_layout_half3() {
 }
 #[repr(C)]
 #[repr(align(8))]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct half4 {
-    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct short4 {
+    pub xyzw: [::std::os::raw::c_short; 4usize],
 }
 #[test]
-fn bindgen_test_layout_half4() {
+fn bindgen_test_layout_short4() {
     assert_eq!(
-        ::std::mem::size_of::<half4>(),
+        ::std::mem::size_of::<short4>(),
         8usize,
-        concat!("Size of: ", stringify!(half4))
+        concat!("Size of: ", stringify!(short4))
     );
     assert_eq!(
-        ::std::mem::align_of::<half4>(),
+        ::std::mem::align_of::<short4>(),
         8usize,
-        concat!("Alignment of ", stringify!(half4))
+        concat!("Alignment of ", stringify!(short4))
     );
     fn test_field_xyzw() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<half4>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<short4>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(half4),
+                stringify!(short4),
                 "::",
                 stringify!(xyzw)
             )
@@ -178,7 +426,7 @@ fn bindgen_test_layout_half4() {
 }
 #[repr(C)]
 #[repr(align(4))]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct ushort2 {
     pub xy: [::std::os::raw::c_ushort; 2usize],
 }
@@ -213,218 +461,1800 @@ fn bindgen_test_layout_ushort2() {
     test_field_xy();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct packed_float2 {
-    pub xy: [f32; 2usize],
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct ushort3 {
+    pub xyz: [::std::os::raw::c_ushort; 3usize],
 }
 #[test]
-fn bindgen_test_layout_packed_float2() {
+fn bindgen_test_layout_ushort3() {
     assert_eq!(
-        ::std::mem::size_of::<packed_float2>(),
+        ::std::mem::size_of::<ushort3>(),
         8usize,
-        concat!("Size of: ", stringify!(packed_float2))
+        concat!("Size of: ", stringify!(ushort3))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_float2>(),
-        4usize,
-        concat!("Alignment of ", stringify!(packed_float2))
+        ::std::mem::align_of::<ushort3>(),
+        8usize,
+        concat!("Alignment of ", stringify!(ushort3))
     );
-    fn test_field_xy() {
+    fn test_field_xyz() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_float2>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<ushort3>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_float2),
+                stringify!(ushort3),
                 "::",
-                stringify!(xy)
+                stringify!(xyz)
             )
         );
     }
-    test_field_xy();
+    test_field_xyz();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct packed_float3 {
-    pub xyz: [f32; 3usize],
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct ushort4 {
+    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+}
+#[test]
+fn bindgen_test_layout_ushort4() {
+    assert_eq!(
+        ::std::mem::size_of::<ushort4>(),
+        8usize,
+        concat!("Size of: ", stringify!(ushort4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<ushort4>(),
+        8usize,
+        concat!("Alignment of ", stringify!(ushort4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<ushort4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(ushort4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct int2 {
+    pub xy: [::std::os::raw::c_int; 2usize],
+}
+#[test]
+fn bindgen_test_layout_int2() {
+    assert_eq!(
+        ::std::mem::size_of::<int2>(),
+        8usize,
+        concat!("Size of: ", stringify!(int2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<int2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(int2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<int2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!("Offset of field: ", stringify!(int2), "::", stringify!(xy))
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct int3 {
+    pub xyz: [::std::os::raw::c_int; 3usize],
+}
+#[test]
+fn bindgen_test_layout_int3() {
+    assert_eq!(
+        ::std::mem::size_of::<int3>(),
+        16usize,
+        concat!("Size of: ", stringify!(int3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<int3>(),
+        16usize,
+        concat!("Alignment of ", stringify!(int3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<int3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!("Offset of field: ", stringify!(int3), "::", stringify!(xyz))
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct int4 {
+    pub xyzw: [::std::os::raw::c_int; 4usize],
+}
+#[test]
+fn bindgen_test_layout_int4() {
+    assert_eq!(
+        ::std::mem::size_of::<int4>(),
+        16usize,
+        concat!("Size of: ", stringify!(int4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<int4>(),
+        16usize,
+        concat!("Alignment of ", stringify!(int4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<int4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(int4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uint2 {
+    pub xy: [::std::os::raw::c_uint; 2usize],
+}
+#[test]
+fn bindgen_test_layout_uint2() {
+    assert_eq!(
+        ::std::mem::size_of::<uint2>(),
+        8usize,
+        concat!("Size of: ", stringify!(uint2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<uint2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(uint2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<uint2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!("Offset of field: ", stringify!(uint2), "::", stringify!(xy))
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uint3 {
+    pub xyz: [::std::os::raw::c_uint; 3usize],
+}
+#[test]
+fn bindgen_test_layout_uint3() {
+    assert_eq!(
+        ::std::mem::size_of::<uint3>(),
+        16usize,
+        concat!("Size of: ", stringify!(uint3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<uint3>(),
+        16usize,
+        concat!("Alignment of ", stringify!(uint3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<uint3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(uint3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct uint4 {
+    pub xyzw: [::std::os::raw::c_uint; 4usize],
+}
+#[test]
+fn bindgen_test_layout_uint4() {
+    assert_eq!(
+        ::std::mem::size_of::<uint4>(),
+        16usize,
+        concat!("Size of: ", stringify!(uint4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<uint4>(),
+        16usize,
+        concat!("Alignment of ", stringify!(uint4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<uint4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(uint4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half2 {
+    pub xy: [::std::os::raw::c_ushort; 2usize],
+}
+#[test]
+fn bindgen_test_layout_half2() {
+    assert_eq!(
+        ::std::mem::size_of::<half2>(),
+        4usize,
+        concat!("Size of: ", stringify!(half2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(half2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!("Offset of field: ", stringify!(half2), "::", stringify!(xy))
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half3 {
+    pub xyz: [::std::os::raw::c_ushort; 3usize],
+}
+#[test]
+fn bindgen_test_layout_half3() {
+    assert_eq!(
+        ::std::mem::size_of::<half3>(),
+        8usize,
+        concat!("Size of: ", stringify!(half3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half3>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half4 {
+    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+}
+#[test]
+fn bindgen_test_layout_half4() {
+    assert_eq!(
+        ::std::mem::size_of::<half4>(),
+        8usize,
+        concat!("Size of: ", stringify!(half4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half4>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float2 {
+    pub xy: [f32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_float2() {
+    assert_eq!(
+        ::std::mem::size_of::<float2>(),
+        8usize,
+        concat!("Size of: ", stringify!(float2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<float2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(float2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<float2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(float2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float3 {
+    pub xyz: [f32; 3usize],
+}
+#[test]
+fn bindgen_test_layout_float3() {
+    assert_eq!(
+        ::std::mem::size_of::<float3>(),
+        16usize,
+        concat!("Size of: ", stringify!(float3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<float3>(),
+        16usize,
+        concat!("Alignment of ", stringify!(float3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<float3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(float3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float4 {
+    pub xyzw: [f32; 4usize],
+}
+#[test]
+fn bindgen_test_layout_float4() {
+    assert_eq!(
+        ::std::mem::size_of::<float4>(),
+        16usize,
+        concat!("Size of: ", stringify!(float4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<float4>(),
+        16usize,
+        concat!("Alignment of ", stringify!(float4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<float4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(float4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_char2 {
+    pub xy: [::std::os::raw::c_schar; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_char2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_char2>(),
+        2usize,
+        concat!("Size of: ", stringify!(packed_char2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_char2>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_char2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_char2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_char2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_char3 {
+    pub xyz: [::std::os::raw::c_schar; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_char3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_char3>(),
+        3usize,
+        concat!("Size of: ", stringify!(packed_char3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_char3>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_char3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_char3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_char3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_char4 {
+    pub xyzw: [::std::os::raw::c_schar; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_char4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_char4>(),
+        4usize,
+        concat!("Size of: ", stringify!(packed_char4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_char4>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_char4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_char4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_char4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uchar2 {
+    pub xy: [::std::os::raw::c_uchar; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uchar2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uchar2>(),
+        2usize,
+        concat!("Size of: ", stringify!(packed_uchar2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uchar2>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_uchar2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uchar2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uchar2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uchar3 {
+    pub xyz: [::std::os::raw::c_uchar; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uchar3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uchar3>(),
+        3usize,
+        concat!("Size of: ", stringify!(packed_uchar3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uchar3>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_uchar3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uchar3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uchar3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uchar4 {
+    pub xyzw: [::std::os::raw::c_uchar; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uchar4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uchar4>(),
+        4usize,
+        concat!("Size of: ", stringify!(packed_uchar4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uchar4>(),
+        1usize,
+        concat!("Alignment of ", stringify!(packed_uchar4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uchar4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uchar4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_short2 {
+    pub xy: [::std::os::raw::c_short; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_short2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_short2>(),
+        4usize,
+        concat!("Size of: ", stringify!(packed_short2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_short2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_short2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_short2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_short2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_short3 {
+    pub xyz: [::std::os::raw::c_short; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_short3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_short3>(),
+        6usize,
+        concat!("Size of: ", stringify!(packed_short3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_short3>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_short3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_short3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_short3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_short4 {
+    pub xyzw: [::std::os::raw::c_short; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_short4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_short4>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_short4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_short4>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_short4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_short4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_short4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_ushort2 {
+    pub xy: [::std::os::raw::c_ushort; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_ushort2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_ushort2>(),
+        4usize,
+        concat!("Size of: ", stringify!(packed_ushort2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_ushort2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_ushort2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_ushort2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_ushort2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_ushort3 {
+    pub xyz: [::std::os::raw::c_ushort; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_ushort3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_ushort3>(),
+        6usize,
+        concat!("Size of: ", stringify!(packed_ushort3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_ushort3>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_ushort3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_ushort3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_ushort3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_ushort4 {
+    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_ushort4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_ushort4>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_ushort4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_ushort4>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_ushort4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_ushort4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_ushort4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_int2 {
+    pub xy: [::std::os::raw::c_int; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_int2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_int2>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_int2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_int2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_int2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_int2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_int2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_int3 {
+    pub xyz: [::std::os::raw::c_int; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_int3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_int3>(),
+        12usize,
+        concat!("Size of: ", stringify!(packed_int3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_int3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_int3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_int3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_int3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_int4 {
+    pub xyzw: [::std::os::raw::c_int; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_int4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_int4>(),
+        16usize,
+        concat!("Size of: ", stringify!(packed_int4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_int4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_int4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_int4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_int4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uint2 {
+    pub xy: [::std::os::raw::c_uint; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uint2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uint2>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_uint2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uint2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_uint2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uint2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uint2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uint3 {
+    pub xyz: [::std::os::raw::c_uint; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uint3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uint3>(),
+        12usize,
+        concat!("Size of: ", stringify!(packed_uint3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uint3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_uint3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uint3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uint3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_uint4 {
+    pub xyzw: [::std::os::raw::c_uint; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_uint4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_uint4>(),
+        16usize,
+        concat!("Size of: ", stringify!(packed_uint4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_uint4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_uint4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_uint4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_uint4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_half2 {
+    pub xy: [::std::os::raw::c_ushort; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_half2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_half2>(),
+        4usize,
+        concat!("Size of: ", stringify!(packed_half2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_half2>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_half2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_half2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_half2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_half3 {
+    pub xyz: [::std::os::raw::c_ushort; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_half3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_half3>(),
+        6usize,
+        concat!("Size of: ", stringify!(packed_half3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_half3>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_half3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_half3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_half3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct packed_half4 {
+    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_half4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_half4>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_half4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_half4>(),
+        2usize,
+        concat!("Alignment of ", stringify!(packed_half4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_half4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_half4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct packed_float2 {
+    pub xy: [f32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_packed_float2() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_float2>(),
+        8usize,
+        concat!("Size of: ", stringify!(packed_float2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_float2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_float2))
+    );
+    fn test_field_xy() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_float2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_float2),
+                "::",
+                stringify!(xy)
+            )
+        );
+    }
+    test_field_xy();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct packed_float3 {
+    pub xyz: [f32; 3usize],
+}
+#[test]
+fn bindgen_test_layout_packed_float3() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_float3>(),
+        12usize,
+        concat!("Size of: ", stringify!(packed_float3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_float3>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_float3))
+    );
+    fn test_field_xyz() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_float3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_float3),
+                "::",
+                stringify!(xyz)
+            )
+        );
+    }
+    test_field_xyz();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct packed_float4 {
+    pub xyzw: [f32; 4usize],
+}
+#[test]
+fn bindgen_test_layout_packed_float4() {
+    assert_eq!(
+        ::std::mem::size_of::<packed_float4>(),
+        16usize,
+        concat!("Size of: ", stringify!(packed_float4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<packed_float4>(),
+        4usize,
+        concat!("Alignment of ", stringify!(packed_float4))
+    );
+    fn test_field_xyzw() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<packed_float4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(packed_float4),
+                "::",
+                stringify!(xyzw)
+            )
+        );
+    }
+    test_field_xyzw();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half2x2 {
+    pub columns: [[::std::os::raw::c_ushort; 2usize]; 2usize],
+}
+#[test]
+fn bindgen_test_layout_half2x2() {
+    assert_eq!(
+        ::std::mem::size_of::<half2x2>(),
+        8usize,
+        concat!("Size of: ", stringify!(half2x2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half2x2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(half2x2))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half2x2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half2x2),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half2x3 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 2usize],
+}
+#[test]
+fn bindgen_test_layout_half2x3() {
+    assert_eq!(
+        ::std::mem::size_of::<half2x3>(),
+        16usize,
+        concat!("Size of: ", stringify!(half2x3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half2x3>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half2x3))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half2x3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half2x3),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half2x4 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 2usize],
+}
+#[test]
+fn bindgen_test_layout_half2x4() {
+    assert_eq!(
+        ::std::mem::size_of::<half2x4>(),
+        16usize,
+        concat!("Size of: ", stringify!(half2x4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half2x4>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half2x4))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half2x4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half2x4),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half3x2 {
+    pub columns: [[::std::os::raw::c_ushort; 2usize]; 3usize],
+}
+#[test]
+fn bindgen_test_layout_half3x2() {
+    assert_eq!(
+        ::std::mem::size_of::<half3x2>(),
+        12usize,
+        concat!("Size of: ", stringify!(half3x2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half3x2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(half3x2))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half3x2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half3x2),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half3x3 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 3usize],
+}
+#[test]
+fn bindgen_test_layout_half3x3() {
+    assert_eq!(
+        ::std::mem::size_of::<half3x3>(),
+        24usize,
+        concat!("Size of: ", stringify!(half3x3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half3x3>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half3x3))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half3x3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half3x3),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half3x4 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 3usize],
+}
+#[test]
+fn bindgen_test_layout_half3x4() {
+    assert_eq!(
+        ::std::mem::size_of::<half3x4>(),
+        24usize,
+        concat!("Size of: ", stringify!(half3x4))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half3x4>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half3x4))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half3x4>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half3x4),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half4x2 {
+    pub columns: [[::std::os::raw::c_ushort; 2usize]; 4usize],
+}
+#[test]
+fn bindgen_test_layout_half4x2() {
+    assert_eq!(
+        ::std::mem::size_of::<half4x2>(),
+        16usize,
+        concat!("Size of: ", stringify!(half4x2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half4x2>(),
+        4usize,
+        concat!("Alignment of ", stringify!(half4x2))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half4x2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half4x2),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half4x3 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 4usize],
+}
+#[test]
+fn bindgen_test_layout_half4x3() {
+    assert_eq!(
+        ::std::mem::size_of::<half4x3>(),
+        32usize,
+        concat!("Size of: ", stringify!(half4x3))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<half4x3>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half4x3))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<half4x3>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(half4x3),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct half4x4 {
+    pub columns: [[::std::os::raw::c_ushort; 4usize]; 4usize],
 }
 #[test]
-fn bindgen_test_layout_packed_float3() {
+fn bindgen_test_layout_half4x4() {
     assert_eq!(
-        ::std::mem::size_of::<packed_float3>(),
-        12usize,
-        concat!("Size of: ", stringify!(packed_float3))
+        ::std::mem::size_of::<half4x4>(),
+        32usize,
+        concat!("Size of: ", stringify!(half4x4))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_float3>(),
-        4usize,
-        concat!("Alignment of ", stringify!(packed_float3))
+        ::std::mem::align_of::<half4x4>(),
+        8usize,
+        concat!("Alignment of ", stringify!(half4x4))
     );
-    fn test_field_xyz() {
+    fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_float3>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<half4x4>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_float3),
+                stringify!(half4x4),
                 "::",
-                stringify!(xyz)
+                stringify!(columns)
             )
         );
     }
-    test_field_xyz();
+    test_field_columns();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct packed_float4 {
-    pub xyzw: [f32; 4usize],
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float2x2 {
+    pub columns: [[f32; 2usize]; 2usize],
 }
 #[test]
-fn bindgen_test_layout_packed_float4() {
+fn bindgen_test_layout_float2x2() {
     assert_eq!(
-        ::std::mem::size_of::<packed_float4>(),
+        ::std::mem::size_of::<float2x2>(),
         16usize,
-        concat!("Size of: ", stringify!(packed_float4))
+        concat!("Size of: ", stringify!(float2x2))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_float4>(),
-        4usize,
-        concat!("Alignment of ", stringify!(packed_float4))
+        ::std::mem::align_of::<float2x2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(float2x2))
     );
-    fn test_field_xyzw() {
+    fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_float4>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float2x2>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_float4),
+                stringify!(float2x2),
                 "::",
-                stringify!(xyzw)
+                stringify!(columns)
             )
         );
     }
-    test_field_xyzw();
+    test_field_columns();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct packed_half2 {
-    pub xy: [::std::os::raw::c_ushort; 2usize],
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float2x3 {
+    pub columns: [[f32; 4usize]; 2usize],
 }
 #[test]
-fn bindgen_test_layout_packed_half2() {
+fn bindgen_test_layout_float2x3() {
     assert_eq!(
-        ::std::mem::size_of::<packed_half2>(),
-        4usize,
-        concat!("Size of: ", stringify!(packed_half2))
+        ::std::mem::size_of::<float2x3>(),
+        32usize,
+        concat!("Size of: ", stringify!(float2x3))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_half2>(),
-        2usize,
-        concat!("Alignment of ", stringify!(packed_half2))
+        ::std::mem::align_of::<float2x3>(),
+        16usize,
+        concat!("Alignment of ", stringify!(float2x3))
     );
-    fn test_field_xy() {
+    fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_half2>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float2x3>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xy) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_half2),
+                stringify!(float2x3),
                 "::",
-                stringify!(xy)
+                stringify!(columns)
             )
         );
     }
-    test_field_xy();
+    test_field_columns();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct packed_half3 {
-    pub xyz: [::std::os::raw::c_ushort; 3usize],
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float2x4 {
+    pub columns: [[f32; 4usize]; 2usize],
 }
 #[test]
-fn bindgen_test_layout_packed_half3() {
+fn bindgen_test_layout_float2x4() {
     assert_eq!(
-        ::std::mem::size_of::<packed_half3>(),
-        6usize,
-        concat!("Size of: ", stringify!(packed_half3))
+        ::std::mem::size_of::<float2x4>(),
+        32usize,
+        concat!("Size of: ", stringify!(float2x4))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_half3>(),
-        2usize,
-        concat!("Alignment of ", stringify!(packed_half3))
+        ::std::mem::align_of::<float2x4>(),
+        16usize,
+        concat!("Alignment of ", stringify!(float2x4))
     );
-    fn test_field_xyz() {
+    fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_half3>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float2x4>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xyz) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_half3),
+                stringify!(float2x4),
                 "::",
-                stringify!(xyz)
+                stringify!(columns)
             )
         );
     }
-    test_field_xyz();
+    test_field_columns();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct packed_half4 {
-    pub xyzw: [::std::os::raw::c_ushort; 4usize],
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float3x2 {
+    pub columns: [[f32; 2usize]; 3usize],
 }
 #[test]
-fn bindgen_test_layout_packed_half4() {
+fn bindgen_test_layout_float3x2() {
     assert_eq!(
-        ::std::mem::size_of::<packed_half4>(),
-        8usize,
-        concat!("Size of: ", stringify!(packed_half4))
+        ::std::mem::size_of::<float3x2>(),
+        24usize,
+        concat!("Size of: ", stringify!(float3x2))
     );
     assert_eq!(
-        ::std::mem::align_of::<packed_half4>(),
-        2usize,
-        concat!("Alignment of ", stringify!(packed_half4))
+        ::std::mem::align_of::<float3x2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(float3x2))
     );
-    fn test_field_xyzw() {
+    fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<packed_half4>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float3x2>::uninit();
                 let ptr = uninit.as_ptr();
-                ::std::ptr::addr_of!((*ptr).xyzw) as usize - ptr as usize
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(packed_half4),
+                stringify!(float3x2),
                 "::",
-                stringify!(xyzw)
+                stringify!(columns)
             )
         );
     }
-    test_field_xyzw();
+    test_field_columns();
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
 pub struct float3x3 {
     pub columns: [[f32; 4usize]; 3usize],
 }
@@ -460,33 +2290,69 @@ fn bindgen_test_layout_float3x3() {
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct float4x3 {
-    pub columns: [[f32; 4usize]; 4usize],
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float3x4 {
+    pub columns: [[f32; 4usize]; 3usize],
 }
 #[test]
-fn bindgen_test_layout_float4x3() {
+fn bindgen_test_layout_float3x4() {
     assert_eq!(
-        ::std::mem::size_of::<float4x3>(),
-        64usize,
-        concat!("Size of: ", stringify!(float4x3))
+        ::std::mem::size_of::<float3x4>(),
+        48usize,
+        concat!("Size of: ", stringify!(float3x4))
     );
     assert_eq!(
-        ::std::mem::align_of::<float4x3>(),
+        ::std::mem::align_of::<float3x4>(),
         16usize,
-        concat!("Alignment of ", stringify!(float4x3))
+        concat!("Alignment of ", stringify!(float3x4))
     );
     fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<float4x3>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float3x4>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(float4x3),
+                stringify!(float3x4),
+                "::",
+                stringify!(columns)
+            )
+        );
+    }
+    test_field_columns();
+}
+#[repr(C)]
+#[repr(align(8))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float4x2 {
+    pub columns: [[f32; 2usize]; 4usize],
+}
+#[test]
+fn bindgen_test_layout_float4x2() {
+    assert_eq!(
+        ::std::mem::size_of::<float4x2>(),
+        32usize,
+        concat!("Size of: ", stringify!(float4x2))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<float4x2>(),
+        8usize,
+        concat!("Alignment of ", stringify!(float4x2))
+    );
+    fn test_field_columns() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<float4x2>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(float4x2),
                 "::",
                 stringify!(columns)
             )
@@ -496,33 +2362,33 @@ fn bindgen_test_layout_float4x3() {
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
-pub struct float4x4 {
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float4x3 {
     pub columns: [[f32; 4usize]; 4usize],
 }
 #[test]
-fn bindgen_test_layout_float4x4() {
+fn bindgen_test_layout_float4x3() {
     assert_eq!(
-        ::std::mem::size_of::<float4x4>(),
+        ::std::mem::size_of::<float4x3>(),
         64usize,
-        concat!("Size of: ", stringify!(float4x4))
+        concat!("Size of: ", stringify!(float4x3))
     );
     assert_eq!(
-        ::std::mem::align_of::<float4x4>(),
+        ::std::mem::align_of::<float4x3>(),
         16usize,
-        concat!("Alignment of ", stringify!(float4x4))
+        concat!("Alignment of ", stringify!(float4x3))
     );
     fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<float4x4>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float4x3>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(float4x4),
+                stringify!(float4x3),
                 "::",
                 stringify!(columns)
             )
@@ -531,34 +2397,34 @@ fn bindgen_test_layout_float4x4() {
     test_field_columns();
 }
 #[repr(C)]
-#[repr(align(8))]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
-pub struct half3x3 {
-    pub columns: [[::std::os::raw::c_ushort; 4usize]; 3usize],
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct float4x4 {
+    pub columns: [[f32; 4usize]; 4usize],
 }
 #[test]
-fn bindgen_test_layout_half3x3() {
+fn bindgen_test_layout_float4x4() {
     assert_eq!(
-        ::std::mem::size_of::<half3x3>(),
-        24usize,
-        concat!("Size of: ", stringify!(half3x3))
+        ::std::mem::size_of::<float4x4>(),
+        64usize,
+        concat!("Size of: ", stringify!(float4x4))
     );
     assert_eq!(
-        ::std::mem::align_of::<half3x3>(),
-        8usize,
-        concat!("Alignment of ", stringify!(half3x3))
+        ::std::mem::align_of::<float4x4>(),
+        16usize,
+        concat!("Alignment of ", stringify!(float4x4))
     );
     fn test_field_columns() {
         assert_eq!(
             unsafe {
-                let uninit = ::std::mem::MaybeUninit::<half3x3>::uninit();
+                let uninit = ::std::mem::MaybeUninit::<float4x4>::uninit();
                 let ptr = uninit.as_ptr();
                 ::std::ptr::addr_of!((*ptr).columns) as usize - ptr as usize
             },
             0usize,
             concat!(
                 "Offset of field: ",
-                stringify!(half3x3),
+                stringify!(float4x4),
                 "::",
                 stringify!(columns)
             )
@@ -568,7 +2434,7 @@ fn bindgen_test_layout_half3x3() {
 }
 pub const DEBUG_PATH_MAX_NUM_POINTS: ::std::os::raw::c_uint = 8;
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
 pub struct DebugPath {
     pub points: [packed_float3; 8usize],
     pub screen_pos: float2,
@@ -639,7 +2505,7 @@ fn bindgen_test_layout_DebugPath() {
     test_field_num_points();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct GeometryNoTxCoords {
     pub indices: ::std::os::raw::c_ulong,
     pub positions: ::std::os::raw::c_ulong,
@@ -710,7 +2576,7 @@ fn bindgen_test_layout_GeometryNoTxCoords() {
     test_field_normals();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct Geometry {
     pub indices: ::std::os::raw::c_ulong,
     pub positions: ::std::os::raw::c_ulong,
@@ -799,7 +2665,7 @@ fn bindgen_test_layout_Geometry() {
     test_field_tx_coords();
 }
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
 pub struct Material {
     pub ambient_texture: ::std::os::raw::c_ulong,
     pub diffuse_texture: ::std::os::raw::c_ulong,
@@ -907,7 +2773,7 @@ fn bindgen_test_layout_Material() {
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
 pub struct ModelSpace {
     pub m_model_to_projection: float4x4,
     pub m_normal_to_world: float3x3,
@@ -961,7 +2827,7 @@ fn bindgen_test_layout_ModelSpace() {
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Default, Copy, Clone, PartialEq)]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
 pub struct ProjectedSpace {
     pub m_world_to_projection: float4x4,
     pub m_screen_to_world: float4x4,
@@ -1031,8 +2897,45 @@ fn bindgen_test_layout_ProjectedSpace() {
     }
     test_field_position_world();
 }
+pub const SH_IRRADIANCE_NUM_COEFFICIENTS: ::std::os::raw::c_uint = 9;
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Default, Copy, Clone, PartialEq, MetalLayout)]
+pub struct SHIrradiance {
+    pub coefficients: [float4; 9usize],
+}
+#[test]
+fn bindgen_test_layout_SHIrradiance() {
+    assert_eq!(
+        ::std::mem::size_of::<SHIrradiance>(),
+        144usize,
+        concat!("Size of: ", stringify!(SHIrradiance))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<SHIrradiance>(),
+        16usize,
+        concat!("Alignment of ", stringify!(SHIrradiance))
+    );
+    fn test_field_coefficients() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<SHIrradiance>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).coefficients) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(SHIrradiance),
+                "::",
+                stringify!(coefficients)
+            )
+        );
+    }
+    test_field_coefficients();
+}
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct TriNormals {
     pub normals: [::std::os::raw::c_uint; 2usize],
 }
@@ -1066,6 +2969,76 @@ fn bindgen_test_layout_TriNormals() {
     }
     test_field_normals();
 }
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct TriNormalsOct16 {
+    pub normals: [::std::os::raw::c_uint; 3usize],
+}
+#[test]
+fn bindgen_test_layout_TriNormalsOct16() {
+    assert_eq!(
+        ::std::mem::size_of::<TriNormalsOct16>(),
+        12usize,
+        concat!("Size of: ", stringify!(TriNormalsOct16))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<TriNormalsOct16>(),
+        4usize,
+        concat!("Alignment of ", stringify!(TriNormalsOct16))
+    );
+    fn test_field_normals() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<TriNormalsOct16>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).normals) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(TriNormalsOct16),
+                "::",
+                stringify!(normals)
+            )
+        );
+    }
+    test_field_normals();
+}
+#[repr(C)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
+pub struct TriNormalsXyz111110 {
+    pub normals: [::std::os::raw::c_uint; 3usize],
+}
+#[test]
+fn bindgen_test_layout_TriNormalsXyz111110() {
+    assert_eq!(
+        ::std::mem::size_of::<TriNormalsXyz111110>(),
+        12usize,
+        concat!("Size of: ", stringify!(TriNormalsXyz111110))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<TriNormalsXyz111110>(),
+        4usize,
+        concat!("Alignment of ", stringify!(TriNormalsXyz111110))
+    );
+    fn test_field_normals() {
+        assert_eq!(
+            unsafe {
+                let uninit = ::std::mem::MaybeUninit::<TriNormalsXyz111110>::uninit();
+                let ptr = uninit.as_ptr();
+                ::std::ptr::addr_of!((*ptr).normals) as usize - ptr as usize
+            },
+            0usize,
+            concat!(
+                "Offset of field: ",
+                stringify!(TriNormalsXyz111110),
+                "::",
+                stringify!(normals)
+            )
+        );
+    }
+    test_field_normals();
+}
 
 #[test]
 fn test_metal_types_derive_copy() {
@@ -1077,21 +3050,77 @@ fn test_metal_types_derive_copy() {
     HasCopyClone(PhantomData::<Material>);
     HasCopyClone(PhantomData::<ModelSpace>);
     HasCopyClone(PhantomData::<ProjectedSpace>);
+    HasCopyClone(PhantomData::<SHIrradiance>);
     HasCopyClone(PhantomData::<TriNormals>);
+    HasCopyClone(PhantomData::<TriNormalsOct16>);
+    HasCopyClone(PhantomData::<TriNormalsXyz111110>);
+    HasCopyClone(PhantomData::<bool2>);
+    HasCopyClone(PhantomData::<bool3>);
+    HasCopyClone(PhantomData::<bool4>);
+    HasCopyClone(PhantomData::<char2>);
+    HasCopyClone(PhantomData::<char3>);
+    HasCopyClone(PhantomData::<char4>);
     HasCopyClone(PhantomData::<float2>);
+    HasCopyClone(PhantomData::<float2x2>);
+    HasCopyClone(PhantomData::<float2x3>);
+    HasCopyClone(PhantomData::<float2x4>);
+    HasCopyClone(PhantomData::<float3>);
+    HasCopyClone(PhantomData::<float3x2>);
     HasCopyClone(PhantomData::<float3x3>);
+    HasCopyClone(PhantomData::<float3x4>);
     HasCopyClone(PhantomData::<float4>);
+    HasCopyClone(PhantomData::<float4x2>);
     HasCopyClone(PhantomData::<float4x3>);
     HasCopyClone(PhantomData::<float4x4>);
     HasCopyClone(PhantomData::<half2>);
+    HasCopyClone(PhantomData::<half2x2>);
+    HasCopyClone(PhantomData::<half2x3>);
+    HasCopyClone(PhantomData::<half2x4>);
     HasCopyClone(PhantomData::<half3>);
+    HasCopyClone(PhantomData::<half3x2>);
     HasCopyClone(PhantomData::<half3x3>);
+    HasCopyClone(PhantomData::<half3x4>);
     HasCopyClone(PhantomData::<half4>);
+    HasCopyClone(PhantomData::<half4x2>);
+    HasCopyClone(PhantomData::<half4x3>);
+    HasCopyClone(PhantomData::<half4x4>);
+    HasCopyClone(PhantomData::<int2>);
+    HasCopyClone(PhantomData::<int3>);
+    HasCopyClone(PhantomData::<int4>);
+    HasCopyClone(PhantomData::<packed_char2>);
+    HasCopyClone(PhantomData::<packed_char3>);
+    HasCopyClone(PhantomData::<packed_char4>);
     HasCopyClone(PhantomData::<packed_float2>);
     HasCopyClone(PhantomData::<packed_float3>);
     HasCopyClone(PhantomData::<packed_float4>);
     HasCopyClone(PhantomData::<packed_half2>);
     HasCopyClone(PhantomData::<packed_half3>);
     HasCopyClone(PhantomData::<packed_half4>);
+    HasCopyClone(PhantomData::<packed_int2>);
+    HasCopyClone(PhantomData::<packed_int3>);
+    HasCopyClone(PhantomData::<packed_int4>);
+    HasCopyClone(PhantomData::<packed_short2>);
+    HasCopyClone(PhantomData::<packed_short3>);
+    HasCopyClone(PhantomData::<packed_short4>);
+    HasCopyClone(PhantomData::<packed_uchar2>);
+    HasCopyClone(PhantomData::<packed_uchar3>);
+    HasCopyClone(PhantomData::<packed_uchar4>);
+    HasCopyClone(PhantomData::<packed_uint2>);
+    HasCopyClone(PhantomData::<packed_uint3>);
+    HasCopyClone(PhantomData::<packed_uint4>);
+    HasCopyClone(PhantomData::<packed_ushort2>);
+    HasCopyClone(PhantomData::<packed_ushort3>);
+    HasCopyClone(PhantomData::<packed_ushort4>);
+    HasCopyClone(PhantomData::<short2>);
+    HasCopyClone(PhantomData::<short3>);
+    HasCopyClone(PhantomData::<short4>);
+    HasCopyClone(PhantomData::<uchar2>);
+    HasCopyClone(PhantomData::<uchar3>);
+    HasCopyClone(PhantomData::<uchar4>);
+    HasCopyClone(PhantomData::<uint2>);
+    HasCopyClone(PhantomData::<uint3>);
+    HasCopyClone(PhantomData::<uint4>);
     HasCopyClone(PhantomData::<ushort2>);
+    HasCopyClone(PhantomData::<ushort3>);
+    HasCopyClone(PhantomData::<ushort4>);
 }
\ No newline at end of file
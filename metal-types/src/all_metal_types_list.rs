@@ -3,28 +3,84 @@ GENERATED FILE. DO NOT MODIFY.
 
 This file is generated by the `build.rs`.
 ***************************************************************************************************/
-pub const TYPES: [&'static str; 23] = [
+pub const TYPES: [&'static str; 79] = [
 	"DebugPath",
 	"Geometry",
 	"GeometryNoTxCoords",
 	"Material",
 	"ModelSpace",
 	"ProjectedSpace",
+	"SHIrradiance",
 	"TriNormals",
+	"TriNormalsOct16",
+	"TriNormalsXyz111110",
+	"bool2",
+	"bool3",
+	"bool4",
+	"char2",
+	"char3",
+	"char4",
 	"float2",
+	"float2x2",
+	"float2x3",
+	"float2x4",
+	"float3",
+	"float3x2",
 	"float3x3",
+	"float3x4",
 	"float4",
+	"float4x2",
 	"float4x3",
 	"float4x4",
 	"half2",
+	"half2x2",
+	"half2x3",
+	"half2x4",
 	"half3",
+	"half3x2",
 	"half3x3",
+	"half3x4",
 	"half4",
+	"half4x2",
+	"half4x3",
+	"half4x4",
+	"int2",
+	"int3",
+	"int4",
+	"packed_char2",
+	"packed_char3",
+	"packed_char4",
 	"packed_float2",
 	"packed_float3",
 	"packed_float4",
 	"packed_half2",
 	"packed_half3",
 	"packed_half4",
+	"packed_int2",
+	"packed_int3",
+	"packed_int4",
+	"packed_short2",
+	"packed_short3",
+	"packed_short4",
+	"packed_uchar2",
+	"packed_uchar3",
+	"packed_uchar4",
+	"packed_uint2",
+	"packed_uint3",
+	"packed_uint4",
+	"packed_ushort2",
+	"packed_ushort3",
+	"packed_ushort4",
+	"short2",
+	"short3",
+	"short4",
+	"uchar2",
+	"uchar3",
+	"uchar4",
+	"uint2",
+	"uint3",
+	"uint4",
 	"ushort2",
+	"ushort3",
+	"ushort4",
 ];
@@ -0,0 +1,466 @@
+// Conversions between f32 SIMD vectors/matrices and half-precision types (`half2`, `half3`,
+// `half4`, `half3x3`, `half4x4`, and packed variants), and basic arithmetic.
+//
+// Arithmetic is performed in f32 and rounded back to half (same as `half::f16`'s operators).
+use super::{
+    f16, f32x4x4, float3x3, half2, half3, half3x3, half4, half4x4, packed_half2, packed_half3,
+    packed_half4,
+};
+use std::{
+    error::Error,
+    ffi::c_ushort,
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+    simd::{f32x2, f32x4},
+};
+
+#[inline(always)]
+fn to_half(v: f32) -> c_ushort {
+    f16::from_f32(v).to_bits()
+}
+
+#[inline(always)]
+fn to_f32(v: c_ushort) -> f32 {
+    f16::from_bits(v).to_f32()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HalfConversionError {
+    // Finite value is outside the range of half (larger magnitude than `f16::MAX`).
+    Overflow {
+        index: usize,
+        value: f32,
+    },
+    // Relative error of the rounded value exceeds the maximum allowed (ex. values too small for
+    // half, including non-zero values rounded to zero).
+    PrecisionLoss {
+        index: usize,
+        value: f32,
+        rounded: f32,
+    },
+}
+
+impl Display for HalfConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow { index, value } => {
+                write!(f, "Component {index} ({value}) overflows half")
+            }
+            Self::PrecisionLoss {
+                index,
+                value,
+                rounded,
+            } => write!(
+                f,
+                "Component {index} ({value}) loses too much precision when converted to half ({rounded})"
+            ),
+        }
+    }
+}
+
+impl Error for HalfConversionError {}
+
+// Converts each component to half, reporting overflow or a relative error greater than
+// `max_relative_error`. Infinities and NaNs are converted as is.
+pub fn checked_to_half<const N: usize>(
+    values: [f32; N],
+    max_relative_error: f32,
+) -> Result<[c_ushort; N], HalfConversionError> {
+    let mut out = [0; N];
+    for (index, (&value, out)) in values.iter().zip(out.iter_mut()).enumerate() {
+        let half = f16::from_f32(value);
+        if value.is_finite() {
+            if half.is_infinite() {
+                return Err(HalfConversionError::Overflow { index, value });
+            }
+            let rounded = half.to_f32();
+            if value != 0. && ((rounded - value) / value).abs() > max_relative_error {
+                return Err(HalfConversionError::PrecisionLoss {
+                    index,
+                    value,
+                    rounded,
+                });
+            }
+        }
+        *out = half.to_bits();
+    }
+    Ok(out)
+}
+
+macro_rules! impl_half_vector {
+    ($($ty:ident . $field:ident: [$($c:ident),+] ($n:literal)),* $(,)?) => {$(
+        impl $ty {
+            #[inline]
+            pub fn new($($c: f32),+) -> Self {
+                Self::from_f32s([$($c),+])
+            }
+
+            #[inline]
+            pub fn from_f32s(v: [f32; $n]) -> Self {
+                Self { $field: v.map(to_half) }
+            }
+
+            // See `checked_to_half()`.
+            #[inline]
+            pub fn checked_from_f32s(
+                v: [f32; $n],
+                max_relative_error: f32,
+            ) -> Result<Self, HalfConversionError> {
+                Ok(Self {
+                    $field: checked_to_half(v, max_relative_error)?,
+                })
+            }
+
+            #[inline]
+            pub fn to_f32s(&self) -> [f32; $n] {
+                self.$field.map(to_f32)
+            }
+        }
+
+        impl Add for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                let (l, r) = (self.to_f32s(), rhs.to_f32s());
+                Self::from_f32s(std::array::from_fn(|i| l[i] + r[i]))
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                let (l, r) = (self.to_f32s(), rhs.to_f32s());
+                Self::from_f32s(std::array::from_fn(|i| l[i] - r[i]))
+            }
+        }
+
+        // Component-wise multiplication.
+        impl Mul for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self::Output {
+                let (l, r) = (self.to_f32s(), rhs.to_f32s());
+                Self::from_f32s(std::array::from_fn(|i| l[i] * r[i]))
+            }
+        }
+
+        impl Mul<f32> for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self::from_f32s(self.to_f32s().map(|v| v * rhs))
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                // Flip the sign bit, exact for all values (including infinities and NaNs).
+                Self { $field: self.$field.map(|v| v ^ 0x8000) }
+            }
+        }
+    )*};
+}
+
+impl_half_vector!(
+    half2.xy: [x, y] (2),
+    half3.xyz: [x, y, z] (3),
+    half4.xyzw: [x, y, z, w] (4),
+    packed_half2.xy: [x, y] (2),
+    packed_half3.xyz: [x, y, z] (3),
+    packed_half4.xyzw: [x, y, z, w] (4),
+);
+
+macro_rules! impl_from_simd {
+    ($($simd:ident <=> $ty:ident . $field:ident: [$($i:literal),+] + [$($pad:literal),*]),* $(,)?) => {$(
+        impl From<$simd> for $ty {
+            #[inline]
+            fn from(v: $simd) -> Self {
+                Self { $field: [$(to_half(v[$i])),+] }
+            }
+        }
+
+        impl From<$ty> for $simd {
+            #[inline]
+            fn from(v: $ty) -> Self {
+                $simd::from_array([$(to_f32(v.$field[$i])),+ $(, $pad)*])
+            }
+        }
+    )*};
+}
+
+// 3 component halves are converted from the XYZ components of a f32x4 (W is ignored), and converted
+// to a f32x4 with W set to 0.
+impl_from_simd!(
+    f32x2 <=> half2.xy: [0, 1] + [],
+    f32x4 <=> half3.xyz: [0, 1, 2] + [0.],
+    f32x4 <=> half4.xyzw: [0, 1, 2, 3] + [],
+    f32x2 <=> packed_half2.xy: [0, 1] + [],
+    f32x4 <=> packed_half3.xyz: [0, 1, 2] + [0.],
+    f32x4 <=> packed_half4.xyzw: [0, 1, 2, 3] + [],
+);
+
+impl half3x3 {
+    #[inline]
+    pub fn from_columns(columns: [[f32; 3]; 3]) -> Self {
+        Self {
+            columns: columns.map(|[x, y, z]| [to_half(x), to_half(y), to_half(z), 0]),
+        }
+    }
+
+    // See `checked_to_half()`, the error's `index` is the column major component index (ex.
+    // column 1, row 2 => 5).
+    pub fn checked_from_columns(
+        columns: [[f32; 3]; 3],
+        max_relative_error: f32,
+    ) -> Result<Self, HalfConversionError> {
+        let [c0, c1, c2] = columns;
+        let [x0, y0, z0, x1, y1, z1, x2, y2, z2] = checked_to_half(
+            [
+                c0[0], c0[1], c0[2], c1[0], c1[1], c1[2], c2[0], c2[1], c2[2],
+            ],
+            max_relative_error,
+        )?;
+        Ok(Self {
+            columns: [[x0, y0, z0, 0], [x1, y1, z1, 0], [x2, y2, z2, 0]],
+        })
+    }
+
+    #[inline]
+    pub fn to_columns(&self) -> [[f32; 3]; 3] {
+        self.columns
+            .map(|[x, y, z, _]| [to_f32(x), to_f32(y), to_f32(z)])
+    }
+}
+
+// Upper-left 3x3 (ex. `half3x3::from(m_model_to_world.normal_matrix())`).
+impl From<f32x4x4> for half3x3 {
+    #[inline]
+    fn from(m: f32x4x4) -> Self {
+        let c = m.columns;
+        Self::from_columns([0, 1, 2].map(|i| [c[i][0], c[i][1], c[i][2]]))
+    }
+}
+
+impl From<float3x3> for half3x3 {
+    #[inline]
+    fn from(m: float3x3) -> Self {
+        Self::from_columns(m.columns.map(|[x, y, z, _]| [x, y, z]))
+    }
+}
+
+impl From<half3x3> for float3x3 {
+    #[inline]
+    fn from(m: half3x3) -> Self {
+        Self {
+            columns: m.to_columns().map(|[x, y, z]| [x, y, z, 0.]),
+        }
+    }
+}
+
+impl From<half3x3> for f32x4x4 {
+    #[inline]
+    fn from(m: half3x3) -> Self {
+        let [c0, c1, c2] = m.to_columns();
+        Self {
+            columns: [
+                [c0[0], c0[1], c0[2], 0.],
+                [c1[0], c1[1], c1[2], 0.],
+                [c2[0], c2[1], c2[2], 0.],
+                [0., 0., 0., 1.],
+            ],
+        }
+    }
+}
+
+impl Mul<half3> for half3x3 {
+    type Output = half3;
+
+    #[inline]
+    fn mul(self, rhs: half3) -> Self::Output {
+        let [c0, c1, c2] = self.to_columns();
+        let [x, y, z] = rhs.to_f32s();
+        half3::from_f32s([0, 1, 2].map(|i| c0[i] * x + c1[i] * y + c2[i] * z))
+    }
+}
+
+impl Mul<half3x3> for half3x3 {
+    type Output = half3x3;
+
+    #[inline]
+    fn mul(self, rhs: half3x3) -> Self::Output {
+        Self {
+            columns: rhs.columns.map(|[x, y, z, _]| {
+                let [x, y, z] = (self * half3 { xyz: [x, y, z] }).xyz;
+                [x, y, z, 0]
+            }),
+        }
+    }
+}
+
+impl From<f32x4x4> for half4x4 {
+    #[inline]
+    fn from(m: f32x4x4) -> Self {
+        Self {
+            columns: m.columns.map(|c| c.map(to_half)),
+        }
+    }
+}
+
+impl From<half4x4> for f32x4x4 {
+    #[inline]
+    fn from(m: half4x4) -> Self {
+        Self {
+            columns: m.columns.map(|c| c.map(to_f32)),
+        }
+    }
+}
+
+impl half4x4 {
+    // See `checked_to_half()`, the error's `index` is the column major component index (ex.
+    // column 1, row 2 => 6).
+    pub fn checked_from(m: f32x4x4, max_relative_error: f32) -> Result<Self, HalfConversionError> {
+        let mut columns = [[0; 4]; 4];
+        for (i, (c, out)) in m.columns.iter().zip(columns.iter_mut()).enumerate() {
+            *out = checked_to_half(*c, max_relative_error).map_err(|e| match e {
+                HalfConversionError::Overflow { index, value } => HalfConversionError::Overflow {
+                    index: i * 4 + index,
+                    value,
+                },
+                HalfConversionError::PrecisionLoss {
+                    index,
+                    value,
+                    rounded,
+                } => HalfConversionError::PrecisionLoss {
+                    index: i * 4 + index,
+                    value,
+                    rounded,
+                },
+            })?;
+        }
+        Ok(Self { columns })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vector_conversions() {
+        let v = half3::new(1., -0.5, 2.);
+        assert_eq!(v.to_f32s(), [1., -0.5, 2.]);
+        assert_eq!(
+            half3::from(f32x4::from_array([1., -0.5, 2., 9.])).xyz,
+            v.xyz
+        );
+        assert_eq!(f32x4::from(v), f32x4::from_array([1., -0.5, 2., 0.]));
+
+        let v = half2::new(0.25, 4.);
+        assert_eq!(f32x2::from(v), f32x2::from_array([0.25, 4.]));
+        assert_eq!(half2::from(f32x2::from_array([0.25, 4.])).xy, v.xy);
+
+        let v = packed_half4::new(1., 2., 3., 4.);
+        assert_eq!(f32x4::from(v), f32x4::from_array([1., 2., 3., 4.]));
+        assert_eq!(
+            packed_half4::from(f32x4::from_array([1., 2., 3., 4.])).xyzw,
+            v.xyzw
+        );
+    }
+
+    #[test]
+    fn test_vector_arithmetic() {
+        let a = half3::new(1., 2., 3.);
+        let b = half3::new(0.5, -1., 2.);
+        assert_eq!((a + b).to_f32s(), [1.5, 1., 5.]);
+        assert_eq!((a - b).to_f32s(), [0.5, 3., 1.]);
+        assert_eq!((a * b).to_f32s(), [0.5, -2., 6.]);
+        assert_eq!((a * 2.).to_f32s(), [2., 4., 6.]);
+        assert_eq!((-a).to_f32s(), [-1., -2., -3.]);
+        assert_eq!((-half2::new(0., 1.)).to_f32s(), [-0., -1.]);
+    }
+
+    #[test]
+    fn test_checked_conversion() {
+        assert_eq!(
+            half3::checked_from_f32s([1., 0.1, -65504.], 1e-3).map(|v| v.xyz),
+            Ok(half3::new(1., 0.1, -65504.).xyz)
+        );
+        assert_eq!(
+            half3::checked_from_f32s([1., 70000., 0.], 1e-3).map(|v| v.xyz),
+            Err(HalfConversionError::Overflow {
+                index: 1,
+                value: 70000.
+            })
+        );
+        assert_eq!(
+            half2::checked_from_f32s([1e-8, 1.], 1e-3).map(|v| v.xy),
+            Err(HalfConversionError::PrecisionLoss {
+                index: 0,
+                value: 1e-8,
+                rounded: 0.
+            })
+        );
+        // Representable, but exceeds the allowed relative error.
+        assert!(matches!(
+            half2::checked_from_f32s([1., 1.0001], 1e-5),
+            Err(HalfConversionError::PrecisionLoss { index: 1, .. })
+        ));
+        assert!(half2::checked_from_f32s([1., 1.0001], 1e-3).is_ok());
+        // Infinities are not considered overflow.
+        assert!(half2::checked_from_f32s([f32::INFINITY, 0.], 0.).is_ok());
+    }
+
+    #[test]
+    fn test_matrix_conversions() {
+        let m = f32x4x4::scale_translate(2., 3., 4., 5., 6., 7.);
+        let h = half3x3::from(m);
+        assert_eq!(h.to_columns(), [[2., 0., 0.], [0., 3., 0.], [0., 0., 4.]]);
+        assert_eq!(f32x4x4::from(h), f32x4x4::scale(2., 3., 4., 1.));
+        assert_eq!(half3x3::from(float3x3::from(m)).columns, h.columns);
+        assert_eq!(
+            float3x3::from(h).columns,
+            float3x3::from(f32x4x4::scale(2., 3., 4., 1.)).columns
+        );
+
+        assert_eq!(f32x4x4::from(half4x4::from(m)), m);
+        assert_eq!(half4x4::checked_from(m, 0.).map(f32x4x4::from), Ok(m));
+        assert_eq!(
+            half4x4::checked_from(f32x4x4::translate(0., 1e5, 0.), 0.).map(f32x4x4::from),
+            Err(HalfConversionError::Overflow {
+                index: 13,
+                value: 1e5
+            })
+        );
+
+        assert_eq!(
+            half3x3::checked_from_columns([[1., 0., 0.], [0., 1e-9, 0.], [0., 0., 1.]], 1e-3)
+                .map(|m| m.columns),
+            Err(HalfConversionError::PrecisionLoss {
+                index: 4,
+                value: 1e-9,
+                rounded: 0.
+            })
+        );
+    }
+
+    #[test]
+    fn test_matrix_arithmetic() {
+        let m = half3x3::from(f32x4x4::scale(2., 3., 4., 1.));
+        // 90 degree rotation around Z
+        let r = half3x3::from_columns([[0., 1., 0.], [-1., 0., 0.], [0., 0., 1.]]);
+        assert_eq!((m * half3::new(1., 1., 1.)).to_f32s(), [2., 3., 4.]);
+        assert_eq!((r * half3::new(1., 2., 3.)).to_f32s(), [-2., 1., 3.]);
+        assert_eq!(
+            (m * r).to_columns(),
+            [[0., 3., 0.], [-2., 0., 0.], [0., 0., 4.]]
+        );
+    }
+}
@@ -2,11 +2,29 @@
 #![feature(portable_simd)]
 mod all_metal_types;
 mod all_metal_types_list;
+mod half_math;
+#[cfg(feature = "metal")]
+mod metal_conversions;
+mod metal_layout;
+mod primitives;
+mod quat;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod spherical_harmonics;
+#[cfg(test)]
+mod test_util;
+mod transform;
 mod tri_normals;
 
 pub use all_metal_types::*;
 pub use all_metal_types_list::*;
-use metal::{MTLPackedFloat3, MTLPackedFloat4x3};
+pub use half::f16;
+pub use half_math::*;
+pub use metal_layout::*;
+pub use metal_layout_derive::MetalLayout;
+pub use primitives::*;
+pub use quat::*;
+pub use transform::*;
 pub use tri_normals::*;
 
 /**************************************************************************************************
@@ -85,31 +103,12 @@ fn dot(lhs: f32x4, rhs: f32x4) -> f32 {
     (lhs * rhs).reduce_sum()
 }
 
-impl From<MTLPackedFloat4x3> for float4x3 {
-    #[inline]
-    fn from(MTLPackedFloat4x3 { columns: c }: MTLPackedFloat4x3) -> Self {
-        Self {
-            columns: [
-                [c[0].0, c[0].1, c[0].2, 0.],
-                [c[1].0, c[1].1, c[1].2, 0.],
-                [c[2].0, c[2].1, c[2].2, 0.],
-                [c[3].0, c[3].1, c[3].2, 0.],
-            ],
-        }
-    }
-}
-
-impl From<float4x3> for MTLPackedFloat4x3 {
-    fn from(float4x3 { columns: c }: float4x3) -> Self {
-        Self {
-            columns: [
-                MTLPackedFloat3(c[0][0], c[0][1], c[0][2]),
-                MTLPackedFloat3(c[1][0], c[1][1], c[1][2]),
-                MTLPackedFloat3(c[2][0], c[2][1], c[2][2]),
-                MTLPackedFloat3(c[3][0], c[3][1], c[3][2]),
-            ],
-        }
-    }
+// Cross product of the XYZ components, W is zero.
+#[inline]
+fn cross(lhs: f32x4, rhs: f32x4) -> f32x4 {
+    let &[lx, ly, lz, _] = lhs.as_array();
+    let &[rx, ry, rz, _] = rhs.as_array();
+    f32x4::from_array([ly * rz - lz * ry, lz * rx - lx * rz, lx * ry - ly * rx, 0.])
 }
 
 #[allow(non_camel_case_types)]
@@ -216,6 +215,32 @@ impl f32x4x4 {
         }
     }
 
+    // Transforms normals (directions perpendicular to a surface) consistently with how this matrix
+    // transforms positions: the inverse-transpose of the upper 3x3 (translation is dropped).
+    // Unlike the upper 3x3 itself, normals remain perpendicular to the surface when there's
+    // non-uniform scale.
+    //
+    // Computed as the cofactor matrix divided by the determinant. Dividing by the (signed)
+    // determinant keeps normals facing outward for mirroring transforms (negative determinant).
+    // Degenerate transforms (zero determinant, ex. scaled to zero along an axis) have no inverse,
+    // the cofactor matrix alone still produces correct normal directions.
+    #[inline]
+    pub fn normal_matrix(&self) -> Self {
+        let c = self.columns;
+        let [c0, c1, c2] = [c[0], c[1], c[2]].map(|c| f32x4::from_array([c[0], c[1], c[2], 0.]));
+        let cofactor = [cross(c1, c2), cross(c2, c0), cross(c0, c1)];
+        let det = dot(c0, cofactor[0]);
+        let inv_det = f32x4::splat(if det == 0. { 1. } else { 1. / det });
+        Self {
+            columns: [
+                (cofactor[0] * inv_det).to_array(),
+                (cofactor[1] * inv_det).to_array(),
+                (cofactor[2] * inv_det).to_array(),
+                [0., 0., 0., 1.],
+            ],
+        }
+    }
+
     // TODO: Add translate_scale_rotate()
     // - Many projects need this for m_model_to_world and instead perform the heavy 3 matrix
     //   multiplications.
@@ -288,6 +313,120 @@ impl f32x4x4 {
         Self::x_rotate(xrot) * Self::y_rotate(yrot) * Self::z_rotate(zrot)
     }
 
+    // Rotation around an arbitrary axis (XYZ components, W is ignored), using the same rotation
+    // direction as `x_rotate()`, `y_rotate()`, and `z_rotate()`.
+    // ex. `axis_angle_rotate([1, 0, 0, 0], a) == x_rotate(a)`
+    #[inline]
+    pub fn axis_angle_rotate(axis: f32x4, angle: f32) -> Self {
+        let axis = f32x4::from_array([axis[0], axis[1], axis[2], 0.]).normalize();
+        let &[x, y, z, _] = axis.as_array();
+        let (s, c) = angle.sin_cos();
+        let t = 1. - c;
+        Self::new(
+            [t * x * x + c, t * x * y + s * z, t * x * z - s * y, 0.],
+            [t * x * y - s * z, t * y * y + c, t * y * z + s * x, 0.],
+            [t * x * z + s * y, t * y * z - s * x, t * z * z + c, 0.],
+            [0., 0., 0., 1.],
+        )
+    }
+
+    // Transforms world space to camera space, with the camera positioned at `eye` and looking at
+    // `target` (XYZ components, W is ignored).
+    // Camera space is left-handed: looking down the +Z axis, +Y is up, and +X is right. This matches
+    // `metal_app::components::Camera`.
+    #[inline]
+    pub fn look_at(eye: f32x4, target: f32x4, up: f32x4) -> Self {
+        let eye = f32x4::from_array([eye[0], eye[1], eye[2], 0.]);
+        let z = (f32x4::from_array([target[0], target[1], target[2], 0.]) - eye).normalize();
+        let x = cross(up, z).normalize();
+        let y = cross(z, x);
+        let &[xx, xy, xz, _] = x.as_array();
+        let &[yx, yy, yz, _] = y.as_array();
+        let &[zx, zy, zz, _] = z.as_array();
+        Self::new(
+            [xx, xy, xz, -dot(x, eye)],
+            [yx, yy, yz, -dot(y, eye)],
+            [zx, zy, zz, -dot(z, eye)],
+            [0., 0., 0., 1.],
+        )
+    }
+
+    // Perspective projection of camera space (see `look_at()`), with a vertical field of view
+    // `fov_y` (radians), visible Z coordinates between `near` and `far`.
+    //
+    // IMPORTANT: Metal's NDC coordinate space has a Z range of [0, 1], **NOT [-1, 1]** (OpenGL).
+    // - `perspective()`                     near -> 0, far -> 1
+    // - `perspective_infinite()`            near -> 0, infinity -> 1
+    // - `perspective_reverse_z()`           near -> 1, far -> 0
+    // - `perspective_infinite_reverse_z()`  near -> 1, infinity -> 0
+    //
+    // Reverse-Z distributes floating point depth precision more evenly across the Z range (use with
+    // a `MTLCompareFunction::Greater` depth test and a depth clear value of 0).
+    // See https://developer.nvidia.com/content/depth-precision-visualized
+    #[inline]
+    pub fn perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        let z_range = far - near;
+        Self::perspective_with_z(fov_y, aspect_ratio, far / z_range, -near * far / z_range)
+    }
+
+    #[inline]
+    pub fn perspective_infinite(fov_y: f32, aspect_ratio: f32, near: f32) -> Self {
+        Self::perspective_with_z(fov_y, aspect_ratio, 1., -near)
+    }
+
+    #[inline]
+    pub fn perspective_reverse_z(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        let z_range = far - near;
+        Self::perspective_with_z(fov_y, aspect_ratio, -near / z_range, near * far / z_range)
+    }
+
+    #[inline]
+    pub fn perspective_infinite_reverse_z(fov_y: f32, aspect_ratio: f32, near: f32) -> Self {
+        Self::perspective_with_z(fov_y, aspect_ratio, 0., near)
+    }
+
+    #[inline]
+    fn perspective_with_z(fov_y: f32, aspect_ratio: f32, z_scale: f32, z_translate: f32) -> Self {
+        let sy = 1. / (fov_y / 2.).tan();
+        let sx = sy / aspect_ratio;
+        Self::new(
+            [sx, 0., 0., 0.],
+            [0., sy, 0., 0.],
+            [0., 0., z_scale, z_translate],
+            [0., 0., 1., 0.],
+        )
+    }
+
+    // Perspective projection of an (possibly off-center) view frustum, where `left`, `right`,
+    // `bottom`, and `top` are the bounds of the view at Z = `near`.
+    #[inline]
+    pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        let w = right - left;
+        let h = top - bottom;
+        let z_range = far - near;
+        Self::new(
+            [2. * near / w, 0., -(right + left) / w, 0.],
+            [0., 2. * near / h, -(top + bottom) / h, 0.],
+            [0., 0., far / z_range, -near * far / z_range],
+            [0., 0., 1., 0.],
+        )
+    }
+
+    // Orthographic projection of the box bounded by `left`, `right`, `bottom`, `top`, `near`, and
+    // `far` into Metal's NDC coordinate space (Z range of [0, 1]).
+    #[inline]
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        let w = right - left;
+        let h = top - bottom;
+        let z_range = far - near;
+        Self::new(
+            [2. / w, 0., 0., -(right + left) / w],
+            [0., 2. / h, 0., -(top + bottom) / h],
+            [0., 0., 1. / z_range, -near / z_range],
+            [0., 0., 0., 1.],
+        )
+    }
+
     #[inline]
     pub const fn identity() -> Self {
         Self::translate(0., 0., 0.)
@@ -397,29 +536,12 @@ impl From<f32x4x4> for float3x3 {
     }
 }
 
-impl From<f32x4x4> for MTLPackedFloat4x3 {
-    #[inline(always)]
-    fn from(m: f32x4x4) -> Self {
+impl ModelSpace {
+    #[inline]
+    pub fn from_model_to_world(m_world_to_projection: f32x4x4, m_model_to_world: f32x4x4) -> Self {
         Self {
-            columns: [
-                MTLPackedFloat3(m.columns[0][0], m.columns[0][1], m.columns[0][2]),
-                MTLPackedFloat3(m.columns[1][0], m.columns[1][1], m.columns[1][2]),
-                MTLPackedFloat3(m.columns[2][0], m.columns[2][1], m.columns[2][2]),
-                MTLPackedFloat3(m.columns[3][0], m.columns[3][1], m.columns[3][2]),
-            ],
-        }
-    }
-}
-impl From<MTLPackedFloat4x3> for f32x4x4 {
-    #[inline(always)]
-    fn from(m: MTLPackedFloat4x3) -> Self {
-        f32x4x4 {
-            columns: [
-                [m.columns[0].0, m.columns[0].1, m.columns[0].2, 0.],
-                [m.columns[1].0, m.columns[1].1, m.columns[1].2, 0.],
-                [m.columns[2].0, m.columns[2].1, m.columns[2].2, 0.],
-                [m.columns[3].0, m.columns[3].1, m.columns[3].2, 1.],
-            ],
+            m_model_to_projection: m_world_to_projection * m_model_to_world,
+            m_normal_to_world: m_model_to_world.normal_matrix().into(),
         }
     }
 }
@@ -431,15 +553,9 @@ mod test {
 
     mod test_f32x4_extras {
         use super::*;
+        use crate::test_util::assert_approx_eq_f32x4;
 
-        fn assert_eq_f32x4(actual: f32x4, expected: f32x4) {
-            const TOLERANCE: f32x4 = f32x4::from_array([1e-6; 4]);
-            let pass = (actual - expected).abs().simd_lt(TOLERANCE).all();
-            if !pass {
-                dbg!(expected, actual);
-            }
-            assert!(pass);
-        }
+        const TOLERANCE: f32 = 1e-6;
 
         #[test]
         fn test_length() {
@@ -454,7 +570,7 @@ mod test {
             let v = f32x4::from_array([1., 2., 3., 4.]);
             let actual = v.normalize();
             let expected = v / f32x4::splat(v.length());
-            assert_eq_f32x4(actual, expected);
+            assert_approx_eq_f32x4(actual, expected, TOLERANCE);
         }
 
         #[test]
@@ -465,7 +581,7 @@ mod test {
                 let expected: f32x4 = expected.into();
                 let actual = v.reflect(i);
 
-                assert_eq_f32x4(actual, expected);
+                assert_approx_eq_f32x4(actual, expected, TOLERANCE);
             }
             t([1., 0., 0., 0.], [1., 1., 0., 0.], [-1., 1., 0., 0.]);
             t([1., 1., 0., 0.], [1., 0., 0., 0.], [0., -1., 0., 0.]);
@@ -475,6 +591,218 @@ mod test {
 
     mod test_f32x4x4 {
         use super::*;
+        use crate::test_util::assert_approx_eq_f32x4x4;
+        use std::f32::consts::PI;
+
+        const TOLERANCE: f32 = 1e-6;
+
+        // Projects a camera space point into NDC.
+        fn project(m: f32x4x4, p: [f32; 3]) -> f32x4 {
+            let p = m * f32x4::from_array([p[0], p[1], p[2], 1.]);
+            p / f32x4::splat(p[3])
+        }
+
+        #[test]
+        fn test_axis_angle_rotate() {
+            for angle in [0., 0.5, -1., PI / 2., 3.] {
+                let axis_angle_rotate =
+                    |x, y, z| f32x4x4::axis_angle_rotate(f32x4::from_array([x, y, z, 0.]), angle);
+                assert_approx_eq_f32x4x4(
+                    axis_angle_rotate(1., 0., 0.),
+                    f32x4x4::x_rotate(angle),
+                    TOLERANCE,
+                );
+                assert_approx_eq_f32x4x4(
+                    axis_angle_rotate(0., 1., 0.),
+                    f32x4x4::y_rotate(angle),
+                    TOLERANCE,
+                );
+                assert_approx_eq_f32x4x4(
+                    axis_angle_rotate(0., 0., 1.),
+                    f32x4x4::z_rotate(angle),
+                    TOLERANCE,
+                );
+                // Axis is normalized
+                assert_approx_eq_f32x4x4(
+                    axis_angle_rotate(0., 0., 5.),
+                    f32x4x4::z_rotate(angle),
+                    TOLERANCE,
+                );
+            }
+
+            // Rotating around the diagonal by 120 degrees, cycles the axes.
+            let m = f32x4x4::axis_angle_rotate(f32x4::from_array([1., 1., 1., 0.]), -2. * PI / 3.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0., 0., 1., 0.],
+                    [1., 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 0., 1.],
+                ),
+                TOLERANCE,
+            );
+        }
+
+        #[test]
+        fn test_look_at() {
+            let up = f32x4::from_array([0., 1., 0., 0.]);
+            let origin = f32x4::from_array([0., 0., 0., 1.]);
+
+            // Same as `metal_app::components::Camera` with no rotation.
+            assert_approx_eq_f32x4x4(
+                f32x4x4::look_at(f32x4::from_array([0., 0., -5., 1.]), origin, up),
+                f32x4x4::translate(0., 0., 5.),
+                TOLERANCE,
+            );
+
+            let m = f32x4x4::look_at(f32x4::from_array([5., 0., 0., 1.]), origin, up);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0., 0., 1., 0.],
+                    [0., 1., 0., 0.],
+                    [-1., 0., 0., 5.],
+                    [0., 0., 0., 1.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(m * origin, f32x4::from_array([0., 0., 5., 1.]));
+        }
+
+        #[test]
+        fn test_perspective() {
+            let m = f32x4x4::perspective(PI / 2., 2., 1., 3.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0.5, 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 1.5, -1.5],
+                    [0., 0., 1., 0.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(project(m, [0., 0., 1.])[2], 0.);
+            assert_eq!(project(m, [0., 0., 3.])[2], 1.);
+            // Top right corner of the near plane
+            assert_eq!(
+                project(m, [2., 1., 1.]),
+                f32x4::from_array([1., 1., 0., 1.])
+            );
+        }
+
+        #[test]
+        fn test_perspective_infinite() {
+            let m = f32x4x4::perspective_infinite(PI / 2., 2., 1.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0.5, 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 1., -1.],
+                    [0., 0., 1., 0.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(project(m, [0., 0., 1.])[2], 0.);
+            assert!(project(m, [0., 0., 1e6])[2] < 1.);
+            assert!(project(m, [0., 0., 1e6])[2] > 0.9999);
+        }
+
+        #[test]
+        fn test_perspective_reverse_z() {
+            let m = f32x4x4::perspective_reverse_z(PI / 2., 2., 1., 3.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0.5, 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., -0.5, 1.5],
+                    [0., 0., 1., 0.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(project(m, [0., 0., 1.])[2], 1.);
+            assert_eq!(project(m, [0., 0., 3.])[2], 0.);
+        }
+
+        #[test]
+        fn test_perspective_infinite_reverse_z() {
+            let m = f32x4x4::perspective_infinite_reverse_z(PI / 2., 2., 1.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0.5, 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 0., 1.],
+                    [0., 0., 1., 0.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(project(m, [0., 0., 1.])[2], 1.);
+            assert!(project(m, [0., 0., 1e6])[2] > 0.);
+            assert!(project(m, [0., 0., 1e6])[2] < 0.0001);
+        }
+
+        #[test]
+        fn test_frustum() {
+            // Symmetric frustum is the same as perspective
+            assert_approx_eq_f32x4x4(
+                f32x4x4::frustum(-2., 2., -1., 1., 1., 3.),
+                f32x4x4::perspective(PI / 2., 2., 1., 3.),
+                TOLERANCE,
+            );
+
+            let m = f32x4x4::frustum(0., 2., -1., 3., 1., 3.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [1., 0., -1., 0.],
+                    [0., 0.5, -0.5, 0.],
+                    [0., 0., 1.5, -1.5],
+                    [0., 0., 1., 0.],
+                ),
+                TOLERANCE,
+            );
+            assert_eq!(
+                project(m, [0., -1., 1.]),
+                f32x4::from_array([-1., -1., 0., 1.])
+            );
+            assert_eq!(
+                project(m, [6., 9., 3.]),
+                f32x4::from_array([1., 1., 1., 1.])
+            );
+        }
+
+        #[test]
+        fn test_orthographic() {
+            let m = f32x4x4::orthographic(-2., 2., -1., 1., 0., 10.);
+            assert_approx_eq_f32x4x4(
+                m,
+                f32x4x4::new(
+                    [0.5, 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 0.1, 0.],
+                    [0., 0., 0., 1.],
+                ),
+                TOLERANCE,
+            );
+
+            let m = f32x4x4::orthographic(1., 3., 2., 6., 1., 5.);
+            assert_eq!(
+                project(m, [1., 2., 1.]),
+                f32x4::from_array([-1., -1., 0., 1.])
+            );
+            assert_eq!(
+                project(m, [3., 6., 5.]),
+                f32x4::from_array([1., 1., 1., 1.])
+            );
+            assert_eq!(
+                project(m, [2., 4., 3.]),
+                f32x4::from_array([0., 0., 0.5, 1.])
+            );
+        }
 
         #[test]
         fn test_inverse() {
@@ -492,6 +820,50 @@ mod test {
             }
         }
 
+        #[test]
+        fn test_normal_matrix() {
+            // Rotation and translation: same rotation, no translation.
+            let r = f32x4x4::rotate(1., 2., 3.);
+            assert_approx_eq_f32x4x4(
+                (f32x4x4::translate(40., 50., 60.) * r).normal_matrix(),
+                r,
+                TOLERANCE,
+            );
+
+            // Non-uniform scale
+            assert_approx_eq_f32x4x4(
+                f32x4x4::scale(2., 4., 8., 1.).normal_matrix(),
+                f32x4x4::scale(0.5, 0.25, 0.125, 1.),
+                TOLERANCE,
+            );
+
+            // Normals remain perpendicular to transformed tangents.
+            let m = f32x4x4::translate(1., 2., 3.)
+                * f32x4x4::rotate(0.5, -1., 2.)
+                * f32x4x4::scale(3., 0.5, 2., 1.);
+            let n = f32x4::from_array([1., 1., 1., 0.]).normalize();
+            let tangents = [[1., -1., 0., 0.], [0., 1., -1., 0.]].map(f32x4::from_array);
+            let transformed_n = m.normal_matrix() * n;
+            for t in tangents {
+                assert!(dot(m * t, transformed_n).abs() < 1e-6);
+                // Upper 3x3 (incorrect) does NOT remain perpendicular.
+                assert!(dot(m * t, m.zero_translate() * n).abs() > 1e-1);
+            }
+
+            // Mirroring (negative determinant)
+            let m = f32x4x4::scale(-1., 2., 2., 1.);
+            assert_approx_eq_f32x4x4(
+                m.normal_matrix(),
+                f32x4x4::scale(-1., 0.5, 0.5, 1.),
+                TOLERANCE,
+            );
+
+            // Degenerate (zero determinant), flattened onto the XY plane.
+            let m = f32x4x4::scale(2., 2., 0., 1.);
+            let n = m.normal_matrix() * f32x4::from_array([0., 0., 1., 0.]);
+            assert_eq!(n.normalize(), f32x4::from_array([0., 0., 1., 0.]));
+        }
+
         #[test]
         fn test_translate() {
             let t = f32x4x4::translate(40., 50., 60.);
@@ -595,11 +967,33 @@ mod test {
             );
         }
     }
+
+    mod test_model_space {
+        use super::*;
+
+        #[test]
+        fn test_from_model_to_world() {
+            let m_world_to_projection = f32x4x4::perspective(1., 1.5, 0.1, 100.);
+            let m_model_to_world = f32x4x4::translate(1., 2., 3.)
+                * f32x4x4::rotate(0.5, -1., 2.)
+                * f32x4x4::scale(3., 0.5, 2., 1.);
+            let model_space =
+                ModelSpace::from_model_to_world(m_world_to_projection, m_model_to_world);
+            assert_eq!(
+                model_space.m_model_to_projection,
+                m_world_to_projection * m_model_to_world
+            );
+            assert_eq!(
+                model_space.m_normal_to_world.columns,
+                float3x3::from(m_model_to_world.normal_matrix()).columns
+            );
+        }
+    }
 }
 
 #[repr(C)]
 #[allow(non_snake_case)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct MTLQuadTessellationFactorsHalf {
     pub edgeTessellationFactor: [c_ushort; 4usize],
     pub insideTessellationFactor: [c_ushort; 2usize],
@@ -0,0 +1,92 @@
+// Conversions to/from `metal` crate types (requires the `metal` feature).
+use super::{f32x4x4, float4x3, Transform};
+use metal::{MTLPackedFloat3, MTLPackedFloat4x3};
+
+impl From<MTLPackedFloat4x3> for float4x3 {
+    #[inline]
+    fn from(MTLPackedFloat4x3 { columns: c }: MTLPackedFloat4x3) -> Self {
+        Self {
+            columns: [
+                [c[0].0, c[0].1, c[0].2, 0.],
+                [c[1].0, c[1].1, c[1].2, 0.],
+                [c[2].0, c[2].1, c[2].2, 0.],
+                [c[3].0, c[3].1, c[3].2, 0.],
+            ],
+        }
+    }
+}
+
+impl From<float4x3> for MTLPackedFloat4x3 {
+    fn from(float4x3 { columns: c }: float4x3) -> Self {
+        Self {
+            columns: [
+                MTLPackedFloat3(c[0][0], c[0][1], c[0][2]),
+                MTLPackedFloat3(c[1][0], c[1][1], c[1][2]),
+                MTLPackedFloat3(c[2][0], c[2][1], c[2][2]),
+                MTLPackedFloat3(c[3][0], c[3][1], c[3][2]),
+            ],
+        }
+    }
+}
+
+impl From<f32x4x4> for MTLPackedFloat4x3 {
+    #[inline(always)]
+    fn from(m: f32x4x4) -> Self {
+        Self {
+            columns: [
+                MTLPackedFloat3(m.columns[0][0], m.columns[0][1], m.columns[0][2]),
+                MTLPackedFloat3(m.columns[1][0], m.columns[1][1], m.columns[1][2]),
+                MTLPackedFloat3(m.columns[2][0], m.columns[2][1], m.columns[2][2]),
+                MTLPackedFloat3(m.columns[3][0], m.columns[3][1], m.columns[3][2]),
+            ],
+        }
+    }
+}
+
+impl From<MTLPackedFloat4x3> for f32x4x4 {
+    #[inline(always)]
+    fn from(m: MTLPackedFloat4x3) -> Self {
+        f32x4x4 {
+            columns: [
+                [m.columns[0].0, m.columns[0].1, m.columns[0].2, 0.],
+                [m.columns[1].0, m.columns[1].1, m.columns[1].2, 0.],
+                [m.columns[2].0, m.columns[2].1, m.columns[2].2, 0.],
+                [m.columns[3].0, m.columns[3].1, m.columns[3].2, 1.],
+            ],
+        }
+    }
+}
+
+impl From<Transform> for MTLPackedFloat4x3 {
+    #[inline(always)]
+    fn from(t: Transform) -> Self {
+        f32x4x4::from(t).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Quat;
+    use std::simd::f32x4;
+
+    #[test]
+    fn test_f32x4x4_round_trip() {
+        let m = f32x4x4::translate(1., 2., 3.) * f32x4x4::rotate(0.5, -1., 2.);
+        let packed = MTLPackedFloat4x3::from(m);
+        assert_eq!(f32x4x4::from(packed), m);
+        assert_eq!(float4x3::from(packed).columns, float4x3::from(m).columns);
+        assert_eq!(f32x4x4::from(MTLPackedFloat4x3::from(float4x3::from(m))), m);
+    }
+
+    #[test]
+    fn test_transform_into_mtl_packed_float4x3() {
+        let v = |x, y, z| f32x4::from_array([x, y, z, 0.]);
+        let t = Transform::new(v(1., 2., 3.), Quat::IDENTITY, v(4., 5., 6.));
+        let MTLPackedFloat4x3 { columns: c } = t.into();
+        assert_eq!(
+            c.map(|MTLPackedFloat3(x, y, z)| [x, y, z]),
+            [[4., 0., 0.], [0., 5., 0.], [0., 0., 6.], [1., 2., 3.]]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+// Size and alignment of a type in the Metal Shading Language, see "Metal Shading Language
+// Specification (Version 2.4)":
+// - 2.1 Scalar Data Types / Table 2.1. Metal scalar data types
+// - 2.2 Vector Data Types / Table 2.3. Size and alignment of vector data types
+// - 2.3 Matrix Data Types / Table 2.5. Size and alignment of matrix data types
+//
+// Required by `Bind` and `BindMany` (metal-app) to ensure values passed to shaders have the same
+// layout in Rust and Metal.
+//
+// Use `#[derive(MetalLayout)]` to implement for `#[repr(C)]` structs, composed of types implementing
+// `MetalLayout`. Metal vector and matrix types (ex. `float3`) use the sizes and alignments listed by
+// the specification, so a Rust mirror with a different layout (ex. missing `#[repr(align(16))]`)
+// fails to compile. Derived structs check each field's offset against the Metal layout computed from
+// the fields' `METAL_SIZE` and `METAL_ALIGN`.
+//
+// # Safety
+//
+// `METAL_SIZE` and `METAL_ALIGN` must equal Rust's `size_of::<Self>()` and `align_of::<Self>()`.
+pub unsafe trait MetalLayout: Copy {
+    const METAL_SIZE: usize;
+    const METAL_ALIGN: usize;
+}
+
+// Scalars, pointers (`ulong`, ex. `ARG_CONSTANT_PTR(float)` on the Rust side), and types generated
+// by bindgen from `rust_bindgen_only_metal_types.h` (layout verified by bindgen's layout tests),
+// have identical Rust and Metal layouts.
+macro_rules! impl_metal_layout {
+    ($($ty:ty),* $(,)?) => {$(
+        unsafe impl MetalLayout for $ty {
+            const METAL_SIZE: usize = std::mem::size_of::<Self>();
+            const METAL_ALIGN: usize = std::mem::align_of::<Self>();
+        }
+    )*};
+}
+
+impl_metal_layout!(bool, i8, u8, i16, u16, i32, u32, i64, u64, f32, half::f16);
+
+// Arrays have no padding between elements (element size is a multiple of the element alignment).
+unsafe impl<T: MetalLayout, const N: usize> MetalLayout for [T; N] {
+    const METAL_SIZE: usize = T::METAL_SIZE * N;
+    const METAL_ALIGN: usize = T::METAL_ALIGN;
+}
+
+// Compile-fail tests for `#[derive(MetalLayout)]`, run by `cargo test --doc`.
+#[cfg(doctest)]
+mod compile_fail_test {
+    /// Baseline, verifies the compile-fail tests below only fail because of what they test.
+    /// ```
+    /// use metal_types::{float3, MetalLayout};
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct ReprC {
+    ///     a: float3,
+    ///     b: f32,
+    /// }
+    /// ```
+    struct ReprC;
+
+    /// ```compile_fail
+    /// use metal_types::{float3, MetalLayout};
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct NotReprC {
+    ///     a: float3,
+    ///     b: f32,
+    /// }
+    /// ```
+    struct NotReprC;
+
+    /// ```compile_fail
+    /// use metal_types::{float3, MetalLayout};
+    /// #[repr(C, packed)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct ReprPacked {
+    ///     a: float3,
+    ///     b: f32,
+    /// }
+    /// ```
+    struct ReprPacked;
+
+    /// ```compile_fail,E0277
+    /// use metal_types::{float3, MetalLayout};
+    /// #[derive(Copy, Clone)]
+    /// struct NoMetalLayout(f32);
+    ///
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct FieldWithoutMetalLayout {
+    ///     a: float3,
+    ///     b: NoMetalLayout,
+    /// }
+    /// ```
+    struct FieldWithoutMetalLayout;
+
+    /// Baseline, verifies `MslVectorMismatch` only fails because of the missing alignment.
+    /// ```
+    /// use metal_types::MetalLayout;
+    /// #[allow(non_camel_case_types)]
+    /// #[repr(C, align(16))]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct float3 {
+    ///     xyz: [f32; 3],
+    /// }
+    /// ```
+    struct MslVector;
+
+    /// Metal's `float3` is 16 bytes and 16 byte aligned, but this Rust mirror is 12 bytes and 4 byte
+    /// aligned.
+    /// ```compile_fail,E0080
+    /// use metal_types::MetalLayout;
+    /// #[allow(non_camel_case_types)]
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct float3 {
+    ///     xyz: [f32; 3],
+    /// }
+    /// ```
+    struct MslVectorMismatch;
+
+    /// Baseline, verifies `FieldLayoutMismatch` only fails because of the missing alignment.
+    /// ```
+    /// use metal_types::MetalLayout;
+    /// #[allow(non_camel_case_types)]
+    /// #[repr(C, align(16))]
+    /// #[derive(Copy, Clone)]
+    /// struct float3([f32; 3]);
+    /// unsafe impl MetalLayout for float3 {
+    ///     const METAL_SIZE: usize = 16;
+    ///     const METAL_ALIGN: usize = 16;
+    /// }
+    ///
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct Light {
+    ///     position: float3,
+    ///     intensity: f32,
+    /// }
+    /// ```
+    struct FieldLayout;
+
+    /// A field whose Metal layout (Metal's `float3`, 16 bytes and 16 byte aligned) differs from its
+    /// Rust layout (12 bytes and 4 byte aligned): `intensity` is at offset 16 in Metal, but 12 in
+    /// Rust.
+    /// ```compile_fail,E0080
+    /// use metal_types::MetalLayout;
+    /// #[allow(non_camel_case_types)]
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone)]
+    /// struct float3([f32; 3]);
+    /// unsafe impl MetalLayout for float3 {
+    ///     const METAL_SIZE: usize = 16;
+    ///     const METAL_ALIGN: usize = 16;
+    /// }
+    ///
+    /// #[repr(C)]
+    /// #[derive(Copy, Clone, MetalLayout)]
+    /// struct Light {
+    ///     position: float3,
+    ///     intensity: f32,
+    /// }
+    /// ```
+    struct FieldLayoutMismatch;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{float2, float3, float3x3, float4, half3, packed_float3, MetalLayout, ModelSpace};
+    use std::mem::{align_of, size_of};
+
+    fn assert_layout<T: MetalLayout>(size: usize, align: usize) {
+        assert_eq!((T::METAL_SIZE, T::METAL_ALIGN), (size, align));
+        assert_eq!((size_of::<T>(), align_of::<T>()), (size, align));
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, MetalLayout)]
+    struct Light {
+        position: float3,
+        intensity: f32,
+        color: packed_float3,
+        shadow: bool,
+    }
+
+    #[repr(C, align(32))]
+    #[derive(Copy, Clone, MetalLayout)]
+    struct Aligned {
+        a: float2,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, MetalLayout)]
+    struct Nested {
+        light: Light,
+        lights: [Light; 2],
+        normal_matrix: float3x3,
+        index: u8,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, MetalLayout)]
+    struct Tuple(half3, f32);
+
+    #[repr(u8)]
+    #[allow(dead_code)]
+    #[derive(Copy, Clone, MetalLayout)]
+    enum Mode {
+        A,
+        B,
+    }
+
+    #[test]
+    fn test_metal_types() {
+        assert_layout::<f32>(4, 4);
+        assert_layout::<bool>(1, 1);
+        assert_layout::<float3>(16, 16);
+        assert_layout::<packed_float3>(12, 4);
+        assert_layout::<half3>(8, 8);
+        assert_layout::<float3x3>(48, 16);
+        assert_layout::<float4>(16, 16);
+        assert_layout::<[packed_float3; 3]>(36, 4);
+        assert_layout::<ModelSpace>(112, 16);
+    }
+
+    #[test]
+    fn test_derive() {
+        // float3 (16) + f32 (4) + packed_float3 (12) + bool (1), rounded up to 16 byte alignment.
+        assert_layout::<Light>(48, 16);
+        assert_layout::<Aligned>(32, 32);
+        assert_layout::<Nested>(48 * 3 + 48 + 16, 16);
+        assert_layout::<Tuple>(16, 8);
+        assert_layout::<Mode>(1, 1);
+    }
+}
@@ -0,0 +1,594 @@
+// Geometric primitives and intersection tests (ex. culling, picking, and fitting shadow frustums).
+//
+// Points and directions are `f32x4`s, only the XYZ components are used (W is ignored).
+use super::{cross, dot, f32x4_extras, f32x4x4, ProjectedSpace};
+use std::simd::{f32x4, SimdFloat};
+
+#[inline]
+fn xyz(v: f32x4) -> f32x4 {
+    f32x4::from_array([v[0], v[1], v[2], 0.])
+}
+
+// Axis-aligned bounding box.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: f32x4,
+    pub max: f32x4,
+}
+
+impl Aabb {
+    // Contains nothing, the identity for `union()` and `add_point()`. W is 0, same as `new()`.
+    pub const EMPTY: Self = Self {
+        min: f32x4::from_array([f32::INFINITY, f32::INFINITY, f32::INFINITY, 0.]),
+        max: f32x4::from_array([f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY, 0.]),
+    };
+
+    #[inline]
+    pub fn new(min: f32x4, max: f32x4) -> Self {
+        Self {
+            min: xyz(min),
+            max: xyz(max),
+        }
+    }
+
+    #[inline]
+    pub fn from_center_size(center: f32x4, size: f32x4) -> Self {
+        let half_size = xyz(size) * f32x4::splat(0.5);
+        Self::new(center - half_size, center + half_size)
+    }
+
+    #[inline]
+    pub fn from_points(points: impl IntoIterator<Item = f32x4>) -> Self {
+        points
+            .into_iter()
+            .fold(Self::EMPTY, |aabb, p| aabb.add_point(p))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|i| self.min[i] > self.max[i])
+    }
+
+    #[inline]
+    pub fn center(&self) -> f32x4 {
+        (self.min + self.max) * f32x4::splat(0.5)
+    }
+
+    #[inline]
+    pub fn size(&self) -> f32x4 {
+        self.max - self.min
+    }
+
+    #[inline]
+    pub fn add_point(&self, p: f32x4) -> Self {
+        Self::new(self.min.simd_min(p), self.max.simd_max(p))
+    }
+
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.min.simd_min(other.min), self.max.simd_max(other.max))
+    }
+
+    #[inline]
+    pub fn contains_point(&self, p: f32x4) -> bool {
+        (0..3).all(|i| self.min[i] <= p[i] && p[i] <= self.max[i])
+    }
+
+    #[inline]
+    pub fn intersects_aabb(&self, other: &Self) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    // Bounding box of this box transformed by an affine transform `m`.
+    // See "Transforming Axis-Aligned Bounding Boxes" by Jim Arvo, Graphics Gems (1990).
+    pub fn transform(&self, m: f32x4x4) -> Self {
+        // Infinite extents would otherwise produce NaNs (0 * ∞) or a non-empty box.
+        if self.is_empty() {
+            return Self::EMPTY;
+        }
+        let translation = xyz(m.columns[3].into());
+        let (mut min, mut max) = (translation, translation);
+        for i in 0..3 {
+            let axis = xyz(m.columns[i].into());
+            let a = axis * f32x4::splat(self.min[i]);
+            let b = axis * f32x4::splat(self.max[i]);
+            min += a.simd_min(b);
+            max += a.simd_max(b);
+        }
+        Self { min, max }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sphere {
+    pub center: f32x4,
+    pub radius: f32,
+}
+
+impl Sphere {
+    #[inline]
+    pub fn new(center: f32x4, radius: f32) -> Self {
+        Self {
+            center: xyz(center),
+            radius,
+        }
+    }
+
+    // Smallest sphere containing the box.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.center(), aabb.size().length() * 0.5)
+    }
+
+    #[inline]
+    pub fn contains_point(&self, p: f32x4) -> bool {
+        (xyz(p) - self.center).length() <= self.radius
+    }
+}
+
+// Plane containing all points `p` where `dot(normal, p) + d == 0`. Points in front of the plane
+// (in the direction of the normal) have a positive signed distance.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: f32x4,
+    pub d: f32,
+}
+
+impl Plane {
+    #[inline]
+    pub fn new(normal: f32x4, d: f32) -> Self {
+        Self {
+            normal: xyz(normal),
+            d,
+        }
+    }
+
+    #[inline]
+    pub fn from_point_normal(point: f32x4, normal: f32x4) -> Self {
+        let normal = xyz(normal).normalize();
+        Self::new(normal, -dot(normal, xyz(point)))
+    }
+
+    // Plane with a unit length normal, so `signed_distance()` is in world units. Degenerate planes
+    // (zero length normal, ex. the far plane of an infinite perspective projection) are unchanged.
+    #[inline]
+    pub fn normalize(&self) -> Self {
+        let length = self.normal.length();
+        if length > 0. {
+            Self::new(self.normal / f32x4::splat(length), self.d / length)
+        } else {
+            *self
+        }
+    }
+
+    #[inline]
+    pub fn signed_distance(&self, p: f32x4) -> f32 {
+        dot(self.normal, xyz(p)) + self.d
+    }
+}
+
+// View frustum as 6 inward facing planes: left, right, bottom, top, near, and far.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    // Extracts the planes from a projection matrix (ex. `m_world_to_projection` results in world
+    // space planes), assuming Metal's NDC coordinate space (Z range of [0, 1]).
+    // See "Fast Extraction of Viewing Frustum Planes from the World-View-Projection Matrix" by Gil
+    // Gribb and Klaus Hartmann.
+    pub fn from_projection(m: f32x4x4) -> Self {
+        let (r0, r1, r2, r3) = (m.row::<0>(), m.row::<1>(), m.row::<2>(), m.row::<3>());
+        let plane = |p: f32x4| Plane::new(p, p[3]).normalize();
+        Self {
+            planes: [
+                plane(r3 + r0),
+                plane(r3 - r0),
+                plane(r3 + r1),
+                plane(r3 - r1),
+                plane(r2),
+                plane(r3 - r2),
+            ],
+        }
+    }
+
+    #[inline]
+    pub fn contains_point(&self, p: f32x4) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(p) >= 0.)
+    }
+
+    // Conservative test, may return true for spheres outside but near the corners of the frustum.
+    #[inline]
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+    }
+
+    // Conservative test, may return true for boxes outside but near the corners of the frustum.
+    #[inline]
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // Corner of the box furthest in the direction of the plane's normal.
+            let mut p = aabb.min;
+            for i in 0..3 {
+                if plane.normal[i] >= 0. {
+                    p[i] = aabb.max[i];
+                }
+            }
+            plane.signed_distance(p) >= 0.
+        })
+    }
+}
+
+impl From<&ProjectedSpace> for Frustum {
+    #[inline]
+    fn from(projected_space: &ProjectedSpace) -> Self {
+        Self::from_projection(projected_space.m_world_to_projection)
+    }
+}
+
+// Same conventions as Metal ray tracing's `ray` (only intersections between `min_distance` and
+// `max_distance` along the ray are reported).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: f32x4,
+    pub direction: f32x4,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+// Distance along the ray and barycentric coordinates (weights of the 2nd and 3rd triangle vertices)
+// of a ray-triangle intersection.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TriangleIntersection {
+    pub distance: f32,
+    pub barycentric_coord: [f32; 2],
+}
+
+impl Ray {
+    #[inline]
+    pub fn new(origin: f32x4, direction: f32x4) -> Self {
+        Self {
+            origin: xyz(origin),
+            direction: xyz(direction),
+            min_distance: 0.,
+            max_distance: f32::INFINITY,
+        }
+    }
+
+    #[inline]
+    pub fn at(&self, distance: f32) -> f32x4 {
+        self.origin + self.direction * f32x4::splat(distance)
+    }
+
+    // Distance to the nearest intersection with either side of the triangle.
+    // See "Fast, Minimum Storage Ray/Triangle Intersection" by Tomas Möller and Ben Trumbore.
+    pub fn intersect_triangle(&self, [v0, v1, v2]: [f32x4; 3]) -> Option<TriangleIntersection> {
+        const EPSILON: f32 = 1e-8;
+        let edge1 = xyz(v1 - v0);
+        let edge2 = xyz(v2 - v0);
+        let p = cross(self.direction, edge2);
+        let det = dot(edge1, p);
+        if det.abs() < EPSILON {
+            // Ray is parallel to the triangle
+            return None;
+        }
+        let inv_det = 1. / det;
+        let s = self.origin - xyz(v0);
+        let u = dot(s, p) * inv_det;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = cross(s, edge1);
+        let v = dot(self.direction, q) * inv_det;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+        let distance = dot(edge2, q) * inv_det;
+        (self.min_distance..=self.max_distance)
+            .contains(&distance)
+            .then_some(TriangleIntersection {
+                distance,
+                barycentric_coord: [u, v],
+            })
+    }
+
+    // Distance to the nearest intersection with the box (`min_distance` if the ray starts inside).
+    // See "An Efficient and Robust Ray-Box Intersection Algorithm" by Amy Williams et al.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let (mut near, mut far) = (self.min_distance, self.max_distance);
+        for i in 0..3 {
+            if self.direction[i] == 0. {
+                // Parallel to the slab, avoid 0 * inf (NaN) when the origin is on the slab boundary.
+                if self.origin[i] < aabb.min[i] || self.origin[i] > aabb.max[i] {
+                    return None;
+                }
+                continue;
+            }
+            let inv_direction = 1. / self.direction[i];
+            let t0 = (aabb.min[i] - self.origin[i]) * inv_direction;
+            let t1 = (aabb.max[i] - self.origin[i]) * inv_direction;
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+        (near <= far).then_some(near)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::assert_approx_eq_f32x4;
+    use std::f32::consts::PI;
+
+    const TOLERANCE: f32 = 1e-5;
+
+    fn v(x: f32, y: f32, z: f32) -> f32x4 {
+        f32x4::from_array([x, y, z, 0.])
+    }
+
+    mod test_aabb {
+        use super::*;
+
+        #[test]
+        fn test_from_points() {
+            let aabb = Aabb::from_points([v(1., -2., 3.), v(-1., 2., 0.), v(0., 0., 5.)]);
+            assert_eq!(aabb, Aabb::new(v(-1., -2., 0.), v(1., 2., 5.)));
+            assert_eq!(aabb.center(), v(0., 0., 2.5));
+            assert_eq!(aabb.size(), v(2., 4., 5.));
+            assert_eq!(Aabb::from_center_size(aabb.center(), aabb.size()), aabb);
+
+            assert!(Aabb::from_points([]).is_empty());
+            assert!(!aabb.is_empty());
+        }
+
+        #[test]
+        fn test_empty() {
+            assert!(Aabb::EMPTY.is_empty());
+            assert_eq!(Aabb::from_points([]), Aabb::EMPTY);
+            assert_eq!(Aabb::EMPTY.min[3], 0.);
+            assert_eq!(Aabb::EMPTY.max[3], 0.);
+            assert_eq!(
+                Aabb::EMPTY.add_point(v(1., 2., 3.)),
+                Aabb::new(v(1., 2., 3.), v(1., 2., 3.))
+            );
+
+            let m = f32x4x4::translate(1., 2., 3.) * f32x4x4::rotate(0.3, -0.7, 1.1);
+            assert_eq!(Aabb::EMPTY.transform(m), Aabb::EMPTY);
+            assert_eq!(
+                Aabb::EMPTY.transform(f32x4x4::y_rotate(PI / 2.)),
+                Aabb::EMPTY
+            );
+        }
+
+        #[test]
+        fn test_union_and_intersects() {
+            let a = Aabb::new(v(0., 0., 0.), v(1., 1., 1.));
+            let b = Aabb::new(v(0.5, 0.5, 0.5), v(2., 2., 2.));
+            let c = Aabb::new(v(1.5, 0., 0.), v(2., 1., 1.));
+            assert_eq!(a.union(&b), Aabb::new(v(0., 0., 0.), v(2., 2., 2.)));
+            assert_eq!(a.union(&Aabb::EMPTY), a);
+            assert!(a.intersects_aabb(&b));
+            assert!(!a.intersects_aabb(&c));
+            assert!(a.contains_point(v(0.5, 1., 0.)));
+            assert!(!a.contains_point(v(0.5, 1.1, 0.)));
+        }
+
+        #[test]
+        fn test_transform() {
+            let aabb = Aabb::new(v(-1., -2., -3.), v(1., 2., 3.));
+            assert_eq!(
+                aabb.transform(f32x4x4::scale_translate(2., 2., 2., 1., 2., 3.)),
+                Aabb::new(v(-1., -2., -3.), v(3., 6., 9.))
+            );
+
+            // Rotating 90 degrees around Y swaps the X and Z extents.
+            let rotated = aabb.transform(f32x4x4::y_rotate(PI / 2.));
+            assert_approx_eq_f32x4(rotated.min, v(-3., -2., -1.), TOLERANCE);
+            assert_approx_eq_f32x4(rotated.max, v(3., 2., 1.), TOLERANCE);
+
+            // Same as transforming every corner.
+            let m = f32x4x4::translate(1., 2., 3.) * f32x4x4::rotate(0.3, -0.7, 1.1);
+            let corners = (0..8).map(|i| {
+                let corner = v(
+                    if i & 1 == 0 { -1. } else { 1. },
+                    if i & 2 == 0 { -2. } else { 2. },
+                    if i & 4 == 0 { -3. } else { 3. },
+                );
+                m * f32x4::from_array([corner[0], corner[1], corner[2], 1.])
+            });
+            let expected = Aabb::from_points(corners);
+            let actual = aabb.transform(m);
+            assert_approx_eq_f32x4(actual.min, expected.min, TOLERANCE);
+            assert_approx_eq_f32x4(actual.max, expected.max, TOLERANCE);
+        }
+    }
+
+    mod test_sphere {
+        use super::*;
+
+        #[test]
+        fn test_from_aabb() {
+            let sphere = Sphere::from_aabb(&Aabb::new(v(-1., -1., -1.), v(1., 1., 1.)));
+            assert_eq!(sphere.center, v(0., 0., 0.));
+            assert!((sphere.radius - 3_f32.sqrt()).abs() < 1e-6);
+            assert!(sphere.contains_point(v(1., 1., 1.) * f32x4::splat(0.99)));
+            assert!(!sphere.contains_point(v(1., 1., 1.1)));
+        }
+    }
+
+    mod test_plane {
+        use super::*;
+
+        #[test]
+        fn test_signed_distance() {
+            let plane = Plane::from_point_normal(v(0., 2., 0.), v(0., 3., 0.));
+            assert_eq!(plane, Plane::new(v(0., 1., 0.), -2.));
+            assert_eq!(plane.signed_distance(v(5., 5., 5.)), 3.);
+            assert_eq!(plane.signed_distance(v(5., 0., 5.)), -2.);
+            assert_eq!(Plane::new(v(0., 2., 0.), -4.).normalize(), plane);
+        }
+    }
+
+    mod test_frustum {
+        use super::*;
+
+        const NEAR: f32 = 1.;
+        const FAR: f32 = 10.;
+
+        fn frustum() -> Frustum {
+            // Camera at (0, 0, -5) looking towards +Z.
+            let m_world_to_camera = f32x4x4::translate(0., 0., 5.);
+            let m_camera_to_projection = f32x4x4::perspective(PI / 2., 1., NEAR, FAR);
+            Frustum::from(&ProjectedSpace {
+                m_world_to_projection: m_camera_to_projection * m_world_to_camera,
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn test_planes() {
+            let [left, right, bottom, top, near, far] = frustum().planes;
+            let s = 0.5_f32.sqrt();
+            assert_approx_eq_f32x4(left.normal, v(s, 0., s), TOLERANCE);
+            assert_approx_eq_f32x4(right.normal, v(-s, 0., s), TOLERANCE);
+            assert_approx_eq_f32x4(bottom.normal, v(0., s, s), TOLERANCE);
+            assert_approx_eq_f32x4(top.normal, v(0., -s, s), TOLERANCE);
+            assert_approx_eq_f32x4(near.normal, v(0., 0., 1.), TOLERANCE);
+            assert_approx_eq_f32x4(far.normal, v(0., 0., -1.), TOLERANCE);
+            assert!((near.signed_distance(v(0., 0., -4.))).abs() < 1e-5);
+            assert!((far.signed_distance(v(0., 0., 5.))).abs() < 1e-5);
+        }
+
+        #[test]
+        fn test_contains_point() {
+            let f = frustum();
+            assert!(f.contains_point(v(0., 0., 0.)));
+            assert!(f.contains_point(v(4.9, 0., 0.)));
+            assert!(!f.contains_point(v(5.1, 0., 0.)));
+            assert!(!f.contains_point(v(0., 0., -4.5)));
+            assert!(!f.contains_point(v(0., 0., 5.5)));
+        }
+
+        #[test]
+        fn test_intersects_sphere() {
+            let f = frustum();
+            assert!(f.intersects_sphere(&Sphere::new(v(0., 0., 0.), 1.)));
+            assert!(f.intersects_sphere(&Sphere::new(v(0., 0., -5.), 1.5)));
+            assert!(!f.intersects_sphere(&Sphere::new(v(0., 0., -5.), 0.5)));
+            assert!(!f.intersects_sphere(&Sphere::new(v(0., 0., 7.), 1.)));
+            assert!(!f.intersects_sphere(&Sphere::new(v(-8., 0., 0.), 1.)));
+        }
+
+        #[test]
+        fn test_intersects_aabb() {
+            let f = frustum();
+            let unit = |c: f32x4| Aabb::from_center_size(c, v(1., 1., 1.));
+            assert!(f.intersects_aabb(&unit(v(0., 0., 0.))));
+            assert!(f.intersects_aabb(&unit(v(0., 0., 5.4))));
+            assert!(f.intersects_aabb(&unit(v(5.4, 0., 0.))));
+            assert!(!f.intersects_aabb(&unit(v(0., 0., 5.6))));
+            assert!(!f.intersects_aabb(&unit(v(0., 0., -5.))));
+            assert!(!f.intersects_aabb(&unit(v(0., 7., 0.))));
+            // Contains the whole frustum
+            assert!(f.intersects_aabb(&Aabb::from_center_size(v(0., 0., 0.), v(100., 100., 100.))));
+        }
+
+        #[test]
+        fn test_infinite_far_plane() {
+            let f = Frustum::from_projection(f32x4x4::perspective_infinite(PI / 2., 1., NEAR));
+            assert!(f.contains_point(v(0., 0., 1e6)));
+            assert!(!f.contains_point(v(0., 0., 0.5)));
+        }
+    }
+
+    mod test_ray {
+        use super::*;
+
+        const TRIANGLE: [f32x4; 3] = [
+            f32x4::from_array([0., 0., 0., 0.]),
+            f32x4::from_array([1., 0., 0., 0.]),
+            f32x4::from_array([0., 1., 0., 0.]),
+        ];
+
+        #[test]
+        fn test_intersect_triangle() {
+            let ray = Ray::new(v(0.25, 0.5, -2.), v(0., 0., 1.));
+            let hit = ray.intersect_triangle(TRIANGLE).unwrap();
+            assert!((hit.distance - 2.).abs() < 1e-6);
+            assert_eq!(hit.barycentric_coord, [0.25, 0.5]);
+            assert_approx_eq_f32x4(ray.at(hit.distance), v(0.25, 0.5, 0.), TOLERANCE);
+
+            // Back side
+            let ray = Ray::new(v(0.25, 0.25, 2.), v(0., 0., -1.));
+            assert!((ray.intersect_triangle(TRIANGLE).unwrap().distance - 2.).abs() < 1e-6);
+
+            // Misses
+            assert_eq!(
+                Ray::new(v(0.75, 0.75, -2.), v(0., 0., 1.)).intersect_triangle(TRIANGLE),
+                None
+            );
+            assert_eq!(
+                Ray::new(v(0.25, 0.25, -2.), v(0., 0., -1.)).intersect_triangle(TRIANGLE),
+                None
+            );
+            assert_eq!(
+                Ray::new(v(0.25, 0.25, -2.), v(1., 0., 0.)).intersect_triangle(TRIANGLE),
+                None
+            );
+            assert_eq!(
+                Ray {
+                    max_distance: 1.,
+                    ..Ray::new(v(0.25, 0.5, -2.), v(0., 0., 1.))
+                }
+                .intersect_triangle(TRIANGLE),
+                None
+            );
+        }
+
+        #[test]
+        fn test_intersect_aabb() {
+            let aabb = Aabb::new(v(-1., -1., -1.), v(1., 1., 1.));
+            assert_eq!(
+                Ray::new(v(0., 0., -5.), v(0., 0., 1.)).intersect_aabb(&aabb),
+                Some(4.)
+            );
+            assert_eq!(
+                Ray::new(v(-5., -5., -5.), v(1., 1., 1.)).intersect_aabb(&aabb),
+                Some(4.)
+            );
+            // Inside
+            assert_eq!(
+                Ray::new(v(0., 0., 0.), v(0., 1., 0.)).intersect_aabb(&aabb),
+                Some(0.)
+            );
+            // Parallel to, and on the boundary of, a slab
+            assert_eq!(
+                Ray::new(v(1., 0., -5.), v(0., 0., 1.)).intersect_aabb(&aabb),
+                Some(4.)
+            );
+
+            // Misses
+            assert_eq!(
+                Ray::new(v(0., 0., -5.), v(0., 0., -1.)).intersect_aabb(&aabb),
+                None
+            );
+            assert_eq!(
+                Ray::new(v(0., 2., -5.), v(0., 0., 1.)).intersect_aabb(&aabb),
+                None
+            );
+            assert_eq!(
+                Ray {
+                    max_distance: 3.,
+                    ..Ray::new(v(0., 0., -5.), v(0., 0., 1.))
+                }
+                .intersect_aabb(&aabb),
+                None
+            );
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use super::{cross, dot, f32x4_extras, f32x4x4};
+use std::{
+    ops::{Mul, Neg},
+    simd::f32x4,
+};
+
+// Unit quaternion representing a rotation (XYZ is the vector part, W is the scalar part).
+//
+// Rotations use the same direction as `f32x4x4::x_rotate()`, `y_rotate()`, `z_rotate()`, and
+// `axis_angle_rotate()`.
+// ex. `f32x4x4::from(Quat::from_axis_angle(axis, a)) == f32x4x4::axis_angle_rotate(axis, a)`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Quat(pub f32x4);
+
+#[inline]
+fn xyz(v: f32x4) -> f32x4 {
+    f32x4::from_array([v[0], v[1], v[2], 0.])
+}
+
+impl Quat {
+    pub const IDENTITY: Self = Self(f32x4::from_array([0., 0., 0., 1.]));
+
+    #[inline]
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self(f32x4::from_array([x, y, z, w]))
+    }
+
+    // Rotation around an arbitrary axis (XYZ components, W is ignored).
+    #[inline]
+    pub fn from_axis_angle(axis: f32x4, angle: f32) -> Self {
+        let (s, c) = (-angle / 2.).sin_cos();
+        let mut q = xyz(axis).normalize() * f32x4::splat(s);
+        q[3] = c;
+        Self(q)
+    }
+
+    // Same rotation as `f32x4x4::rotate(xrot, yrot, zrot)`.
+    #[inline]
+    pub fn from_euler(xrot: f32, yrot: f32, zrot: f32) -> Self {
+        Self::from_axis_angle(f32x4::from_array([1., 0., 0., 0.]), xrot)
+            * Self::from_axis_angle(f32x4::from_array([0., 1., 0., 0.]), yrot)
+            * Self::from_axis_angle(f32x4::from_array([0., 0., 1., 0.]), zrot)
+    }
+
+    // Rotation of the upper-left 3x3 of `m`, which must be orthonormal (no scale or shear).
+    // Based on http://www.euclideanspace.com/maths/geometry/rotations/conversions/matrixToQuaternion/
+    pub fn from_rotation_matrix(m: f32x4x4) -> Self {
+        let c = m.columns;
+        let (m00, m01, m02) = (c[0][0], c[1][0], c[2][0]);
+        let (m10, m11, m12) = (c[0][1], c[1][1], c[2][1]);
+        let (m20, m21, m22) = (c[0][2], c[1][2], c[2][2]);
+        let trace = m00 + m11 + m22;
+        let q = if trace > 0. {
+            let s = (trace + 1.).sqrt() * 2.;
+            Self::new((m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s, 0.25 * s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1. + m00 - m11 - m22).sqrt() * 2.;
+            Self::new(0.25 * s, (m01 + m10) / s, (m02 + m20) / s, (m21 - m12) / s)
+        } else if m11 > m22 {
+            let s = (1. + m11 - m00 - m22).sqrt() * 2.;
+            Self::new((m01 + m10) / s, 0.25 * s, (m12 + m21) / s, (m02 - m20) / s)
+        } else {
+            let s = (1. + m22 - m00 - m11).sqrt() * 2.;
+            Self::new((m02 + m20) / s, (m12 + m21) / s, 0.25 * s, (m10 - m01) / s)
+        };
+        q.normalize()
+    }
+
+    // Rotation that orients +Z towards `forward` and +Y towards `up` (XYZ components, W is ignored).
+    // This is the inverse of the rotation in `f32x4x4::look_at()`.
+    // ex. `Quat::look_rotation(target - eye, up) * f32x4::from_array([0., 0., 1., 0.])` points
+    //     towards `target`.
+    #[inline]
+    pub fn look_rotation(forward: f32x4, up: f32x4) -> Self {
+        let z = xyz(forward).normalize();
+        let x = cross(up, z).normalize();
+        let y = cross(z, x);
+        Self::from_rotation_matrix(f32x4x4 {
+            columns: [x.to_array(), y.to_array(), z.to_array(), [0., 0., 0., 1.]],
+        })
+    }
+
+    #[inline]
+    pub fn conjugate(&self) -> Self {
+        Self(self.0 * f32x4::from_array([-1., -1., -1., 1.]))
+    }
+
+    // Same as `conjugate()`, assumes this is a unit quaternion.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        self.conjugate()
+    }
+
+    #[inline]
+    pub fn normalize(&self) -> Self {
+        Self(self.0.normalize())
+    }
+
+    #[inline]
+    pub fn dot(&self, rhs: Self) -> f32 {
+        dot(self.0, rhs.0)
+    }
+
+    // Normalized linear interpolation, along the shortest path. Faster than `slerp()`, but the
+    // angular velocity is not constant.
+    #[inline]
+    pub fn nlerp(&self, to: Self, t: f32) -> Self {
+        let to = if self.dot(to) < 0. { -to } else { to };
+        Self(self.0 + (to.0 - self.0) * f32x4::splat(t)).normalize()
+    }
+
+    // Spherical linear interpolation, along the shortest path.
+    pub fn slerp(&self, to: Self, t: f32) -> Self {
+        let mut cos_theta = self.dot(to);
+        let to = if cos_theta < 0. {
+            cos_theta = -cos_theta;
+            -to
+        } else {
+            to
+        };
+        // Nearly identical rotations, avoid dividing by sin(theta) ~= 0.
+        if cos_theta > 0.9995 {
+            return self.nlerp(to, t);
+        }
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((1. - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        Self(self.0 * f32x4::splat(a) + to.0 * f32x4::splat(b))
+    }
+}
+
+impl Neg for Quat {
+    type Output = Quat;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+// Combines rotations, `lhs * rhs` rotates by `rhs` then `lhs` (same as `f32x4x4`).
+impl Mul<Quat> for Quat {
+    type Output = Quat;
+
+    #[inline]
+    fn mul(self, rhs: Quat) -> Self::Output {
+        let (l, r) = (self.0, rhs.0);
+        let mut q = f32x4::splat(l[3]) * xyz(r) + f32x4::splat(r[3]) * xyz(l) + cross(l, r);
+        q[3] = l[3] * r[3] - dot(xyz(l), xyz(r));
+        Self(q)
+    }
+}
+
+// Rotates the XYZ components, W is unchanged.
+impl Mul<f32x4> for Quat {
+    type Output = f32x4;
+
+    #[inline]
+    fn mul(self, rhs: f32x4) -> Self::Output {
+        let u = xyz(self.0);
+        let v = xyz(rhs);
+        let uv = cross(u, v);
+        let mut out = v + f32x4::splat(2. * self.0[3]) * uv + f32x4::splat(2.) * cross(u, uv);
+        out[3] = rhs[3];
+        out
+    }
+}
+
+impl From<Quat> for f32x4x4 {
+    #[inline]
+    fn from(Quat(q): Quat) -> Self {
+        let &[x, y, z, w] = q.as_array();
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+        f32x4x4::new(
+            [1. - 2. * (yy + zz), 2. * (xy - wz), 2. * (xz + wy), 0.],
+            [2. * (xy + wz), 1. - 2. * (xx + zz), 2. * (yz - wx), 0.],
+            [2. * (xz - wy), 2. * (yz + wx), 1. - 2. * (xx + yy), 0.],
+            [0., 0., 0., 1.],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{assert_approx_eq_f32x4, assert_approx_eq_f32x4x4};
+    use std::f32::consts::PI;
+
+    const TOLERANCE: f32 = 1e-6;
+
+    // `q` and `-q` represent the same rotation.
+    fn assert_same_rotation(actual: Quat, expected: Quat) {
+        if actual.dot(expected) < 0. {
+            assert_approx_eq_f32x4(-actual.0, expected.0, TOLERANCE);
+        } else {
+            assert_approx_eq_f32x4(actual.0, expected.0, TOLERANCE);
+        }
+    }
+
+    fn v(x: f32, y: f32, z: f32) -> f32x4 {
+        f32x4::from_array([x, y, z, 0.])
+    }
+
+    const AXES: [[f32; 3]; 5] = [
+        [1., 0., 0.],
+        [0., 1., 0.],
+        [0., 0., 1.],
+        [1., 2., 3.],
+        [-3., 0.5, -1.],
+    ];
+    const ANGLES: [f32; 6] = [0., 0.5, -1., PI / 2., 3., -PI];
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(f32x4x4::from(Quat::IDENTITY), f32x4x4::identity());
+        assert_eq!(Quat::IDENTITY * v(1., 2., 3.), v(1., 2., 3.));
+    }
+
+    #[test]
+    fn test_from_axis_angle() {
+        for [x, y, z] in AXES {
+            for angle in ANGLES {
+                let q = Quat::from_axis_angle(v(x, y, z), angle);
+                assert_approx_eq_f32x4x4(
+                    q.into(),
+                    f32x4x4::axis_angle_rotate(v(x, y, z), angle),
+                    TOLERANCE,
+                );
+            }
+        }
+
+        let sqrt_half = 0.5_f32.sqrt();
+        assert_same_rotation(
+            Quat::from_axis_angle(v(0., 0., 2.), PI / 2.),
+            Quat::new(0., 0., -sqrt_half, sqrt_half),
+        );
+    }
+
+    #[test]
+    fn test_from_euler() {
+        for (xrot, yrot, zrot) in [(0., 0., 0.), (0.5, 0., 0.), (0., -1., 2.), (1., 2., 3.)] {
+            assert_approx_eq_f32x4x4(
+                Quat::from_euler(xrot, yrot, zrot).into(),
+                f32x4x4::rotate(xrot, yrot, zrot),
+                TOLERANCE,
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_rotation_matrix() {
+        // Exercise every branch (positive trace, and largest X, Y, and Z diagonal).
+        for [x, y, z] in AXES {
+            for angle in ANGLES {
+                let q = Quat::from_axis_angle(v(x, y, z), angle);
+                assert_same_rotation(Quat::from_rotation_matrix(q.into()), q);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = Quat::from_euler(0.3, -1.2, 2.);
+        let b = Quat::from_axis_angle(v(1., 2., 3.), 0.7);
+        assert_approx_eq_f32x4x4(
+            (a * b).into(),
+            f32x4x4::from(a) * f32x4x4::from(b),
+            TOLERANCE,
+        );
+        assert_same_rotation(a * a.inverse(), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn test_mul_with_f32x4() {
+        let q = Quat::from_euler(0.3, -1.2, 2.);
+        for p in [[1., 0., 0., 1.], [0., 1., 0., 0.], [-4., 5., 6., 1.]] {
+            let p = f32x4::from_array(p);
+            assert_approx_eq_f32x4(q * p, f32x4x4::from(q) * p, TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_look_rotation() {
+        for forward in [v(0., 0., 1.), v(1., 0., 0.), v(0., 0., -1.), v(1., -2., 3.)] {
+            let q = Quat::look_rotation(forward, v(0., 1., 0.));
+            assert_approx_eq_f32x4(q * v(0., 0., 1.), forward.normalize(), TOLERANCE);
+            // Up stays in the plane of `forward` and `up`.
+            let up = q * v(0., 1., 0.);
+            assert!(up[1] > 0.);
+            assert!(dot(up, forward).abs() < TOLERANCE);
+
+            // Inverse of look_at's rotation.
+            let eye = v(0., 0., 0.);
+            let look_at = f32x4x4::look_at(eye, forward, v(0., 1., 0.));
+            assert_approx_eq_f32x4x4(q.inverse().into(), look_at, TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_nlerp() {
+        let a = Quat::from_axis_angle(v(0., 1., 0.), 0.);
+        let b = Quat::from_axis_angle(v(0., 1., 0.), 1.);
+        assert_same_rotation(a.nlerp(b, 0.), a);
+        assert_same_rotation(a.nlerp(b, 1.), b);
+        assert_same_rotation(a.nlerp(b, 0.5), Quat::from_axis_angle(v(0., 1., 0.), 0.5));
+        // Shortest path
+        assert_same_rotation(a.nlerp(-b, 0.5), Quat::from_axis_angle(v(0., 1., 0.), 0.5));
+    }
+
+    #[test]
+    fn test_slerp() {
+        let axis = v(1., 2., 3.);
+        let a = Quat::from_axis_angle(axis, 0.2);
+        let b = Quat::from_axis_angle(axis, 2.2);
+        for t in [0., 0.25, 0.5, 0.75, 1.] {
+            let expected = Quat::from_axis_angle(axis, 0.2 + 2. * t);
+            assert_same_rotation(a.slerp(b, t), expected);
+            // Shortest path
+            assert_same_rotation(a.slerp(-b, t), expected);
+        }
+        // Nearly identical rotations
+        let c = Quat::from_axis_angle(axis, 0.2001);
+        assert_same_rotation(a.slerp(c, 0.5), Quat::from_axis_angle(axis, 0.20005));
+    }
+}
@@ -8,44 +8,188 @@
 
 // Spec: 2.2 Vector Data Types / Table 2.3. Size and alignment of vector data types
 
-struct alignas(8) float2 {
-    float xy[2];
+struct alignas(2) bool2 {
+    bool xy[2];
 };
 
-struct alignas(16) float4 {
-    float xyzw[4];
+struct alignas(4) bool3 {
+    bool xyz[3];
 };
 
-struct alignas(4) half2 {
+struct alignas(4) bool4 {
+    bool xyzw[4];
+};
+
+struct alignas(2) char2 {
+    signed char xy[2];
+};
+
+struct alignas(4) char3 {
+    signed char xyz[3];
+};
+
+struct alignas(4) char4 {
+    signed char xyzw[4];
+};
+
+struct alignas(2) uchar2 {
+    unsigned char xy[2];
+};
+
+struct alignas(4) uchar3 {
+    unsigned char xyz[3];
+};
+
+struct alignas(4) uchar4 {
+    unsigned char xyzw[4];
+};
+
+struct alignas(4) short2 {
+    short xy[2];
+};
+
+struct alignas(8) short3 {
+    short xyz[3];
+};
+
+struct alignas(8) short4 {
+    short xyzw[4];
+};
+
+struct alignas(4) ushort2 {
     unsigned short xy[2];
 };
 
-struct alignas(8) half3 {
+struct alignas(8) ushort3 {
     unsigned short xyz[3];
 };
 
-struct alignas(8) half4 {
+struct alignas(8) ushort4 {
     unsigned short xyzw[4];
 };
 
-struct alignas(4) ushort2 {
+struct alignas(8) int2 {
+    int xy[2];
+};
+
+struct alignas(16) int3 {
+    int xyz[3];
+};
+
+struct alignas(16) int4 {
+    int xyzw[4];
+};
+
+struct alignas(8) uint2 {
+    unsigned int xy[2];
+};
+
+struct alignas(16) uint3 {
+    unsigned int xyz[3];
+};
+
+struct alignas(16) uint4 {
+    unsigned int xyzw[4];
+};
+
+struct alignas(4) half2 {
     unsigned short xy[2];
 };
 
-// Spec: 2.2.3 Packed Vector Types / Table 2.4. Size and alignment of packed vector data types
+struct alignas(8) half3 {
+    unsigned short xyz[3];
+};
 
-struct alignas(4) packed_float2 {
+struct alignas(8) half4 {
+    unsigned short xyzw[4];
+};
+
+struct alignas(8) float2 {
     float xy[2];
 };
 
-struct alignas(4) packed_float3 {
+struct alignas(16) float3 {
     float xyz[3];
 };
 
-struct alignas(4) packed_float4 {
+struct alignas(16) float4 {
     float xyzw[4];
 };
 
+// Spec: 2.2.3 Packed Vector Types / Table 2.4. Size and alignment of packed vector data types
+
+struct alignas(1) packed_char2 {
+    signed char xy[2];
+};
+
+struct alignas(1) packed_char3 {
+    signed char xyz[3];
+};
+
+struct alignas(1) packed_char4 {
+    signed char xyzw[4];
+};
+
+struct alignas(1) packed_uchar2 {
+    unsigned char xy[2];
+};
+
+struct alignas(1) packed_uchar3 {
+    unsigned char xyz[3];
+};
+
+struct alignas(1) packed_uchar4 {
+    unsigned char xyzw[4];
+};
+
+struct alignas(2) packed_short2 {
+    short xy[2];
+};
+
+struct alignas(2) packed_short3 {
+    short xyz[3];
+};
+
+struct alignas(2) packed_short4 {
+    short xyzw[4];
+};
+
+struct alignas(2) packed_ushort2 {
+    unsigned short xy[2];
+};
+
+struct alignas(2) packed_ushort3 {
+    unsigned short xyz[3];
+};
+
+struct alignas(2) packed_ushort4 {
+    unsigned short xyzw[4];
+};
+
+struct alignas(4) packed_int2 {
+    int xy[2];
+};
+
+struct alignas(4) packed_int3 {
+    int xyz[3];
+};
+
+struct alignas(4) packed_int4 {
+    int xyzw[4];
+};
+
+struct alignas(4) packed_uint2 {
+    unsigned int xy[2];
+};
+
+struct alignas(4) packed_uint3 {
+    unsigned int xyz[3];
+};
+
+struct alignas(4) packed_uint4 {
+    unsigned int xyzw[4];
+};
+
 struct alignas(2) packed_half2 {
     unsigned short xy[2];
 };
@@ -58,12 +202,84 @@ struct alignas(2) packed_half4 {
     unsigned short xyzw[4];
 };
 
+struct alignas(4) packed_float2 {
+    float xy[2];
+};
+
+struct alignas(4) packed_float3 {
+    float xyz[3];
+};
+
+struct alignas(4) packed_float4 {
+    float xyzw[4];
+};
+
 // Spec: 2.3 Matrix Data Types / Table 2.5. Size and alignment of matrix data types
 
+struct alignas(4) half2x2 {
+    unsigned short columns[2][2];
+};
+
+struct alignas(8) half2x3 {
+    unsigned short columns[2][4];
+};
+
+struct alignas(8) half2x4 {
+    unsigned short columns[2][4];
+};
+
+struct alignas(4) half3x2 {
+    unsigned short columns[3][2];
+};
+
+struct alignas(8) half3x3 {
+    unsigned short columns[3][4];
+};
+
+struct alignas(8) half3x4 {
+    unsigned short columns[3][4];
+};
+
+struct alignas(4) half4x2 {
+    unsigned short columns[4][2];
+};
+
+struct alignas(8) half4x3 {
+    unsigned short columns[4][4];
+};
+
+struct alignas(8) half4x4 {
+    unsigned short columns[4][4];
+};
+
+struct alignas(8) float2x2 {
+    float columns[2][2];
+};
+
+struct alignas(16) float2x3 {
+    float columns[2][4];
+};
+
+struct alignas(16) float2x4 {
+    float columns[2][4];
+};
+
+struct alignas(8) float3x2 {
+    float columns[3][2];
+};
+
 struct alignas(16) float3x3 {
     float columns[3][4];
 };
 
+struct alignas(16) float3x4 {
+    float columns[3][4];
+};
+
+struct alignas(8) float4x2 {
+    float columns[4][2];
+};
+
 struct alignas(16) float4x3 {
     float columns[4][4];
 };
@@ -72,8 +288,4 @@ struct alignas(16) float4x4 {
     float columns[4][4];
 };
 
-struct alignas(8) half3x3 {
-    unsigned short columns[3][4];
-};
-
 #endif //__METAL_VERSION__
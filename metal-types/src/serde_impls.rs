@@ -0,0 +1,488 @@
+// Serialize/Deserialize implementations (requires the `serde` feature).
+//
+// Types are serialized in a human-readable form:
+// - Vectors are arrays of components (ex. `float3` => `[x, y, z]`)
+// - Matrices are arrays of columns, without padding (ex. `float4x3` => `[[x, y, z]; 4]`)
+// - Halves are serialized as f32 (ex. `half2` => `[0.5, 1.0]`)
+use super::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ffi::{c_uchar, c_uint, c_ulong};
+
+#[inline(always)]
+fn half_to_f32(v: c_ushort) -> f32 {
+    f16::from_bits(v).to_f32()
+}
+
+#[inline(always)]
+fn f32_to_half(v: f32) -> c_ushort {
+    f16::from_f32(v).to_bits()
+}
+
+// Drops (serialize) or zero fills (deserialize) the trailing padding components of a matrix column.
+#[inline(always)]
+fn resize<T: Copy + Default, const FROM: usize, const TO: usize>(v: [T; FROM]) -> [T; TO] {
+    let mut out = [T::default(); TO];
+    let len = FROM.min(TO);
+    out[..len].copy_from_slice(&v[..len]);
+    out
+}
+
+macro_rules! impl_vector {
+    ($($ty:ident . $field:ident: [$elem:ty; $n:literal]),* $(,)?) => {$(
+        impl Serialize for $ty {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.$field.serialize(serializer)
+            }
+        }
+        impl<'de> Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(Self {
+                    $field: <[$elem; $n]>::deserialize(deserializer)?,
+                })
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_half_vector {
+    ($($ty:ident . $field:ident: $n:literal),* $(,)?) => {$(
+        impl Serialize for $ty {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.$field.map(half_to_f32).serialize(serializer)
+            }
+        }
+        impl<'de> Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(Self {
+                    $field: <[f32; $n]>::deserialize(deserializer)?.map(f32_to_half),
+                })
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_matrix {
+    ($($ty:ident: [[$rows:literal; $stored_rows:literal]; $cols:literal]),* $(,)?) => {$(
+        impl Serialize for $ty {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.columns
+                    .map(resize::<f32, $stored_rows, $rows>)
+                    .serialize(serializer)
+            }
+        }
+        impl<'de> Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(Self {
+                    columns: <[[f32; $rows]; $cols]>::deserialize(deserializer)?
+                        .map(resize::<f32, $rows, $stored_rows>),
+                })
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_half_matrix {
+    ($($ty:ident: [[$rows:literal; $stored_rows:literal]; $cols:literal]),* $(,)?) => {$(
+        impl Serialize for $ty {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.columns
+                    .map(|c| resize::<c_ushort, $stored_rows, $rows>(c).map(half_to_f32))
+                    .serialize(serializer)
+            }
+        }
+        impl<'de> Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(Self {
+                    columns: <[[f32; $rows]; $cols]>::deserialize(deserializer)?
+                        .map(|c| resize::<c_ushort, $rows, $stored_rows>(c.map(f32_to_half))),
+                })
+            }
+        }
+    )*};
+}
+
+// Structs are (de)serialized through a mirror struct deriving Serialize/Deserialize. Listing every
+// field (rather than using `..`) ensures a compile error if the generated struct changes.
+macro_rules! impl_struct {
+    ($($ty:ident { $($field:ident: $field_ty:ty),* $(,)? })*) => {
+        mod mirror {
+            use super::*;
+            $(
+                #[derive(Serialize, Deserialize)]
+                pub(super) struct $ty {
+                    $(pub(super) $field: $field_ty),*
+                }
+            )*
+        }
+        $(
+            impl Serialize for $ty {
+                #[inline]
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    mirror::$ty {
+                        $($field: self.$field),*
+                    }
+                    .serialize(serializer)
+                }
+            }
+            impl<'de> Deserialize<'de> for $ty {
+                #[inline]
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let mirror::$ty { $($field),* } = mirror::$ty::deserialize(deserializer)?;
+                    Ok(Self { $($field),* })
+                }
+            }
+        )*
+    };
+}
+
+impl_vector!(
+    bool2.xy: [bool; 2],
+    bool3.xyz: [bool; 3],
+    bool4.xyzw: [bool; 4],
+    char2.xy: [i8; 2],
+    char3.xyz: [i8; 3],
+    char4.xyzw: [i8; 4],
+    uchar2.xy: [u8; 2],
+    uchar3.xyz: [u8; 3],
+    uchar4.xyzw: [u8; 4],
+    short2.xy: [i16; 2],
+    short3.xyz: [i16; 3],
+    short4.xyzw: [i16; 4],
+    ushort2.xy: [u16; 2],
+    ushort3.xyz: [u16; 3],
+    ushort4.xyzw: [u16; 4],
+    int2.xy: [i32; 2],
+    int3.xyz: [i32; 3],
+    int4.xyzw: [i32; 4],
+    uint2.xy: [u32; 2],
+    uint3.xyz: [u32; 3],
+    uint4.xyzw: [u32; 4],
+    float2.xy: [f32; 2],
+    float3.xyz: [f32; 3],
+    float4.xyzw: [f32; 4],
+    packed_char2.xy: [i8; 2],
+    packed_char3.xyz: [i8; 3],
+    packed_char4.xyzw: [i8; 4],
+    packed_uchar2.xy: [u8; 2],
+    packed_uchar3.xyz: [u8; 3],
+    packed_uchar4.xyzw: [u8; 4],
+    packed_short2.xy: [i16; 2],
+    packed_short3.xyz: [i16; 3],
+    packed_short4.xyzw: [i16; 4],
+    packed_ushort2.xy: [u16; 2],
+    packed_ushort3.xyz: [u16; 3],
+    packed_ushort4.xyzw: [u16; 4],
+    packed_int2.xy: [i32; 2],
+    packed_int3.xyz: [i32; 3],
+    packed_int4.xyzw: [i32; 4],
+    packed_uint2.xy: [u32; 2],
+    packed_uint3.xyz: [u32; 3],
+    packed_uint4.xyzw: [u32; 4],
+    packed_float2.xy: [f32; 2],
+    packed_float3.xyz: [f32; 3],
+    packed_float4.xyzw: [f32; 4],
+);
+
+impl_half_vector!(
+    half2.xy: 2,
+    half3.xyz: 3,
+    half4.xyzw: 4,
+    packed_half2.xy: 2,
+    packed_half3.xyz: 3,
+    packed_half4.xyzw: 4,
+);
+
+impl_matrix!(
+    float2x2: [[2; 2]; 2],
+    float2x3: [[3; 4]; 2],
+    float2x4: [[4; 4]; 2],
+    float3x2: [[2; 2]; 3],
+    float3x3: [[3; 4]; 3],
+    float3x4: [[4; 4]; 3],
+    float4x2: [[2; 2]; 4],
+    float4x3: [[3; 4]; 4],
+    float4x4: [[4; 4]; 4],
+);
+
+impl_half_matrix!(
+    half2x2: [[2; 2]; 2],
+    half2x3: [[3; 4]; 2],
+    half2x4: [[4; 4]; 2],
+    half3x2: [[2; 2]; 3],
+    half3x3: [[3; 4]; 3],
+    half3x4: [[4; 4]; 3],
+    half4x2: [[2; 2]; 4],
+    half4x3: [[3; 4]; 4],
+    half4x4: [[4; 4]; 4],
+);
+
+impl_struct!(
+    DebugPath {
+        points: [packed_float3; 8],
+        screen_pos: float2,
+        num_points: c_uchar,
+    }
+    Geometry {
+        indices: c_ulong,
+        positions: c_ulong,
+        normals: c_ulong,
+        tx_coords: c_ulong,
+    }
+    GeometryNoTxCoords {
+        indices: c_ulong,
+        positions: c_ulong,
+        normals: c_ulong,
+    }
+    Material {
+        ambient_texture: c_ulong,
+        diffuse_texture: c_ulong,
+        specular_texture: c_ulong,
+        specular_shineness: f32,
+        ambient_amount: f32,
+    }
+    ModelSpace {
+        m_model_to_projection: float4x4,
+        m_normal_to_world: float3x3,
+    }
+    ProjectedSpace {
+        m_world_to_projection: float4x4,
+        m_screen_to_world: float4x4,
+        position_world: float4,
+    }
+    SHIrradiance {
+        coefficients: [float4; 9],
+    }
+    TriNormals {
+        normals: [c_uint; 2],
+    }
+    TriNormalsOct16 {
+        normals: [c_uint; 3],
+    }
+    TriNormalsXyz111110 {
+        normals: [c_uint; 3],
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+struct MTLQuadTessellationFactorsHalfMirror {
+    edge: [f32; 4],
+    inside: [f32; 2],
+}
+
+impl Serialize for MTLQuadTessellationFactorsHalf {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MTLQuadTessellationFactorsHalfMirror {
+            edge: self.edgeTessellationFactor.map(half_to_f32),
+            inside: self.insideTessellationFactor.map(half_to_f32),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MTLQuadTessellationFactorsHalf {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let MTLQuadTessellationFactorsHalfMirror { edge, inside } =
+            MTLQuadTessellationFactorsHalfMirror::deserialize(deserializer)?;
+        Ok(Self {
+            edgeTessellationFactor: edge.map(f32_to_half),
+            insideTessellationFactor: inside.map(f32_to_half),
+        })
+    }
+}
+
+// Quaternion components `[x, y, z, w]`.
+impl Serialize for Quat {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.to_array().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Quat {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let xyzw = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(Self(f32x4::from_array(xyzw)))
+    }
+}
+
+// Translation and scale only serialize XYZ components (W is ignored, see `Transform`).
+#[derive(Serialize, Deserialize)]
+struct TransformMirror {
+    translation: [f32; 3],
+    rotation: Quat,
+    scale: [f32; 3],
+}
+
+impl Serialize for Transform {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TransformMirror {
+            translation: resize(self.translation.to_array()),
+            rotation: self.rotation,
+            scale: resize(self.scale.to_array()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let TransformMirror {
+            translation: [tx, ty, tz],
+            rotation,
+            scale: [sx, sy, sz],
+        } = TransformMirror::deserialize(deserializer)?;
+        Ok(Self {
+            translation: f32x4::from_array([tx, ty, tz, 0.]),
+            rotation,
+            scale: f32x4::from_array([sx, sy, sz, 1.]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_round_trip<T: Serialize + for<'de> Deserialize<'de> + PartialEq>(
+        v: &T,
+        expected_json: Option<&str>,
+    ) {
+        let json = serde_json::to_string(v).expect("Failed to serialize");
+        if let Some(expected_json) = expected_json {
+            assert_eq!(json, expected_json);
+        }
+        let actual: T = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert!(actual == *v, "Round trip mismatch for {json}");
+    }
+
+    #[test]
+    fn test_vectors() {
+        assert_round_trip(&float3 { xyz: [1., 2., 3.] }, Some("[1.0,2.0,3.0]"));
+        assert_round_trip(&packed_uint2 { xy: [1, 2] }, Some("[1,2]"));
+        assert_round_trip(
+            &bool3 {
+                xyz: [true, false, true],
+            },
+            Some("[true,false,true]"),
+        );
+    }
+
+    #[test]
+    fn test_half_vectors() {
+        assert_round_trip(
+            &half3 {
+                xyz: [1., 0.5, -2.].map(f32_to_half),
+            },
+            Some("[1.0,0.5,-2.0]"),
+        );
+        assert_round_trip(
+            &packed_half2 {
+                xy: [0.25, 4.].map(f32_to_half),
+            },
+            Some("[0.25,4.0]"),
+        );
+    }
+
+    #[test]
+    fn test_matrices() {
+        assert_round_trip(
+            &f32x4x4::translate(1., 2., 3.),
+            Some("[[1.0,0.0,0.0,0.0],[0.0,1.0,0.0,0.0],[0.0,0.0,1.0,0.0],[1.0,2.0,3.0,1.0]]"),
+        );
+
+        // Padding is not serialized
+        assert_round_trip(
+            &float3x3::from(f32x4x4::scale(1., 2., 3., 1.)),
+            Some("[[1.0,0.0,0.0],[0.0,2.0,0.0],[0.0,0.0,3.0]]"),
+        );
+        assert_round_trip(
+            &half2x3 {
+                columns: [[1., 2., 3., 0.], [4., 5., 6., 0.]].map(|c| c.map(f32_to_half)),
+            },
+            Some("[[1.0,2.0,3.0],[4.0,5.0,6.0]]"),
+        );
+        assert_round_trip(
+            &half3x2 {
+                columns: [[1., 2.], [3., 4.], [5., 6.]].map(|c| c.map(f32_to_half)),
+            },
+            Some("[[1.0,2.0],[3.0,4.0],[5.0,6.0]]"),
+        );
+    }
+
+    #[test]
+    fn test_structs() {
+        assert_round_trip(
+            &Material {
+                ambient_texture: 1,
+                diffuse_texture: 2,
+                specular_texture: 3,
+                specular_shineness: 0.5,
+                ambient_amount: 0.25,
+            },
+            Some(
+                r#"{"ambient_texture":1,"diffuse_texture":2,"specular_texture":3,"specular_shineness":0.5,"ambient_amount":0.25}"#,
+            ),
+        );
+        assert_round_trip(
+            &ProjectedSpace {
+                m_world_to_projection: f32x4x4::translate(1., 2., 3.),
+                m_screen_to_world: f32x4x4::scale(4., 5., 6., 1.),
+                position_world: float4 {
+                    xyzw: [7., 8., 9., 1.],
+                },
+            },
+            None,
+        );
+        assert_round_trip(
+            &ModelSpace::from_model_to_world(f32x4x4::identity(), f32x4x4::scale(1., 2., 3., 1.)),
+            None,
+        );
+        assert_round_trip(
+            &SHIrradiance {
+                coefficients: std::array::from_fn(|i| float4 {
+                    xyzw: [i as f32, 0.5, -1., 0.],
+                }),
+            },
+            None,
+        );
+        assert_round_trip(
+            &TriNormals { normals: [1, 2] },
+            Some(r#"{"normals":[1,2]}"#),
+        );
+    }
+
+    #[test]
+    fn test_mtl_quad_tessellation_factors_half() {
+        assert_round_trip(
+            &MTLQuadTessellationFactorsHalf::new(16),
+            Some(r#"{"edge":[16.0,16.0,16.0,16.0],"inside":[16.0,16.0]}"#),
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        assert_round_trip(
+            &Transform::new(
+                f32x4::from_array([1., 2., 3., 0.]),
+                Quat::IDENTITY,
+                f32x4::from_array([4., 5., 6., 1.]),
+            ),
+            Some(
+                r#"{"translation":[1.0,2.0,3.0],"rotation":[0.0,0.0,0.0,1.0],"scale":[4.0,5.0,6.0]}"#,
+            ),
+        );
+    }
+}
@@ -1 +0,0 @@
-#include "./all_metal_types.h"
\ No newline at end of file
@@ -0,0 +1,37 @@
+#pragma once
+
+#include "./macros.h"
+
+DEF_CONSTANT constexpr unsigned int SH_IRRADIANCE_NUM_COEFFICIENTS = 9;
+
+// Diffuse irradiance of an environment, as 3rd order (9 coefficient) spherical harmonics. Commonly
+// created with `asset_compiler::spherical_harmonics` (projected from an environment's cube texture
+// faces), as a compact alternative to an irradiance cube texture.
+struct SHIrradiance {
+    // RGB coefficients (W is unused), already convolved with the cosine lobe and divided by π (the
+    // irradiance cube texture convention, see `asset_compiler::ibl`). Ordered by band (l) and then
+    // order (m):
+    //     (0, 0), (1, -1), (1, 0), (1, 1), (2, -2), (2, -1), (2, 0), (2, 1), (2, 2)
+    float4 coefficients[SH_IRRADIANCE_NUM_COEFFICIENTS];
+
+#ifdef __METAL_VERSION__
+    // Irradiance (divided by π) for a unit length world space normal. Multiply by the diffuse color
+    // (albedo) to get the diffuse lighting contribution.
+    //
+    // Must be kept in sync with `SHIrradiance::irradiance()` in `metal-types/src/spherical_harmonics.rs`.
+    inline float3 irradiance(const float3 n) const constant {
+        const float3 e =
+              coefficients[0].xyz * 0.282095
+            + coefficients[1].xyz * (0.488603 * n.y)
+            + coefficients[2].xyz * (0.488603 * n.z)
+            + coefficients[3].xyz * (0.488603 * n.x)
+            + coefficients[4].xyz * (1.092548 * n.x * n.y)
+            + coefficients[5].xyz * (1.092548 * n.y * n.z)
+            + coefficients[6].xyz * (0.315392 * (3.0 * n.z * n.z - 1.0))
+            + coefficients[7].xyz * (1.092548 * n.x * n.z)
+            + coefficients[8].xyz * (0.546274 * (n.x * n.x - n.y * n.y));
+        // Ringing can produce (slightly) negative irradiance opposite of very bright areas.
+        return max(e, 0.0);
+    }
+#endif // __METAL_VERSION__
+};
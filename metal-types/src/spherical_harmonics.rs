@@ -0,0 +1,112 @@
+use super::{all_metal_types::SHIrradiance, float4, SH_IRRADIANCE_NUM_COEFFICIENTS};
+
+const NUM_COEFFICIENTS: usize = SH_IRRADIANCE_NUM_COEFFICIENTS as _;
+
+// CPU implementation of `metal-types/src/spherical-harmonics.h`, used to create (see
+// `asset_compiler::spherical_harmonics`) and verify spherical harmonics irradiance.
+impl SHIrradiance {
+    // From RGB coefficients (irradiance divided by π), in the same order as `coefficients`.
+    #[inline]
+    pub fn new(rgb_coefficients: [[f32; 3]; NUM_COEFFICIENTS]) -> Self {
+        Self {
+            coefficients: rgb_coefficients.map(|[r, g, b]| float4 {
+                xyzw: [r, g, b, 0.],
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn rgb_coefficients(&self) -> [[f32; 3]; NUM_COEFFICIENTS] {
+        self.coefficients.map(|c| [c.xyzw[0], c.xyzw[1], c.xyzw[2]])
+    }
+
+    // Real spherical harmonics basis functions (bands 0 through 2) evaluated for a unit length
+    // direction, in the same order as `coefficients`.
+    #[inline]
+    pub fn basis([x, y, z]: [f32; 3]) -> [f32; NUM_COEFFICIENTS] {
+        [
+            0.282095,
+            0.488603 * y,
+            0.488603 * z,
+            0.488603 * x,
+            1.092548 * x * y,
+            1.092548 * y * z,
+            0.315392 * (3. * z * z - 1.),
+            1.092548 * x * z,
+            0.546274 * (x * x - y * y),
+        ]
+    }
+
+    // Irradiance (divided by π) for a unit length normal, see `SHIrradiance::irradiance()` in
+    // `metal-types/src/spherical-harmonics.h`.
+    pub fn irradiance(&self, normal: [f32; 3]) -> [f32; 3] {
+        let mut e = [0.; 3];
+        for (c, y) in self.rgb_coefficients().iter().zip(Self::basis(normal)) {
+            for i in 0..3 {
+                e[i] += c[i] * y;
+            }
+        }
+        e.map(|e| e.max(0.))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TOLERANCE: f32 = 1e-5;
+
+    fn assert_approx_eq(actual: [f32; 3], expected: [f32; 3]) {
+        assert!(
+            actual
+                .iter()
+                .zip(&expected)
+                .all(|(a, e)| (a - e).abs() < TOLERANCE),
+            "expected {expected:?}, actual {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_rgb_coefficients() {
+        let rgb = std::array::from_fn(|i| [i as f32, i as f32 * 2., -(i as f32)]);
+        let sh = SHIrradiance::new(rgb);
+        assert_eq!(sh.rgb_coefficients(), rgb);
+        assert!(sh.coefficients.iter().all(|c| c.xyzw[3] == 0.));
+    }
+
+    #[test]
+    fn test_basis() {
+        // Y(0,0) is constant, and the band 1 functions are the (scaled) direction.
+        assert_eq!(
+            SHIrradiance::basis([0., 0., 1.]),
+            [0.282095, 0., 0.488603, 0., 0., 0., 0.630784, 0., 0.]
+        );
+        assert_eq!(
+            SHIrradiance::basis([1., 0., 0.]),
+            [0.282095, 0., 0., 0.488603, 0., 0., -0.315392, 0., 0.546274]
+        );
+        assert_eq!(
+            SHIrradiance::basis([0., -1., 0.]),
+            [0.282095, -0.488603, 0., 0., 0., 0., -0.315392, 0., -0.546274]
+        );
+    }
+
+    #[test]
+    fn test_irradiance() {
+        // Constant (only band 0)
+        let mut rgb = [[0.; 3]; NUM_COEFFICIENTS];
+        rgb[0] = [1. / 0.282095, 2. / 0.282095, 0.];
+        let sh = SHIrradiance::new(rgb);
+        for n in [[0., 0., 1.], [1., 0., 0.], [0., -1., 0.]] {
+            assert_approx_eq(sh.irradiance(n), [1., 2., 0.]);
+        }
+
+        // Linear in Z (band 1), negative irradiance is clamped to 0.
+        rgb[0] = [0.5 / 0.282095; 3];
+        rgb[2] = [0.5 / 0.488603, 1. / 0.488603, 0.];
+        let sh = SHIrradiance::new(rgb);
+        assert_approx_eq(sh.irradiance([0., 0., 1.]), [1., 1.5, 0.5]);
+        assert_approx_eq(sh.irradiance([1., 0., 0.]), [0.5, 0.5, 0.5]);
+        assert_approx_eq(sh.irradiance([0., 0., -1.]), [0., 0., 0.5]);
+    }
+}
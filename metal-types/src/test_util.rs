@@ -0,0 +1,29 @@
+// Helpers shared by tests.
+use crate::f32x4x4;
+use std::simd::{f32x4, SimdFloat, SimdPartialOrd};
+
+// Asserts every component of `actual` is within `tolerance` of `expected`.
+pub(crate) fn assert_approx_eq_f32x4(actual: f32x4, expected: f32x4, tolerance: f32) {
+    assert!(
+        (actual - expected)
+            .abs()
+            .simd_lt(f32x4::splat(tolerance))
+            .all(),
+        "Expected {expected:?} (tolerance {tolerance}), got {actual:?}"
+    );
+}
+
+// Same as `assert_approx_eq_f32x4()`, for every column.
+pub(crate) fn assert_approx_eq_f32x4x4(actual: f32x4x4, expected: f32x4x4, tolerance: f32) {
+    assert!(
+        actual
+            .columns
+            .iter()
+            .zip(expected.columns)
+            .all(|(&a, e)| (f32x4::from_array(a) - f32x4::from_array(e))
+                .abs()
+                .simd_lt(f32x4::splat(tolerance))
+                .all()),
+        "Expected {expected:?} (tolerance {tolerance}), got {actual:?}"
+    );
+}
@@ -0,0 +1,192 @@
+use super::{f32x4x4, float4x3, Quat};
+use std::simd::f32x4;
+
+// Translation, rotation, and scale (XYZ components, W is ignored), applied in the following order:
+// 1. Scale
+// 2. Rotate
+// 3. Translate
+//
+// Unlike composing matrices, rotations can be interpolated (see `Quat::slerp()`) and do not suffer
+// from gimbal lock.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub translation: f32x4,
+    pub rotation: Quat,
+    pub scale: f32x4,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        translation: f32x4::from_array([0.; 4]),
+        rotation: Quat::IDENTITY,
+        scale: f32x4::from_array([1.; 4]),
+    };
+
+    #[inline]
+    pub const fn new(translation: f32x4, rotation: Quat, scale: f32x4) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    #[inline]
+    pub const fn from_translation(translation: f32x4) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    #[inline]
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Self {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    #[inline]
+    pub const fn from_scale(scale: f32x4) -> Self {
+        Self {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    // Interpolates translation and scale linearly, and rotation spherically (see `Quat::slerp()`).
+    #[inline]
+    pub fn lerp(&self, to: &Self, t: f32) -> Self {
+        let t_splat = f32x4::splat(t);
+        Self {
+            translation: self.translation + (to.translation - self.translation) * t_splat,
+            rotation: self.rotation.slerp(to.rotation, t),
+            scale: self.scale + (to.scale - self.scale) * t_splat,
+        }
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform> for f32x4x4 {
+    #[inline]
+    fn from(
+        Transform {
+            translation: t,
+            rotation,
+            scale: s,
+        }: Transform,
+    ) -> Self {
+        let r = f32x4x4::from(rotation).columns;
+        f32x4x4 {
+            columns: [
+                (f32x4::from_array(r[0]) * f32x4::splat(s[0])).to_array(),
+                (f32x4::from_array(r[1]) * f32x4::splat(s[1])).to_array(),
+                (f32x4::from_array(r[2]) * f32x4::splat(s[2])).to_array(),
+                [t[0], t[1], t[2], 1.],
+            ],
+        }
+    }
+}
+
+impl From<Transform> for float4x3 {
+    #[inline(always)]
+    fn from(t: Transform) -> Self {
+        f32x4x4::from(t).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::assert_approx_eq_f32x4x4;
+    use std::f32::consts::PI;
+
+    const TOLERANCE: f32 = 1e-5;
+
+    fn v(x: f32, y: f32, z: f32) -> f32x4 {
+        f32x4::from_array([x, y, z, 0.])
+    }
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(f32x4x4::from(Transform::IDENTITY), f32x4x4::identity());
+        assert_eq!(Transform::default(), Transform::IDENTITY);
+    }
+
+    #[test]
+    fn test_into_f32x4x4() {
+        let rotation = Quat::from_euler(0.5, -1., 2.);
+        let t = Transform::new(v(1., 2., 3.), rotation, v(4., 5., 6.));
+        assert_approx_eq_f32x4x4(
+            t.into(),
+            f32x4x4::translate(1., 2., 3.)
+                * f32x4x4::rotate(0.5, -1., 2.)
+                * f32x4x4::scale(4., 5., 6., 1.),
+            TOLERANCE,
+        );
+
+        assert_approx_eq_f32x4x4(
+            Transform::from_translation(v(1., 2., 3.)).into(),
+            f32x4x4::translate(1., 2., 3.),
+            TOLERANCE,
+        );
+        assert_approx_eq_f32x4x4(
+            Transform::from_rotation(rotation).into(),
+            f32x4x4::rotate(0.5, -1., 2.),
+            TOLERANCE,
+        );
+        assert_approx_eq_f32x4x4(
+            Transform::from_scale(v(4., 5., 6.)).into(),
+            f32x4x4::scale(4., 5., 6., 1.),
+            TOLERANCE,
+        );
+    }
+
+    #[test]
+    fn test_into_float4x3() {
+        let t = Transform::new(v(1., 2., 3.), Quat::IDENTITY, v(4., 5., 6.));
+        assert_eq!(
+            float4x3::from(t).columns,
+            [
+                [4., 0., 0., 0.],
+                [0., 5., 0., 0.],
+                [0., 0., 6., 0.],
+                [1., 2., 3., 0.],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lerp() {
+        let axis = v(0., 1., 0.);
+        let a = Transform::new(
+            v(0., 0., 0.),
+            Quat::from_axis_angle(axis, 0.),
+            v(1., 1., 1.),
+        );
+        let b = Transform::new(
+            v(2., 4., 6.),
+            Quat::from_axis_angle(axis, PI / 2.),
+            v(3., 3., 3.),
+        );
+        assert_approx_eq_f32x4x4(a.lerp(&b, 0.).into(), a.into(), TOLERANCE);
+        assert_approx_eq_f32x4x4(a.lerp(&b, 1.).into(), b.into(), TOLERANCE);
+        assert_approx_eq_f32x4x4(
+            a.lerp(&b, 0.5).into(),
+            Transform::new(
+                v(1., 2., 3.),
+                Quat::from_axis_angle(axis, PI / 4.),
+                v(2., 2., 2.),
+            )
+            .into(),
+            TOLERANCE,
+        );
+    }
+}
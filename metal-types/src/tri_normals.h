@@ -6,12 +6,6 @@ inline half3x3 decompress(const uint n0, const uint n1) {
     const half3 a0 = unpack_unorm10a2_to_half(n0).xyz;
     const half3 a1 = unpack_unorm10a2_to_half(n1).xyz;
     const auto n12z = n1 >> 30;
-
-    // TODO: START HERE
-    // TODO: START HERE
-    // TODO: START HERE
-    // -What if we encode the xs closer together?
-    // - What about heterogeneous encoding a0 is 1010102, but a1 is something else?
     return half3x3(
         half3(a0.x, a1.x, a0.z),
         half3(a0.y, a1.y, a1.z),
@@ -35,8 +29,37 @@ inline half3x3 decode(const uint n0, const uint n1) {
     const auto ns = decompress(n0, n1);
     return decode_normal(ns[0], ns[1], ns[2]);
 }
+
+// https://jcgt.org/published/0003/02/01/
+inline half3 decode_oct16(const uint n) {
+    const float2 e = unpack_snorm2x16_to_float(n);
+    float3 v(e.x, e.y, 1.0 - abs(e.x) - abs(e.y));
+    const float t = saturate(-v.z);
+    v.xy += select(float2(t), float2(-t), v.xy >= 0.0);
+    return half3(normalize(v));
+}
+
+inline half3 decode_xyz111110(const uint n) {
+    const float3 v = float3(n & 0x7FF, (n >> 11) & 0x7FF, n >> 22) / float3(2047.0, 2047.0, 1023.0);
+    return half3(normalize(v * 2.0 - 1.0));
+}
+
+// Interpolates a triangle's normals (`n`) and transforms the result (`m`).
+inline half3 interpolate_normal(const half3x3 n, const float2 barycentric_coord, const constant half3x3 *m) {
+    const half2 b2 = half2(barycentric_coord);
+    const half3 b(1.0 - (b2.x + b2.y), b2.x, b2.y);
+    const half3 normal = n * b;
+
+    // IMPORTANT: Converting to float before normalize may seem redundant, but for models
+    // like yoda, small half precision normals seems to cause normalize to go bonkers.
+    return half3(normalize(float3((*m) * normal)));
+}
 #endif // __METAL_VERSION__
 
+// Per-triangle normals encodings (see `TriNormalsEncoding` in `tri_normals.rs`).
+// All encodings provide the same `normal()` method, switching encodings only requires changing the
+// type used to read the primitive data (ex. `(device TriNormalsOct16 *) hit.primitive_data`).
+
 struct TriNormals {
     // 3 Normals packed/encoded into 2 32-bit (10 10 10 2)
     //          | X            | Y             | Z-sign
@@ -48,14 +71,37 @@ struct TriNormals {
 
     #ifdef __METAL_VERSION__
     inline half3 normal(const float2 barycentric_coord, const constant half3x3 *m) const device {
-        const half2   b2 = half2(barycentric_coord);
-        const half3   b(1.0 - (b2.x + b2.y), b2.x, b2.y);
-        const half3x3 n(decode(normals[0], normals[1]));
-        const half3   normal = n * b;
-
-        // IMPORTANT: Converting to float before normalize may seem redundant, but for models
-        // like yoda, small half precision normals seems to cause normalize to go bonkers.
-        return half3(normalize(float3((*m) * normal)));
+        return interpolate_normal(decode(normals[0], normals[1]), barycentric_coord, m);
+    }
+    #endif // __METAL_VERSION__
+};
+
+struct TriNormalsOct16 {
+    // 3 Normals, each packed/encoded into 1 32-bit (16 16)
+    //          | X (signed normalized)  | Y (signed normalized)
+    // ---------|------------------------|-----------------------
+    // normal i | normals[i] & 0xFFFF    | normals[i] >> 16
+    unsigned int normals[3];
+
+    #ifdef __METAL_VERSION__
+    inline half3 normal(const float2 barycentric_coord, const constant half3x3 *m) const device {
+        const half3x3 n(decode_oct16(normals[0]), decode_oct16(normals[1]), decode_oct16(normals[2]));
+        return interpolate_normal(n, barycentric_coord, m);
+    }
+    #endif // __METAL_VERSION__
+};
+
+struct TriNormalsXyz111110 {
+    // 3 Normals, each packed/encoded into 1 32-bit (11 11 10)
+    //          | X (unsigned normalized) | Y (unsigned normalized)    | Z (unsigned normalized)
+    // ---------|-------------------------|----------------------------|------------------------
+    // normal i | normals[i] & 0x7FF      | (normals[i] >> 11) & 0x7FF | normals[i] >> 22
+    unsigned int normals[3];
+
+    #ifdef __METAL_VERSION__
+    inline half3 normal(const float2 barycentric_coord, const constant half3x3 *m) const device {
+        const half3x3 n(decode_xyz111110(normals[0]), decode_xyz111110(normals[1]), decode_xyz111110(normals[2]));
+        return interpolate_normal(n, barycentric_coord, m);
     }
     #endif // __METAL_VERSION__
 };
@@ -1,18 +1,26 @@
-use super::all_metal_types::TriNormals;
+use super::{
+    all_metal_types::{TriNormals, TriNormalsOct16, TriNormalsXyz111110},
+    cross, dot, f32x4_extras,
+};
 use std::{
     ffi::c_uint,
+    fmt::Display,
     simd::{f32x4, SimdFloat},
 };
 
+// Normals further from unit length are counted by `TriNormalsEncoding::encoding_error()`.
+const UNIT_VECTOR_TOLERANCE: f32 = 0.0001;
+
+#[inline(always)]
+fn is_unit_vector(n: f32x4) -> bool {
+    (1.0 - (n * n).reduce_sum().sqrt()).abs() < UNIT_VECTOR_TOLERANCE
+}
+
 #[inline(always)]
 fn dbg_ensure_unit_vector(n: f32x4) -> f32x4 {
     #[cfg(debug_assertions)]
     {
-        let len = (n * n).reduce_sum().sqrt();
-        if (1.0 - len).abs() >= 0.0001 {
-            println!("Input is not unit vector (needs normalize)");
-        }
-        return n / f32x4::splat(len);
+        return n.normalize();
     }
     #[cfg(not(debug_assertions))]
     {
@@ -20,6 +28,9 @@ fn dbg_ensure_unit_vector(n: f32x4) -> f32x4 {
     }
 }
 
+const MAX_10BIT_VALUE: f32 = ((1u16 << 10) - 1) as f32;
+const MAX_11BIT_VALUE: f32 = ((1u16 << 11) - 1) as f32;
+
 #[inline]
 fn unorm2(b0: bool, b1: bool) -> u32 {
     let b0 = if b0 { 1 } else { 0 };
@@ -27,14 +38,10 @@ fn unorm2(b0: bool, b1: bool) -> u32 {
     b0 | b1
 }
 
+// Values outside of 0 and 1 (from normals that are not unit vectors) are clamped.
 #[inline]
-fn unorm10(mut f: f32) -> u32 {
-    const MAX_10BIT_VALUE: f32 = ((1u16 << 10) - 1) as f32;
-    if f < 0. || f > 1. {
-        println!("Expected {f} to be within 0 and 1.");
-        f = f.clamp(0., 1.);
-    }
-    (f * MAX_10BIT_VALUE) as u32
+fn unorm10(f: f32) -> u32 {
+    (f.clamp(0., 1.) * MAX_10BIT_VALUE) as u32
 }
 
 #[inline]
@@ -66,6 +73,28 @@ fn compress(n0: ([f32; 2], bool), n1: ([f32; 2], bool), n2: ([f32; 2], bool)) ->
     ]
 }
 
+// Inverse of `compress()`, mirrors `decompress()` in `tri_normals.h`.
+#[inline]
+fn decompress([n0, n1]: [c_uint; 2]) -> [([f32; 2], bool); 3] {
+    let unorm10 = |n: u32, shift: u32| ((n >> shift) & 0x3FF) as f32 / MAX_10BIT_VALUE;
+    let n12z = n1 >> 30;
+    [
+        ([unorm10(n0, 0), unorm10(n0, 10)], (n0 >> 30) != 0),
+        ([unorm10(n1, 0), unorm10(n1, 10)], (n12z & 1) != 0),
+        ([unorm10(n0, 20), unorm10(n1, 20)], n12z > 1),
+    ]
+}
+
+// Inverse of `encode_normal()`, mirrors `decode_normal()` in `tri_normals.h`.
+// http://johnwhite3d.blogspot.com/2017/10/signed-octahedron-normal-encoding.html
+#[inline]
+fn decode_normal(([x, y], z_sign): ([f32; 2], bool)) -> f32x4 {
+    let ox = x - y;
+    let oy = x + y - 1.;
+    let oz = (if z_sign { 1. } else { -1. }) * (1. - ox.abs() - oy.abs());
+    f32x4::from_array([ox, oy, oz, 0.]).normalize()
+}
+
 // http://johnwhite3d.blogspot.com/2017/10/signed-octahedron-normal-encoding.html
 #[inline]
 fn encode_normal(v: &[f32], i: usize) -> ([f32; 2], bool) {
@@ -99,9 +128,86 @@ fn encode(v: &[f32], n0: usize, n1: usize, n2: usize) -> [c_uint; 2] {
     )
 }
 
-impl TriNormals {
+// Per-triangle normals, stored as acceleration structure primitive data (see
+// `metal_app::model_acceleration_structure::ModelAccelerationStructure`).
+// Each encoding has a matching Metal struct and decoder in `tri_normals.h`, trading size (bytes per
+// triangle) for quality (see `compare_tri_normals_encodings()`).
+pub trait TriNormalsEncoding: Copy + Default + 'static {
+    const NAME: &'static str;
+
+    fn from_indexed_raw_normals(
+        raw_normals: &[f32],
+        raw_indices: &[u32],
+        start_vertex: usize,
+    ) -> Self;
+
+    // Decodes the 3 normals (W is zero), mirrors the Metal decoder in `tri_normals.h`.
+    //
+    // IMPORTANT: Decoding is performed with single precision (`float`), unlike the shader's half
+    // precision (`half`).
+    fn decode(&self) -> [f32x4; 3];
+
+    // Measures the angular error introduced by encoding every triangle's normals of an indexed mesh.
+    fn encoding_error(raw_normals: &[f32], raw_indices: &[u32]) -> TriNormalsEncodingError {
+        let mut max_angle: f32 = 0.;
+        let mut total_angle: f64 = 0.;
+        let mut count = 0;
+        let mut non_unit_count = 0;
+        for tri in 0..(raw_indices.len() / 3) {
+            let decoded = Self::from_indexed_raw_normals(raw_normals, raw_indices, tri).decode();
+            for (i, decoded) in decoded.into_iter().enumerate() {
+                let expected = raw_normal(raw_normals, (raw_indices[tri * 3 + i] * 3) as _);
+                if !is_unit_vector(expected) {
+                    non_unit_count += 1;
+                }
+                let expected = expected.normalize();
+                // More accurate than `acos(dot)` for small angles.
+                let angle = cross(expected, decoded)
+                    .length()
+                    .atan2(dot(expected, decoded));
+                max_angle = max_angle.max(angle);
+                total_angle += angle as f64;
+                count += 1;
+            }
+        }
+        TriNormalsEncodingError {
+            max_angle,
+            mean_angle: if count == 0 {
+                0.
+            } else {
+                (total_angle / count as f64) as f32
+            },
+            count,
+            non_unit_count,
+        }
+    }
+}
+
+#[inline]
+fn raw_normal(v: &[f32], i: usize) -> f32x4 {
+    f32x4::from_array([v[i], v[i + 1], v[i + 2], 0.])
+}
+
+#[inline]
+fn raw_indexed_normals(
+    raw_normals: &[f32],
+    raw_indices: &[u32],
+    start_vertex: usize,
+) -> [f32x4; 3] {
+    [0, 1, 2].map(|i| {
+        dbg_ensure_unit_vector(raw_normal(
+            raw_normals,
+            (raw_indices[start_vertex * 3 + i] * 3) as _,
+        ))
+    })
+}
+
+// Signed octahedron, 10-bit X and Y, 1-bit Z sign. 3 normals packed into 2 32-bit (10 10 10 2).
+impl TriNormalsEncoding for TriNormals {
+    const NAME: &'static str = "TriNormals (Signed Octahedron 10-10-1)";
+
     #[inline]
-    pub fn from_indexed_raw_normals(
+    fn from_indexed_raw_normals(
         raw_normals: &[f32],
         raw_indices: &[u32],
         start_vertex: usize,
@@ -115,123 +221,540 @@ impl TriNormals {
             ),
         }
     }
+
+    #[inline]
+    fn decode(&self) -> [f32x4; 3] {
+        decompress(self.normals).map(decode_normal)
+    }
+}
+
+#[inline]
+fn sign_not_zero(f: f32) -> f32 {
+    if f >= 0. {
+        1.
+    } else {
+        -1.
+    }
+}
+
+// Octahedron encoding (XY components between [-1, 1]).
+// https://jcgt.org/published/0003/02/01/
+#[inline]
+fn encode_oct(n: f32x4) -> [f32; 2] {
+    let n = n / f32x4::splat(n[0].abs() + n[1].abs() + n[2].abs());
+    if n[2] >= 0. {
+        [n[0], n[1]]
+    } else {
+        [
+            (1. - n[1].abs()) * sign_not_zero(n[0]),
+            (1. - n[0].abs()) * sign_not_zero(n[1]),
+        ]
+    }
+}
+
+// Mirrors `decode_oct16()` in `tri_normals.h`.
+#[inline]
+fn decode_oct([x, y]: [f32; 2]) -> f32x4 {
+    let z = 1. - x.abs() - y.abs();
+    let t = (-z).clamp(0., 1.);
+    let x = x + if x >= 0. { -t } else { t };
+    let y = y + if y >= 0. { -t } else { t };
+    f32x4::from_array([x, y, z, 0.]).normalize()
+}
+
+#[inline]
+fn snorm16(f: f32) -> u32 {
+    ((f.clamp(-1., 1.) * (i16::MAX as f32)).round() as i16) as u16 as u32
+}
+
+// Mirrors Metal's `unpack_snorm2x16_to_float()`.
+#[inline]
+fn unpack_snorm16(n: u32) -> f32 {
+    ((n as u16) as i16 as f32 / (i16::MAX as f32)).max(-1.)
+}
+
+// Octahedron, 16-bit X and Y. Each normal packed into a 32-bit (16 16).
+impl TriNormalsEncoding for TriNormalsOct16 {
+    const NAME: &'static str = "TriNormalsOct16 (Octahedron 16-16)";
+
+    #[inline]
+    fn from_indexed_raw_normals(
+        raw_normals: &[f32],
+        raw_indices: &[u32],
+        start_vertex: usize,
+    ) -> Self {
+        Self {
+            normals: raw_indexed_normals(raw_normals, raw_indices, start_vertex).map(|n| {
+                let [x, y] = encode_oct(n);
+                (snorm16(y) << 16) | snorm16(x)
+            }),
+        }
+    }
+
+    #[inline]
+    fn decode(&self) -> [f32x4; 3] {
+        self.normals
+            .map(|n| decode_oct([unpack_snorm16(n), unpack_snorm16(n >> 16)]))
+    }
+}
+
+#[inline]
+fn unorm(f: f32, max: f32) -> u32 {
+    ((f * 0.5 + 0.5).clamp(0., 1.) * max).round() as u32
+}
+
+// XYZ components (not normalized, mapped from [-1, 1] to [0, 1]), 11-bit X and Y, 10-bit Z. Each
+// normal packed into a 32-bit (11 11 10).
+impl TriNormalsEncoding for TriNormalsXyz111110 {
+    const NAME: &'static str = "TriNormalsXyz111110 (XYZ 11-11-10)";
+
+    #[inline]
+    fn from_indexed_raw_normals(
+        raw_normals: &[f32],
+        raw_indices: &[u32],
+        start_vertex: usize,
+    ) -> Self {
+        Self {
+            normals: raw_indexed_normals(raw_normals, raw_indices, start_vertex).map(|n| {
+                (unorm(n[2], MAX_10BIT_VALUE) << 22)
+                    | (unorm(n[1], MAX_11BIT_VALUE) << 11)
+                    | unorm(n[0], MAX_11BIT_VALUE)
+            }),
+        }
+    }
+
+    // Mirrors `decode_xyz111110()` in `tri_normals.h`.
+    #[inline]
+    fn decode(&self) -> [f32x4; 3] {
+        self.normals.map(|n| {
+            (f32x4::from_array([
+                (n & 0x7FF) as f32,
+                ((n >> 11) & 0x7FF) as f32,
+                (n >> 22) as f32,
+                0.,
+            ]) / f32x4::from_array([MAX_11BIT_VALUE, MAX_11BIT_VALUE, MAX_10BIT_VALUE, 1.])
+                * f32x4::splat(2.)
+                - f32x4::from_array([1., 1., 1., 0.]))
+            .normalize()
+        })
+    }
+}
+
+// Angular error (radians) between original and decoded normals (see
+// `TriNormalsEncoding::encoding_error()`).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TriNormalsEncodingError {
+    pub max_angle: f32,
+    pub mean_angle: f32,
+    // Number of normals measured (3 per triangle).
+    pub count: usize,
+    // Number of normals measured that are not unit vectors (normalized before encoding in debug
+    // builds only).
+    pub non_unit_count: usize,
+}
+
+impl Display for TriNormalsEncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TriNormals encoding error: max {:.4}°, mean {:.4}° ({} normals",
+            self.max_angle.to_degrees(),
+            self.mean_angle.to_degrees(),
+            self.count
+        )?;
+        if self.non_unit_count > 0 {
+            write!(f, ", {} not unit vectors", self.non_unit_count)?;
+        }
+        write!(f, ")")
+    }
+}
+
+// Size and quality of an encoding for a specific mesh.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TriNormalsEncodingReport {
+    pub name: &'static str,
+    pub bytes_per_triangle: usize,
+    pub error: TriNormalsEncodingError,
+}
+
+impl TriNormalsEncodingReport {
+    pub fn new<E: TriNormalsEncoding>(raw_normals: &[f32], raw_indices: &[u32]) -> Self {
+        Self {
+            name: E::NAME,
+            bytes_per_triangle: std::mem::size_of::<E>(),
+            error: E::encoding_error(raw_normals, raw_indices),
+        }
+    }
+
+    // Whether the maximum angular error (radians) is within budget.
+    #[inline]
+    pub fn within_budget(&self, max_angle: f32) -> bool {
+        self.error.max_angle <= max_angle
+    }
+}
+
+impl Display for TriNormalsEncodingReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} bytes/triangle, {}",
+            self.name, self.bytes_per_triangle, self.error
+        )
+    }
+}
+
+// Compares every encoding for a specific mesh, ordered from smallest to largest (bytes per
+// triangle).
+// ex. Smallest encoding within an error budget of 0.1 degrees
+//     `compare_tri_normals_encodings(..).iter().find(|r| r.within_budget(0.1_f32.to_radians()))`
+pub fn compare_tri_normals_encodings(
+    raw_normals: &[f32],
+    raw_indices: &[u32],
+) -> [TriNormalsEncodingReport; 3] {
+    let mut reports = [
+        TriNormalsEncodingReport::new::<TriNormals>(raw_normals, raw_indices),
+        TriNormalsEncodingReport::new::<TriNormalsOct16>(raw_normals, raw_indices),
+        TriNormalsEncodingReport::new::<TriNormalsXyz111110>(raw_normals, raw_indices),
+    ];
+    reports.sort_by(|a, b| {
+        a.bytes_per_triangle
+            .cmp(&b.bytes_per_triangle)
+            .then(a.error.max_angle.total_cmp(&b.error.max_angle))
+    });
+    reports
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::packed_half3;
-    use metal::*;
-    use std::marker::PhantomData;
 
     const COS_PI_4: f32 = 0.7071067811865476;
     const SIN_PI_4: f32 = 0.7071067811865475;
 
     const DECOMPRESS_TOLERANCE: f32 = 0.000928;
     const DECODE_TOLERANCE: f32 = 0.003005207;
+    const MAX_ENCODING_ERROR: f32 = 0.003;
+
+    // Tolerances for the other encodings are derived from their quantization step (distance between
+    // adjacent encoded values). Angular errors (radians) are small enough to approximate the error's
+    // length.
+
+    // TriNormalsOct16: 16-bit snorm X and Y, rounded (< 0.5 steps lost). Z is derived from both and
+    // normalizing grows the error by up to sqrt(3).
+    const OCT16_STEP: f32 = 1. / i16::MAX as f32;
+    const OCT16_DECODE_TOLERANCE: f32 = 2. * OCT16_STEP;
+    const MAX_OCT16_ENCODING_ERROR: f32 = OCT16_DECODE_TOLERANCE;
+    // TriNormalsXyz111110: [-1, 1] rounded to 11-bit X and Y, and 10-bit Z (the coarsest step,
+    // < 0.5 steps lost). Normalizing spreads the error across components.
+    const XYZ111110_STEP: f32 = 2. / MAX_10BIT_VALUE;
+    const XYZ111110_DECODE_TOLERANCE: f32 = XYZ111110_STEP;
+    const MAX_XYZ111110_ENCODING_ERROR: f32 = XYZ111110_STEP;
+
+    fn assert_f32x4_equalish(expected: &[f32], actual: f32x4, tolerance: f32) {
+        for (i, component) in ["x", "y", "z"].into_iter().enumerate() {
+            let diff = (expected[i] - actual[i]).abs();
+            if diff > tolerance {
+                assert_eq!(
+                    expected[i], actual[i],
+                    "\n{component} component is incorrect, diff = {diff}"
+                );
+            }
+        }
+    }
+
+    const TEST_NORMALS: [[f32; 9]; 6] = [
+        [
+            1., 0., 0., // 0
+            0., 1., 0., // 1
+            0., 0., 1., // 2
+        ],
+        [
+            -1., 0., 0., // 0
+            0., -1., 0., // 1
+            0., 0., -1., // 2
+        ],
+        [
+            COS_PI_4, SIN_PI_4, 0., // 0
+            -COS_PI_4, SIN_PI_4, 0., // 1
+            COS_PI_4, -SIN_PI_4, 0., // 2
+        ],
+        [
+            -COS_PI_4, -SIN_PI_4, 0., // 0
+            COS_PI_4, 0., SIN_PI_4, // 1
+            -COS_PI_4, 0., SIN_PI_4, // 2
+        ],
+        [
+            COS_PI_4, 0., -SIN_PI_4, // 0
+            -COS_PI_4, 0., -SIN_PI_4, // 1
+            0., COS_PI_4, SIN_PI_4, // 2
+        ],
+        [
+            0., -COS_PI_4, SIN_PI_4, // 0
+            0., COS_PI_4, -SIN_PI_4, // 1
+            0., -COS_PI_4, -SIN_PI_4, // 2
+        ],
+    ];
 
-    fn assert_eq_with_tolerance(left: f32, right: u16, tolerance: f32, msg: &'static str) {
-        let right = half::f16::from_bits(right).to_f32();
-        let diff = (left - right).abs();
-        if diff > tolerance {
-            assert_eq!(left, right, "\n{msg}, diff = {diff}");
+    #[test]
+    fn compress_rs_decompress_rs() {
+        let input = [([0.1, 0.3], true), ([0.2, 0.4], false), ([0.5, 0.6], true)];
+        let actual = decompress(compress(input[0], input[1], input[2]));
+        for ((expected_xy, expected_z), (actual_xy, actual_z)) in input.into_iter().zip(actual) {
+            assert_eq!(expected_z, actual_z);
+            for (e, a) in expected_xy.into_iter().zip(actual_xy) {
+                assert!((e - a).abs() <= DECOMPRESS_TOLERANCE, "{e} != {a}");
+            }
         }
     }
 
-    fn assert_equalish(expected: &[f32], actual: &packed_half3, tolerance: f32) {
-        assert_eq_with_tolerance(
-            expected[0],
-            actual.xyz[0],
-            tolerance,
-            "x component is incorrect",
-        );
-        assert_eq_with_tolerance(
-            expected[1],
-            actual.xyz[1],
-            tolerance,
-            "y component is incorrect",
+    #[test]
+    fn encode_rs_decode_rs() {
+        for input in TEST_NORMALS {
+            let actual = TriNormals {
+                normals: encode(&input, 0, 3, 6),
+            }
+            .decode();
+            assert_f32x4_equalish(&input[0..3], actual[0], DECODE_TOLERANCE);
+            assert_f32x4_equalish(&input[3..6], actual[1], DECODE_TOLERANCE);
+            assert_f32x4_equalish(&input[6..], actual[2], DECODE_TOLERANCE);
+            for n in actual {
+                assert_eq!(n[3], 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn encoding_error() {
+        // Axis aligned X and Y normals are encoded exactly.
+        let raw_normals = [1., 0., 0., 0., 1., 0., -1., 0., 0., 0., -1., 0.];
+        assert_eq!(
+            TriNormals::encoding_error(&raw_normals, &[0, 1, 2, 2, 3, 0]),
+            TriNormalsEncodingError {
+                max_angle: 0.,
+                mean_angle: 0.,
+                count: 6,
+                non_unit_count: 0,
+            }
         );
-        assert_eq_with_tolerance(
-            expected[2],
-            actual.xyz[2],
-            tolerance,
-            "z component is incorrect",
+
+        let raw_normals: Vec<f32> = TEST_NORMALS.into_iter().flatten().collect();
+        let raw_indices: Vec<u32> = (0..(raw_normals.len() / 3) as u32).rev().collect();
+        let error = TriNormals::encoding_error(&raw_normals, &raw_indices);
+        assert_eq!(error.count, raw_indices.len());
+        assert!(error.mean_angle > 0.);
+        assert!(error.mean_angle <= error.max_angle);
+        assert!(error.max_angle < MAX_ENCODING_ERROR, "{error}");
+        assert_eq!(error.non_unit_count, 0);
+
+        // Normals that are not unit vectors are counted, not printed.
+        let error = TriNormals::encoding_error(&[2., 0., 0., 0., 1., 0., 0., 0., 0.5], &[0, 1, 2]);
+        assert_eq!(error.count, 3);
+        assert_eq!(error.non_unit_count, 2);
+
+        assert_eq!(
+            TriNormals::encoding_error(&raw_normals, &[]),
+            TriNormalsEncodingError {
+                max_angle: 0.,
+                mean_angle: 0.,
+                count: 0,
+                non_unit_count: 0,
+            }
         );
     }
 
-    struct ComputeExecutor<I: Copy + Clone, O: Copy + Clone> {
-        cmd_queue: CommandQueue,
-        output_buf: Buffer,
-        pipeline: ComputePipelineState,
-        _input_type: PhantomData<I>,
-        _output_type: PhantomData<O>,
+    fn assert_encode_rs_decode_rs<E: TriNormalsEncoding>(tolerance: f32) {
+        for input in TEST_NORMALS {
+            let actual = E::from_indexed_raw_normals(&input, &[0, 1, 2], 0).decode();
+            assert_f32x4_equalish(&input[0..3], actual[0], tolerance);
+            assert_f32x4_equalish(&input[3..6], actual[1], tolerance);
+            assert_f32x4_equalish(&input[6..], actual[2], tolerance);
+            for n in actual {
+                assert_eq!(n[3], 0.);
+            }
+        }
     }
 
-    impl<I: Copy + Clone, O: Copy + Clone> ComputeExecutor<I, O> {
-        fn new(src: &'static str) -> Self {
-            let device = Device::system_default().expect("Failed to access Metal Device");
-            let output_buf = device.new_buffer(
-                std::mem::size_of::<O>() as _,
-                MTLResourceOptions::StorageModeShared,
-            );
-            let lib = device
-                .new_library_with_source(src, &CompileOptions::new())
-                .expect("Failed to compile test compute kernel source");
-            let cmd_queue = device.new_command_queue();
-            let test_fn = lib
-                .get_function("test", None)
-                .expect("Failed to get kernel function");
-            let pipeline = device
-                .new_compute_pipeline_state_with_function(&test_fn)
-                .expect("Failed to get kernel function");
-
-            Self {
-                cmd_queue,
-                output_buf,
-                pipeline,
-                _input_type: PhantomData,
-                _output_type: PhantomData,
+    #[test]
+    fn encode_rs_decode_rs_oct16() {
+        assert_encode_rs_decode_rs::<TriNormalsOct16>(OCT16_DECODE_TOLERANCE);
+    }
+
+    #[test]
+    fn encode_rs_decode_rs_xyz111110() {
+        assert_encode_rs_decode_rs::<TriNormalsXyz111110>(XYZ111110_DECODE_TOLERANCE);
+    }
+
+    #[test]
+    fn encoding_error_per_encoding() {
+        // Octahedron folding (negative Z) and random-ish directions.
+        let raw_normals: Vec<f32> = [
+            [0.267, 0.535, 0.802],
+            [-0.577, 0.577, -0.577],
+            [0.9, -0.1, -0.424],
+            [-0.123, -0.456, 0.881],
+            [0.001, 0.002, -0.999],
+            [0.6, 0.8, 0.],
+        ]
+        .into_iter()
+        .flat_map(|n| {
+            f32x4::from_array([n[0], n[1], n[2], 0.])
+                .normalize()
+                .to_array()[..3]
+                .to_vec()
+        })
+        .collect();
+        let raw_indices = [0, 1, 2, 3, 4, 5, 5, 0, 3];
+
+        let error = TriNormalsOct16::encoding_error(&raw_normals, &raw_indices);
+        assert_eq!(error.count, raw_indices.len());
+        assert!(error.max_angle < MAX_OCT16_ENCODING_ERROR, "{error}");
+
+        let error = TriNormalsXyz111110::encoding_error(&raw_normals, &raw_indices);
+        assert_eq!(error.count, raw_indices.len());
+        assert!(error.max_angle < MAX_XYZ111110_ENCODING_ERROR, "{error}");
+    }
+
+    #[test]
+    fn compare_encodings() {
+        let raw_normals: Vec<f32> = TEST_NORMALS.into_iter().flatten().collect();
+        let raw_indices: Vec<u32> = (0..(raw_normals.len() / 3) as u32).collect();
+        let reports = compare_tri_normals_encodings(&raw_normals, &raw_indices);
+        assert_eq!(
+            reports.map(|r| (r.name, r.bytes_per_triangle)),
+            [
+                (TriNormals::NAME, 8),
+                (TriNormalsOct16::NAME, 12),
+                (TriNormalsXyz111110::NAME, 12),
+            ]
+        );
+        for r in reports {
+            assert_eq!(r.error.count, raw_indices.len());
+        }
+
+        // Smallest encoding within budget
+        let find = |budget: f32| {
+            reports
+                .iter()
+                .find(|r| r.within_budget(budget))
+                .map(|r| r.name)
+        };
+        assert_eq!(find(1.), Some(TriNormals::NAME));
+        assert_eq!(
+            find(reports[1].error.max_angle),
+            Some(TriNormalsOct16::NAME)
+        );
+        assert_eq!(find(0.), None);
+    }
+
+    #[cfg(feature = "metal")]
+    mod metal_compute {
+        use super::*;
+        use crate::packed_half3;
+        use metal::*;
+        use std::marker::PhantomData;
+
+        fn assert_eq_with_tolerance(left: f32, right: u16, tolerance: f32, msg: &'static str) {
+            let right = half::f16::from_bits(right).to_f32();
+            let diff = (left - right).abs();
+            if diff > tolerance {
+                assert_eq!(left, right, "\n{msg}, diff = {diff}");
             }
         }
 
-        fn run(&self, input: I) -> &O {
-            let cmd_buf = self.cmd_queue.new_command_buffer();
-            let e = cmd_buf.new_compute_command_encoder();
-            e.set_compute_pipeline_state(&self.pipeline);
-            e.set_bytes(
-                0,
-                std::mem::size_of::<I>() as _,
-                (&input as *const I) as *const _,
+        fn assert_equalish(expected: &[f32], actual: &packed_half3, tolerance: f32) {
+            assert_eq_with_tolerance(
+                expected[0],
+                actual.xyz[0],
+                tolerance,
+                "x component is incorrect",
             );
-            e.set_buffer(1, Some(&self.output_buf), 0);
-            e.dispatch_threads(
-                MTLSize {
-                    width: 1,
-                    height: 1,
-                    depth: 1,
-                },
-                MTLSize {
-                    width: 1,
-                    height: 1,
-                    depth: 1,
-                },
+            assert_eq_with_tolerance(
+                expected[1],
+                actual.xyz[1],
+                tolerance,
+                "y component is incorrect",
+            );
+            assert_eq_with_tolerance(
+                expected[2],
+                actual.xyz[2],
+                tolerance,
+                "z component is incorrect",
             );
-            e.end_encoding();
-            cmd_buf.commit();
-            cmd_buf.wait_until_completed();
-            unsafe { &*(self.output_buf.contents() as *const O) }
         }
-    }
 
-    #[test]
-    pub fn compress_rs_decompress_metal() {
-        let c: ComputeExecutor<[c_uint; 2], [packed_half3; 3]> = ComputeExecutor::new(concat!(
-            r#"
+        struct ComputeExecutor<I: Copy + Clone, O: Copy + Clone> {
+            cmd_queue: CommandQueue,
+            output_buf: Buffer,
+            pipeline: ComputePipelineState,
+            _input_type: PhantomData<I>,
+            _output_type: PhantomData<O>,
+        }
+
+        impl<I: Copy + Clone, O: Copy + Clone> ComputeExecutor<I, O> {
+            fn new(src: &'static str) -> Self {
+                let device = Device::system_default().expect("Failed to access Metal Device");
+                let output_buf = device.new_buffer(
+                    std::mem::size_of::<O>() as _,
+                    MTLResourceOptions::StorageModeShared,
+                );
+                let lib = device
+                    .new_library_with_source(src, &CompileOptions::new())
+                    .expect("Failed to compile test compute kernel source");
+                let cmd_queue = device.new_command_queue();
+                let test_fn = lib
+                    .get_function("test", None)
+                    .expect("Failed to get kernel function");
+                let pipeline = device
+                    .new_compute_pipeline_state_with_function(&test_fn)
+                    .expect("Failed to get kernel function");
+
+                Self {
+                    cmd_queue,
+                    output_buf,
+                    pipeline,
+                    _input_type: PhantomData,
+                    _output_type: PhantomData,
+                }
+            }
+
+            fn run(&self, input: I) -> &O {
+                let cmd_buf = self.cmd_queue.new_command_buffer();
+                let e = cmd_buf.new_compute_command_encoder();
+                e.set_compute_pipeline_state(&self.pipeline);
+                e.set_bytes(
+                    0,
+                    std::mem::size_of::<I>() as _,
+                    (&input as *const I) as *const _,
+                );
+                e.set_buffer(1, Some(&self.output_buf), 0);
+                e.dispatch_threads(
+                    MTLSize {
+                        width: 1,
+                        height: 1,
+                        depth: 1,
+                    },
+                    MTLSize {
+                        width: 1,
+                        height: 1,
+                        depth: 1,
+                    },
+                );
+                e.end_encoding();
+                cmd_buf.commit();
+                cmd_buf.wait_until_completed();
+                unsafe { &*(self.output_buf.contents() as *const O) }
+            }
+        }
+
+        #[test]
+        pub fn compress_rs_decompress_metal() {
+            let c: ComputeExecutor<[c_uint; 2], [packed_half3; 3]> = ComputeExecutor::new(concat!(
+                r#"
 #include <metal_stdlib>
 using namespace metal;
 "#,
-            include_str!("./tri_normals.h"),
-            r#"
+                include_str!("./tri_normals.h"),
+                r#"
 [[kernel]]
 void test(
     constant uint          * input  [[buffer(0)]],
@@ -243,28 +766,28 @@ void test(
     *(&output[2]) = v[2];
 }
 "#
-        ));
-        let t = |input: [([f32; 2], bool); 3], expected: [[f32; 3]; 3]| {
-            let actual = c.run(compress(input[0], input[1], input[2]));
-            assert_equalish(&expected[0], &actual[0], DECOMPRESS_TOLERANCE);
-            assert_equalish(&expected[1], &actual[1], DECOMPRESS_TOLERANCE);
-            assert_equalish(&expected[2], &actual[2], DECOMPRESS_TOLERANCE);
-        };
-        t(
-            [([0.1, 0.3], true), ([0.2, 0.4], false), ([0.5, 0.6], true)],
-            [[0.1, 0.2, 0.5], [0.3, 0.4, 0.6], [1.0, 0.0, 1.0]],
-        );
-    }
+            ));
+            let t = |input: [([f32; 2], bool); 3], expected: [[f32; 3]; 3]| {
+                let actual = c.run(compress(input[0], input[1], input[2]));
+                assert_equalish(&expected[0], &actual[0], DECOMPRESS_TOLERANCE);
+                assert_equalish(&expected[1], &actual[1], DECOMPRESS_TOLERANCE);
+                assert_equalish(&expected[2], &actual[2], DECOMPRESS_TOLERANCE);
+            };
+            t(
+                [([0.1, 0.3], true), ([0.2, 0.4], false), ([0.5, 0.6], true)],
+                [[0.1, 0.2, 0.5], [0.3, 0.4, 0.6], [1.0, 0.0, 1.0]],
+            );
+        }
 
-    #[test]
-    pub fn encode_rs_decode_metal() {
-        let c: ComputeExecutor<[c_uint; 2], [packed_half3; 3]> = ComputeExecutor::new(concat!(
-            r#"
+        #[test]
+        pub fn encode_rs_decode_metal() {
+            let c: ComputeExecutor<[c_uint; 2], [packed_half3; 3]> = ComputeExecutor::new(concat!(
+                r#"
 #include <metal_stdlib>
 using namespace metal;
 "#,
-            include_str!("./tri_normals.h"),
-            r#"
+                include_str!("./tri_normals.h"),
+                r#"
 [[kernel]]
 void test(
     constant uint         * input  [[buffer(0)]],
@@ -276,46 +799,47 @@ void test(
     *(&output[2]) = v[2];
 }
 "#
-        ));
-        let t = |input: [f32; 9]| {
-            let actual = c.run(encode(&input, 0, 3, 6));
-            assert_equalish(&input[0..3], &actual[0], DECODE_TOLERANCE);
-            assert_equalish(&input[3..6], &actual[1], DECODE_TOLERANCE);
-            assert_equalish(&input[6..], &actual[2], DECODE_TOLERANCE);
-        };
-        t([
-            1., 0., 0., // 0
-            0., 1., 0., // 1
-            0., 0., 1., // 2
-        ]);
-        t([
-            -1., 0., 0., // 0
-            0., -1., 0., // 1
-            0., 0., -1., // 2
-        ]);
-
-        t([
-            COS_PI_4, SIN_PI_4, 0., // 0
-            -COS_PI_4, SIN_PI_4, 0., // 1
-            COS_PI_4, -SIN_PI_4, 0., // 2
-        ]);
-
-        t([
-            -COS_PI_4, -SIN_PI_4, 0., // 0
-            COS_PI_4, 0., SIN_PI_4, // 1
-            -COS_PI_4, 0., SIN_PI_4, // 2
-        ]);
-
-        t([
-            COS_PI_4, 0., -SIN_PI_4, // 0
-            -COS_PI_4, 0., -SIN_PI_4, // 1
-            0., COS_PI_4, SIN_PI_4, // 2
-        ]);
-
-        t([
-            0., -COS_PI_4, SIN_PI_4, // 0
-            0., COS_PI_4, -SIN_PI_4, // 1
-            0., -COS_PI_4, -SIN_PI_4, // 2
-        ])
+            ));
+            let t = |input: [f32; 9]| {
+                let actual = c.run(encode(&input, 0, 3, 6));
+                assert_equalish(&input[0..3], &actual[0], DECODE_TOLERANCE);
+                assert_equalish(&input[3..6], &actual[1], DECODE_TOLERANCE);
+                assert_equalish(&input[6..], &actual[2], DECODE_TOLERANCE);
+            };
+            t([
+                1., 0., 0., // 0
+                0., 1., 0., // 1
+                0., 0., 1., // 2
+            ]);
+            t([
+                -1., 0., 0., // 0
+                0., -1., 0., // 1
+                0., 0., -1., // 2
+            ]);
+
+            t([
+                COS_PI_4, SIN_PI_4, 0., // 0
+                -COS_PI_4, SIN_PI_4, 0., // 1
+                COS_PI_4, -SIN_PI_4, 0., // 2
+            ]);
+
+            t([
+                -COS_PI_4, -SIN_PI_4, 0., // 0
+                COS_PI_4, 0., SIN_PI_4, // 1
+                -COS_PI_4, 0., SIN_PI_4, // 2
+            ]);
+
+            t([
+                COS_PI_4, 0., -SIN_PI_4, // 0
+                -COS_PI_4, 0., -SIN_PI_4, // 1
+                0., COS_PI_4, SIN_PI_4, // 2
+            ]);
+
+            t([
+                0., -COS_PI_4, SIN_PI_4, // 0
+                0., COS_PI_4, -SIN_PI_4, // 1
+                0., -COS_PI_4, -SIN_PI_4, // 2
+            ])
+        }
     }
 }
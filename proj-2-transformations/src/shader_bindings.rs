@@ -12,7 +12,7 @@ use metal_app::{metal::*, metal_types::*, pipeline::*};
 
 pub const INITIAL_CAMERA_DISTANCE: f32 = 50.0;
 #[repr(C)]
-#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, MetalLayout)]
 pub struct GeometryPositions {
     pub indices: ::std::os::raw::c_ulong,
     pub positions: ::std::os::raw::c_ulong,
@@ -66,7 +66,7 @@ fn bindgen_test_layout_GeometryPositions() {
 }
 #[repr(C)]
 #[repr(align(16))]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, MetalLayout)]
 pub struct VertexInput {
     pub mins: float4,
     pub maxs: float4,
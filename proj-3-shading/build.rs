@@ -1,3 +1,9 @@
+use metal_build::BuildOptions;
+
 fn main() {
-    metal_build::build();
+    // Enumerate the shading mode (function constants) permutations, see `PipelinePermutations`.
+    // `main_fragment` references the 4 shading mode bool function constants (2^4 permutations).
+    metal_build::build_with_options(BuildOptions {
+        max_function_permutations: 16,
+    });
 }
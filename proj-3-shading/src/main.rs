@@ -18,44 +18,57 @@ const INITIAL_LIGHT_ROTATION: f32x2 = f32x2::from_array([-PI / 4., 0.]);
 const LIBRARY_BYTES: &'static [u8] = include_bytes!(concat!(env!("OUT_DIR"), "/shaders.metallib"));
 const LIGHT_DISTANCE: f32 = INITIAL_CAMERA_DISTANCE / 2.;
 
+type ModelPipelines = PipelinePermutations<
+    main_vertex,
+    main_fragment,
+    RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>,
+>;
+
 struct Delegate {
     camera: Camera,
     command_queue: CommandQueue,
     depth_state: DepthStencilState,
     depth_texture: DepthTexture,
     device: Device,
-    library: Library,
     light: Camera,
     light_pipeline: RenderPipeline<1, light_vertex, light_fragment, (Depth, NoStencil)>,
     m_model_to_world: f32x4x4,
     model: Model<GeometryNoTxCoords, NoMaterial>,
-    model_pipeline: RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>,
+    model_pipelines: ModelPipelines,
     model_space: ModelSpace,
     needs_render: bool,
     shading_mode: ShadingModeSelector,
 }
 
-fn create_model_pipeline(
-    device: &Device,
-    library: &Library,
-    shading_mode: ShadingModeSelector,
-) -> RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)> {
-    RenderPipeline::new(
-        "Render Teapot Pipeline",
-        device,
-        library,
-        [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
-        main_vertex,
-        main_fragment {
-            HasAmbient: shading_mode.has_ambient(),
-            HasDiffuse: shading_mode.has_diffuse(),
-            OnlyNormals: shading_mode.only_normals(),
-            HasSpecular: shading_mode.has_specular(),
+fn create_model_pipelines(device: &Device, library: &Library) -> ModelPipelines {
+    let device = device.clone();
+    let library = library.clone();
+    PipelinePermutations::new_in_background(
+        ShadingModeSelector::MODES.map(|mode| (main_vertex, model_fragment_function(mode))),
+        move |vertex_function, fragment_function| {
+            RenderPipeline::new(
+                "Render Teapot Pipeline",
+                &device,
+                &library,
+                [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
+                vertex_function,
+                fragment_function,
+                (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
+            )
         },
-        (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
     )
 }
 
+#[inline]
+fn model_fragment_function(shading_mode: ShadingModeSelector) -> main_fragment {
+    main_fragment {
+        HasAmbient: shading_mode.has_ambient(),
+        HasDiffuse: shading_mode.has_diffuse(),
+        OnlyNormals: shading_mode.only_normals(),
+        HasSpecular: shading_mode.has_specular(),
+    }
+}
+
 impl RendererDelgate for Delegate {
     fn new(device: Device) -> Self {
         let teapot_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -128,15 +141,11 @@ impl RendererDelgate for Delegate {
             ),
             m_model_to_world,
             model,
-            model_pipeline: create_model_pipeline(&device, &library, shading_mode),
-            model_space: ModelSpace {
-                m_model_to_projection: f32x4x4::identity(),
-                m_normal_to_world: m_model_to_world.into(),
-            },
+            model_pipelines: create_model_pipelines(&device, &library),
+            model_space: ModelSpace::from_model_to_world(f32x4x4::identity(), m_model_to_world),
             needs_render: false,
             shading_mode,
             device,
-            library,
         }
     }
 
@@ -148,7 +157,10 @@ impl RendererDelgate for Delegate {
             .new_command_buffer_with_unretained_references();
         command_buffer.set_label("Renderer Command Buffer");
         let depth_tx = self.depth_texture.texture();
-        self.model_pipeline.new_pass(
+        let model_pipeline = self
+            .model_pipelines
+            .get(&main_vertex, &model_fragment_function(self.shading_mode));
+        model_pipeline.new_pass(
             "Model and Light",
             command_buffer,
             [(
@@ -215,8 +227,6 @@ impl RendererDelgate for Delegate {
             self.needs_render = true;
         }
         if self.shading_mode.on_event(event) {
-            self.model_pipeline =
-                create_model_pipeline(&self.device, &self.library, self.shading_mode);
             self.needs_render = true;
         }
         if self.depth_texture.on_event(event, &self.device) {
@@ -1,3 +1,16 @@
+use metal_build::BuildOptions;
+use std::path::PathBuf;
+
 fn main() {
-    metal_build::build();
+    // Enumerate the shading mode (function constants) permutations, see `PipelinePermutations`.
+    // `main_fragment` references the 4 shading mode bool function constants (2^4 permutations).
+    metal_build::build_with_options(BuildOptions {
+        max_function_permutations: 16,
+    });
+    let common_assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("common-assets");
+    for mtl_file in ["teapot/teapot.mtl", "yoda/yoda.mtl"] {
+        metal_build::build_model_texture_assets(common_assets_dir.join(mtl_file));
+    }
 }
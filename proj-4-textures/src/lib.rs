@@ -24,44 +24,57 @@ const INITIAL_MODE: ShadingModeSelector = ShadingModeSelector::DEFAULT;
 const LIBRARY_BYTES: &'static [u8] = include_bytes!(concat!(env!("OUT_DIR"), "/shaders.metallib"));
 const LIGHT_DISTANCE: f32 = 0.5;
 
+type ModelPipelines = PipelinePermutations<
+    main_vertex,
+    main_fragment,
+    RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>,
+>;
+
 pub struct Delegate<const RENDER_LIGHT: bool> {
     camera: Camera,
     command_queue: CommandQueue,
     depth_state: DepthStencilState,
     depth_texture: DepthTexture,
     device: Device,
-    library: Library,
     light_pipeline: RenderPipeline<1, light_vertex, light_fragment, (Depth, NoStencil)>,
     light: Camera,
     m_model_to_world: f32x4x4,
-    model_pipeline: RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>,
+    model_pipelines: ModelPipelines,
     model_space: ModelSpace,
     model: Model<Geometry, HasMaterial<Material>>,
     needs_render: bool,
     shading_mode: ShadingModeSelector,
 }
 
-fn create_model_pipeline(
-    device: &Device,
-    library: &Library,
-    shading_mode: ShadingModeSelector,
-) -> RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)> {
-    RenderPipeline::new(
-        "Model",
-        &device,
-        &library,
-        [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
-        main_vertex,
-        main_fragment {
-            HasAmbient: shading_mode.has_ambient(),
-            HasDiffuse: shading_mode.has_diffuse(),
-            OnlyNormals: shading_mode.only_normals(),
-            HasSpecular: shading_mode.has_specular(),
+fn create_model_pipelines(device: &Device, library: &Library) -> ModelPipelines {
+    let device = device.clone();
+    let library = library.clone();
+    PipelinePermutations::new_in_background(
+        ShadingModeSelector::MODES.map(|mode| (main_vertex, model_fragment_function(mode))),
+        move |vertex_function, fragment_function| {
+            RenderPipeline::new(
+                "Model",
+                &device,
+                &library,
+                [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
+                vertex_function,
+                fragment_function,
+                (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
+            )
         },
-        (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
     )
 }
 
+#[inline]
+fn model_fragment_function(shading_mode: ShadingModeSelector) -> main_fragment {
+    main_fragment {
+        HasAmbient: shading_mode.has_ambient(),
+        HasDiffuse: shading_mode.has_diffuse(),
+        OnlyNormals: shading_mode.only_normals(),
+        HasSpecular: shading_mode.has_specular(),
+    }
+}
+
 impl<const RENDER_LIGHT: bool> RendererDelgate for Delegate<RENDER_LIGHT> {
     fn new(device: Device) -> Self {
         let executable_name = std::env::args()
@@ -75,7 +88,7 @@ impl<const RENDER_LIGHT: bool> RendererDelgate for Delegate<RENDER_LIGHT> {
             .new_library_with_data(LIBRARY_BYTES)
             .expect("Failed to import shader metal lib.");
         let mode = INITIAL_MODE;
-        let model_pipeline = create_model_pipeline(&device, &library, mode);
+        let model_pipelines = create_model_pipelines(&device, &library);
         let model = Model::from_file(
             model_file,
             &device,
@@ -159,19 +172,11 @@ impl<const RENDER_LIGHT: bool> RendererDelgate for Delegate<RENDER_LIGHT> {
             ),
             m_model_to_world,
             model,
-            model_space: ModelSpace {
-                m_model_to_projection: f32x4x4::identity(),
-                // IMPORTANT: Not a mistake, using Model-to-World 4x4 Matrix for Normal-to-World 3x3
-                // Matrix. Conceptually, we want a matrix that ONLY applies rotation (no
-                // translation). Since normals are directions (not positions, relative to a point on
-                // a surface), translations are meaningless.
-                m_normal_to_world: m_model_to_world.into(),
-            },
-            model_pipeline,
+            model_space: ModelSpace::from_model_to_world(f32x4x4::identity(), m_model_to_world),
+            model_pipelines,
             needs_render: false,
             shading_mode: mode,
             device,
-            library,
         }
     }
 
@@ -183,7 +188,10 @@ impl<const RENDER_LIGHT: bool> RendererDelgate for Delegate<RENDER_LIGHT> {
             .new_command_buffer_with_unretained_references();
         command_buffer.set_label("Renderer Command Buffer");
         let depth_tx = &self.depth_texture.texture();
-        self.model_pipeline.new_pass(
+        let model_pipeline = self
+            .model_pipelines
+            .get(&main_vertex, &model_fragment_function(self.shading_mode));
+        model_pipeline.new_pass(
             "Model and Light",
             command_buffer,
             [(
@@ -261,8 +269,6 @@ impl<const RENDER_LIGHT: bool> RendererDelgate for Delegate<RENDER_LIGHT> {
             self.needs_render = true;
         };
         if self.shading_mode.on_event(event) {
-            self.model_pipeline =
-                create_model_pipeline(&self.device, &self.library, self.shading_mode);
             self.needs_render = true;
         }
         if self.depth_texture.on_event(event, &self.device) {
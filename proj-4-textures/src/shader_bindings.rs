@@ -38,6 +38,17 @@ impl metal_app::pipeline::function::Function for main_vertex {
     type Binds<'c> = main_vertex_binds<'c>;
 }
 impl PipelineFunction<VertexFunctionType> for main_vertex {}
+impl FunctionPermutations for main_vertex {
+    const PERMUTATIONS: usize = 1;
+    #[inline]
+    fn from_permutation_index(_index: usize) -> Self {
+        Self
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        0
+    }
+}
 
 #[allow(non_camel_case_types)]
 pub struct main_fragment_binds<'c> {
@@ -81,6 +92,25 @@ impl metal_app::pipeline::function::Function for main_fragment {
     }
 }
 impl PipelineFunction<FragmentFunctionType> for main_fragment {}
+impl FunctionPermutations for main_fragment {
+    const PERMUTATIONS: usize = 16;
+    #[inline]
+    fn from_permutation_index(index: usize) -> Self {
+        Self {
+            HasAmbient: (index & 1) != 0,
+            HasDiffuse: (index & (1 << 1)) != 0,
+            OnlyNormals: (index & (1 << 2)) != 0,
+            HasSpecular: (index & (1 << 3)) != 0,
+        }
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        (self.HasAmbient as usize)
+            | ((self.HasDiffuse as usize) << 1)
+            | ((self.OnlyNormals as usize) << 2)
+            | ((self.HasSpecular as usize) << 3)
+    }
+}
 
 #[allow(non_camel_case_types)]
 pub struct light_vertex_binds<'c> {
@@ -107,6 +137,17 @@ impl metal_app::pipeline::function::Function for light_vertex {
     type Binds<'c> = light_vertex_binds<'c>;
 }
 impl PipelineFunction<VertexFunctionType> for light_vertex {}
+impl FunctionPermutations for light_vertex {
+    const PERMUTATIONS: usize = 1;
+    #[inline]
+    fn from_permutation_index(_index: usize) -> Self {
+        Self
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        0
+    }
+}
 
 #[allow(non_camel_case_types)]
 pub struct light_fragment;
@@ -115,3 +156,14 @@ impl metal_app::pipeline::function::Function for light_fragment {
     type Binds<'c> = NoBinds;
 }
 impl PipelineFunction<FragmentFunctionType> for light_fragment {}
+impl FunctionPermutations for light_fragment {
+    const PERMUTATIONS: usize = 1;
+    #[inline]
+    fn from_permutation_index(_index: usize) -> Self {
+        Self
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        0
+    }
+}
@@ -11,7 +11,7 @@ use metal_app::{metal::*, metal_types::*, pipeline::*};
 /* automatically generated by rust-bindgen 0.60.1 */
 
 #[repr(u8)]
-#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Hash, PartialEq, Eq, MetalLayout)]
 pub enum TextureFilterMode {
     Nearest = 0,
     Linear = 1,
@@ -1,4 +1,6 @@
 #![feature(fs_try_exists)]
+use asset_compiler::{metadata::Compression, mipmap::MipmapOptions};
+use build_hash::{ModifiedOutput, Outputs};
 use std::path::PathBuf;
 
 const CUBE_TEXTURE_FILENAMES: [&'static str; 6] = [
@@ -13,18 +15,25 @@ const CUBE_TEXTURE_FILENAMES: [&'static str; 6] = [
 fn create_cubemap_asset() {
     let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
     let cube_source_textures_dir = assets_dir.join("cubemap");
-    build_hash::build_hash(
+    let cube_texture_files = CUBE_TEXTURE_FILENAMES.map(|f| cube_source_textures_dir.join(f));
+    let cubemap_asset_dir = assets_dir.join("cubemap.asset");
+    let mipmap_options = MipmapOptions::default();
+    let compression = Compression::default();
+    build_hash::build_hash_with_outputs(
         &assets_dir.join("cubemap_asset_hash"),
-        &[
-            &cube_source_textures_dir.join("cubemap_posx.png").as_path(),
-            &cube_source_textures_dir.join("cubemap_negx.png").as_path(),
-            &cube_source_textures_dir.join("cubemap_posy.png").as_path(),
-            &cube_source_textures_dir.join("cubemap_negy.png").as_path(),
-            &cube_source_textures_dir.join("cubemap_posz.png").as_path(),
-            &cube_source_textures_dir.join("cubemap_negz.png").as_path(),
-        ],
+        &cube_texture_files.each_ref().map(|f| f as _),
+        Outputs {
+            paths: &[&cubemap_asset_dir],
+            modified: ModifiedOutput::Regenerate,
+        },
+        // Recreate the asset whenever the asset format or how it is compiled changes
+        &(
+            asset_compiler::metadata::VERSION,
+            asset_compiler::container::VERSION,
+            mipmap_options,
+            compression,
+        ),
         || {
-            let cubemap_asset_dir = assets_dir.join("cubemap.asset");
             if std::fs::try_exists(&cubemap_asset_dir)
                 .expect("Could not determine whether destination exists or not")
             {
@@ -37,11 +46,11 @@ fn create_cubemap_asset() {
                 }
             }
             std::fs::create_dir(&cubemap_asset_dir).expect("Failed to create temp asset directory");
-            let test_cube_texture_files =
-                CUBE_TEXTURE_FILENAMES.map(|f| cube_source_textures_dir.join(f));
             asset_compiler::cube_texture::create_cube_texture_asset_dir(
                 &cubemap_asset_dir,
-                &test_cube_texture_files,
+                &cube_texture_files,
+                Some(mipmap_options),
+                compression,
             );
         },
     );
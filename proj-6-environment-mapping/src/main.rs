@@ -389,20 +389,20 @@ impl RendererDelgate for Delegate {
                     * self.camera.projected_space.m_screen_to_world,
                 position_world: self.camera.projected_space.position_world,
             };
-            self.model_space = ModelSpace {
-                m_model_to_projection: self.camera.projected_space.m_world_to_projection
-                    * self.m_model_to_world,
-                m_normal_to_world: self.m_model_to_world.into(),
-            };
-            self.mirror_plane_model_space = ModelSpace {
-                m_model_to_projection: self.camera.projected_space.m_world_to_projection
-                    * self.m_mirror_plane_model_to_world,
-                m_normal_to_world: self.m_mirror_plane_model_to_world.into(),
-            };
+            self.model_space = ModelSpace::from_model_to_world(
+                self.camera.projected_space.m_world_to_projection,
+                self.m_model_to_world,
+            );
+            self.mirror_plane_model_space = ModelSpace::from_model_to_world(
+                self.camera.projected_space.m_world_to_projection,
+                self.m_mirror_plane_model_to_world,
+            );
             self.mirror_model_space = ModelSpace {
                 m_model_to_projection: self.mirror_camera_space.m_world_to_projection
                     * self.m_model_to_world,
-                m_normal_to_world: (self.m_world_to_mirror_world * self.m_model_to_world).into(),
+                m_normal_to_world: (self.m_world_to_mirror_world * self.m_model_to_world)
+                    .normal_matrix()
+                    .into(),
             };
             self.needs_render = true;
         }
@@ -47,7 +47,7 @@ pub struct main_fragment_binds<'c> {
     pub camera: Bind<'c, ProjectedSpace>,
     pub light_pos: Bind<'c, float4>,
     pub m_env: Bind<'c, float3x3>,
-    pub darken: Bind<'c, float>,
+    pub darken: Bind<'c, f32>,
     pub env_texture: BindTexture<'c>,
 }
 impl Binds for main_fragment_binds<'_> {
@@ -45,7 +45,7 @@ half4 main_fragment(         VertexOut           in          [[stage_in]],
     const half3  normal     = half3(normalize(in.normal));
     const half3  ref        = half3x3(m_env) * reflect(camera_dir, normal);
 
-    constexpr sampler tx_sampler(mag_filter::linear, address::clamp_to_zero, min_filter::linear);
+    constexpr sampler tx_sampler(mag_filter::linear, address::clamp_to_zero, min_filter::linear, mip_filter::linear);
     const half4 color       = env_texture.sample(tx_sampler, float3(ref));
     return mix(
         shade_phong_blinn(
@@ -87,7 +87,7 @@ BGVertexOut bg_vertex(uint vertex_id [[vertex_id]]) {
 half4 bg_fragment(         BGVertexOut         in          [[stage_in]],
                   constant ProjectedSpace    & camera      [[buffer(0)]],
                            texturecube<half>   env_texture [[texture(0)]]) {
-    constexpr sampler tx_sampler(mag_filter::linear, address::clamp_to_zero, min_filter::linear);
+    constexpr sampler tx_sampler(mag_filter::linear, address::clamp_to_zero, min_filter::linear, mip_filter::linear);
     const float4 pos   = camera.m_screen_to_world * float4(in.position.xy, 1, 1);
     const half4  color = env_texture.sample(tx_sampler, pos.xyz);
     return color;
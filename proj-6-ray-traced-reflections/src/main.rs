@@ -195,14 +195,11 @@ impl RendererDelgate for Delegate {
                 0.,
             ),
             depth_texture: DepthTexture::new("Depth", DEFAULT_DEPTH_FORMAT),
-            model_space: ModelSpace {
-                m_model_to_projection: f32x4x4::identity().into(),
-                m_normal_to_world: m_model_to_world.into(),
-            },
-            mirror_plane_space: ModelSpace {
-                m_model_to_projection: f32x4x4::identity().into(),
-                m_normal_to_world: m_mirror_plane_model_to_world.into(),
-            },
+            model_space: ModelSpace::from_model_to_world(f32x4x4::identity(), m_model_to_world),
+            mirror_plane_space: ModelSpace::from_model_to_world(
+                f32x4x4::identity(),
+                m_mirror_plane_model_to_world,
+            ),
             needs_render: false,
             shading_mode,
             accel_struct,
@@ -69,51 +69,91 @@ impl ModelInstance {
     }
 
     fn on_camera_update(&mut self, camera_m_world_to_projection: f32x4x4) {
-        self.model_space = ModelSpace {
-            m_model_to_projection: (camera_m_world_to_projection * self.m_model_to_world),
-            m_normal_to_world: self.m_model_to_world.into(),
-        };
+        self.model_space =
+            ModelSpace::from_model_to_world(camera_m_world_to_projection, self.m_model_to_world);
     }
 }
 
+type ModelPipeline = RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>;
+type ModelPipelines = PipelinePermutations<
+    main_vertex,
+    main_fragment,
+    (ModelPipeline, function_table::IntersectionFunctionTable),
+>;
+
 struct Delegate {
     camera: Camera,
     command_queue: CommandQueue,
     depth_state: DepthStencilState,
     depth_texture: DepthTexture,
     device: Device,
-    library: Library,
     light: Camera,
     model_light: ModelInstance,
-    model_pipeline: RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)>,
+    model_pipelines: ModelPipelines,
     model_plane: ModelInstance,
     model: ModelInstance,
     model_as: ModelAccelerationStructure,
     needs_render: bool,
     shading_mode: ShadingModeSelector,
+    use_shadow_intersection: bool,
 }
 
-fn create_pipeline(
+// Each pipeline has its own intersection function table, as function handles are specific to a
+// pipeline.
+//
+// Every shading mode is precompiled with and without the shadow intersection function (toggled with
+// the "I" key), starting with the current `use_shadow_intersection`.
+fn create_pipelines(
     device: &Device,
     library: &Library,
-    shading_mode: ShadingModeSelector,
-) -> RenderPipeline<1, main_vertex, main_fragment, (Depth, NoStencil)> {
-    RenderPipeline::new(
-        "Model",
-        device,
-        library,
-        [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
-        main_vertex,
-        main_fragment {
-            HasAmbient: shading_mode.has_ambient(),
-            HasDiffuse: shading_mode.has_diffuse(),
-            OnlyNormals: shading_mode.only_normals(),
-            HasSpecular: shading_mode.has_specular(),
+    use_shadow_intersection: bool,
+) -> ModelPipelines {
+    let device = device.clone();
+    let library = library.clone();
+    PipelinePermutations::new_in_background(
+        [use_shadow_intersection, !use_shadow_intersection]
+            .into_iter()
+            .flat_map(|use_shadow_intersection| {
+                ShadingModeSelector::MODES.map(|mode| {
+                    (
+                        main_vertex,
+                        model_fragment_function(mode, use_shadow_intersection),
+                    )
+                })
+            }),
+        move |vertex_function, fragment_function| {
+            let shadow_intersection_fn = shadow_intersection.get_intersection_function(&library);
+            let pipeline = RenderPipeline::builder(
+                "Model",
+                &library,
+                [(DEFAULT_COLOR_FORMAT, BlendMode::NoBlend)],
+                vertex_function,
+                fragment_function,
+                (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
+            )
+            .fragment_linked_functions(&[&shadow_intersection_fn], &[])
+            .build(&device);
+            let intersection_fns =
+                pipeline.new_intersection_function_table(&[&shadow_intersection_fn]);
+            (pipeline, intersection_fns)
         },
-        (Depth(DEFAULT_DEPTH_FORMAT), NoStencil),
     )
 }
 
+#[inline]
+fn model_fragment_function(
+    shading_mode: ShadingModeSelector,
+    use_shadow_intersection: bool,
+) -> main_fragment {
+    main_fragment {
+        HasAmbient: shading_mode.has_ambient(),
+        HasDiffuse: shading_mode.has_diffuse(),
+        OnlyNormals: shading_mode.only_normals(),
+        HasSpecular: shading_mode.has_specular(),
+        UseShadowIntersection: use_shadow_intersection,
+    }
+}
+
 impl RendererDelgate for Delegate {
     fn new(device: Device) -> Self {
         let executable_name = std::env::args()
@@ -131,6 +171,7 @@ impl RendererDelgate for Delegate {
             .join("..")
             .join("common-assets");
         let shading_mode = ShadingModeSelector::DEFAULT;
+        let use_shadow_intersection = false;
         let command_queue = device.new_command_queue();
         let mut m_model_to_world = f32x4x4::identity();
         Self {
@@ -198,10 +239,10 @@ impl RendererDelgate for Delegate {
                 assets_dir.join("plane").join("plane.obj"),
                 |_| f32x4x4::translate(0., -plane_y, 0.),
             ),
-            model_pipeline: create_pipeline(&device, &library, shading_mode),
+            model_pipelines: create_pipelines(&device, &library, use_shadow_intersection),
             needs_render: true,
             shading_mode,
-            library,
+            use_shadow_intersection,
             device,
             command_queue,
         }
@@ -216,7 +257,11 @@ impl RendererDelgate for Delegate {
             .new_command_buffer_with_unretained_references();
         command_buffer.set_label("Renderer Command Buffer");
         let depth_tx = self.depth_texture.texture();
-        self.model_pipeline.new_pass(
+        let (model_pipeline, model_intersection_fns) = self.model_pipelines.get(
+            &main_vertex,
+            &model_fragment_function(self.shading_mode, self.use_shadow_intersection),
+        );
+        model_pipeline.new_pass(
             "Model, Plane, and Light",
             command_buffer,
             [(
@@ -241,6 +286,7 @@ impl RendererDelgate for Delegate {
                         camera: Bind::Value(&self.camera.projected_space),
                         light_pos: Bind::Value(&self.light.projected_space.position_world),
                         accel_struct: self.model_as.bind(),
+                        intersection_fns: BindIntersectionFunctionTable(model_intersection_fns),
                         ..Binds::SKIP
                     },
                 );
@@ -299,7 +345,14 @@ impl RendererDelgate for Delegate {
             self.needs_render = true;
         }
         if self.shading_mode.on_event(event) {
-            self.model_pipeline = create_pipeline(&self.device, &self.library, self.shading_mode);
+            self.needs_render = true;
+        }
+        if let UserEvent::KeyDown {
+            key_code: UserEvent::KEY_CODE_I,
+            ..
+        } = event
+        {
+            self.use_shadow_intersection = !self.use_shadow_intersection;
             self.needs_render = true;
         }
         if self.depth_texture.on_event(event, &self.device) {
@@ -38,6 +38,25 @@ impl metal_app::pipeline::function::Function for main_vertex {
     type Binds<'c> = main_vertex_binds<'c>;
 }
 impl PipelineFunction<VertexFunctionType> for main_vertex {}
+impl FunctionPermutations for main_vertex {
+    const PERMUTATIONS: usize = 1;
+    #[inline]
+    fn from_permutation_index(_index: usize) -> Self {
+        Self
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        0
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub struct shadow_intersection;
+impl metal_app::pipeline::function::Function for shadow_intersection {
+    const FUNCTION_NAME: &'static str = "shadow_intersection";
+    type Binds<'c> = NoBinds;
+}
+impl IntersectionFunction for shadow_intersection {}
 
 #[allow(non_camel_case_types)]
 pub struct main_fragment_binds<'c> {
@@ -45,6 +64,7 @@ pub struct main_fragment_binds<'c> {
     pub light_pos: Bind<'c, float4>,
     pub material: Bind<'c, Material>,
     pub accel_struct: BindAccelerationStructure<'c>,
+    pub intersection_fns: BindIntersectionFunctionTable<'c>,
 }
 impl Binds for main_fragment_binds<'_> {
     const SKIP: Self = Self {
@@ -52,6 +72,7 @@ impl Binds for main_fragment_binds<'_> {
         light_pos: Bind::Skip,
         material: Bind::Skip,
         accel_struct: BindAccelerationStructure::Skip,
+        intersection_fns: BindIntersectionFunctionTable::Skip,
     };
 
     #[inline(always)]
@@ -60,6 +81,7 @@ impl Binds for main_fragment_binds<'_> {
         self.light_pos.bind::<F>(encoder, 1);
         self.material.bind::<F>(encoder, 2);
         self.accel_struct.bind::<F>(encoder, 3);
+        self.intersection_fns.bind::<F>(encoder, 4);
     }
 }
 
@@ -69,6 +91,7 @@ pub struct main_fragment {
     pub HasDiffuse: bool,
     pub OnlyNormals: bool,
     pub HasSpecular: bool,
+    pub UseShadowIntersection: bool,
 }
 impl metal_app::pipeline::function::Function for main_fragment {
     const FUNCTION_NAME: &'static str = "main_fragment";
@@ -80,7 +103,29 @@ impl metal_app::pipeline::function::Function for main_fragment {
         fcv.set_constant_value_at_index((&self.HasDiffuse as *const _) as _, bool::MTL_DATA_TYPE, 1);
         fcv.set_constant_value_at_index((&self.OnlyNormals as *const _) as _, bool::MTL_DATA_TYPE, 2);
         fcv.set_constant_value_at_index((&self.HasSpecular as *const _) as _, bool::MTL_DATA_TYPE, 3);
+        fcv.set_constant_value_at_index((&self.UseShadowIntersection as *const _) as _, bool::MTL_DATA_TYPE, 5);
         Some(fcv)
     }
 }
 impl PipelineFunction<FragmentFunctionType> for main_fragment {}
+impl FunctionPermutations for main_fragment {
+    const PERMUTATIONS: usize = 32;
+    #[inline]
+    fn from_permutation_index(index: usize) -> Self {
+        Self {
+            HasAmbient: (index & 1) != 0,
+            HasDiffuse: (index & (1 << 1)) != 0,
+            OnlyNormals: (index & (1 << 2)) != 0,
+            HasSpecular: (index & (1 << 3)) != 0,
+            UseShadowIntersection: (index & (1 << 4)) != 0,
+        }
+    }
+    #[inline]
+    fn permutation_index(&self) -> usize {
+        (self.HasAmbient as usize)
+            | ((self.HasDiffuse as usize) << 1)
+            | ((self.OnlyNormals as usize) << 2)
+            | ((self.HasSpecular as usize) << 3)
+            | ((self.UseShadowIntersection as usize) << 4)
+    }
+}
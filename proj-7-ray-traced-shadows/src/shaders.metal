@@ -7,7 +7,14 @@
 #include "../../metal-types/src/shading-mode.h"
 
 using namespace metal;
+using raytracing::intersection_function_table;
 using raytracing::primitive_acceleration_structure;
+using raytracing::triangle_data;
+
+// Whether shadow rays call the custom intersection function (see `shadow_intersection`). Opt-in
+// (toggled with the "I" key), as every triangle is forced to be non-opaque to call it, which slows
+// down every shadow ray.
+constant constexpr bool UseShadowIntersection [[function_constant(5)]];
 
 struct VertexOut
 {
@@ -28,12 +35,22 @@ VertexOut main_vertex(         uint         vertex_id [[vertex_id]],
     };
 }
 
+// Custom intersection function used when tracing shadow rays (see `main_fragment`).
+// Rejects intersections very close to the ray origin, preventing a surface from shadowing itself.
+// This is also where alpha testing would go (ex. sample the material's alpha texture using the
+// triangle's barycentric coordinates).
+[[intersection(triangle, triangle_data)]]
+bool shadow_intersection(float distance [[distance]]) {
+    return distance > 0.0001;
+}
+
 [[fragment]]
 half4 main_fragment(         VertexOut                 in           [[stage_in]],
                     constant ProjectedSpace          & camera       [[buffer(0)]],
                     constant float4                  & light_pos    [[buffer(1)]],
                     constant Material                & material     [[buffer(2)]],
-                    primitive_acceleration_structure   accel_struct [[buffer(3)]])
+                    primitive_acceleration_structure   accel_struct [[buffer(3)]],
+                    intersection_function_table<triangle_data> intersection_fns [[buffer(4)]])
 {
     float4 pos = camera.m_screen_to_world * float4(in.position.xyz, 1);
            pos = pos / pos.w;
@@ -46,12 +63,16 @@ half4 main_fragment(         VertexOut                 in           [[stage_in]]
     bool is_shadow = false;
     if (dot(normal, to_light) >= 0.0) {
         raytracing::ray r(pos.xyz, to_light);
-        raytracing::intersector<> intersector;
+        raytracing::intersector<raytracing::triangle_data> intersector;
         // TODO: Figure out what there's a tiny little teapot shadow right behind the light when the
         // light is positioned right above the ground... weird.
         intersector.set_triangle_cull_mode(raytracing::triangle_cull_mode::back);
         intersector.assume_geometry_type(raytracing::geometry_type::triangle);
-        auto intersection = intersector.intersect(r, accel_struct);
+        if (UseShadowIntersection) {
+            // Force triangles to be non-opaque, so the custom intersection function is called.
+            intersector.force_opacity(raytracing::forced_opacity::non_opaque);
+        }
+        auto intersection = intersector.intersect(r, accel_struct, intersection_fns);
         is_shadow = intersection.type != raytracing::intersection_type::none;
     }
     return shade_phong_blinn(
@@ -1,3 +1,11 @@
+use std::path::PathBuf;
+
 fn main() {
     metal_build::build();
+    let common_assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("common-assets");
+    for mtl_file in ["teapot/teapot.mtl", "yoda/yoda.mtl"] {
+        metal_build::build_model_texture_assets(common_assets_dir.join(mtl_file));
+    }
 }
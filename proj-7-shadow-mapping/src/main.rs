@@ -68,10 +68,8 @@ impl ModelInstance {
     }
 
     fn on_camera_update(&mut self, camera_m_world_to_projection: f32x4x4) {
-        self.model_space = ModelSpace {
-            m_model_to_projection: (camera_m_world_to_projection * self.m_model_to_world),
-            m_normal_to_world: self.m_model_to_world.into(),
-        };
+        self.model_space =
+            ModelSpace::from_model_to_world(camera_m_world_to_projection, self.m_model_to_world);
     }
 }
 
@@ -344,11 +342,10 @@ impl RendererDelgate for Delegate {
                 * f32x4x4::scale(0.1, 0.1, 0.1, 1.0);
             self.model_light
                 .on_camera_update(self.camera.projected_space.m_world_to_projection);
-            self.model_shadow_space = ModelSpace {
-                m_model_to_projection: (self.light.projected_space.m_world_to_projection
-                    * self.model.m_model_to_world),
-                m_normal_to_world: self.model.m_model_to_world.into(),
-            };
+            self.model_shadow_space = ModelSpace::from_model_to_world(
+                self.light.projected_space.m_world_to_projection,
+                self.model.m_model_to_world,
+            );
             self.light_space = ProjectedSpace {
                 //
                 // IMPORTANT: Projecting to a Texture, NOT to the screen.
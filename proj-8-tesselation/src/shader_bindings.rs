@@ -16,7 +16,7 @@ use metal_app::{metal::*, metal_types::*, pipeline::*};
 #[allow(non_camel_case_types)]
 pub struct main_vertex_binds<'c> {
     pub m_world_to_projection: Bind<'c, float4x4>,
-    pub displacement_scale: Bind<'c, float>,
+    pub displacement_scale: Bind<'c, f32>,
     pub disp_tx: BindTexture<'c>,
 }
 impl Binds for main_vertex_binds<'_> {
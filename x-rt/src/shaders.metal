@@ -40,6 +40,8 @@ half4 main_fragment(
     inter.assume_geometry_type(geometry_type::triangle);
     auto hit = inter.intersect(r, accelerationStructure);
     if (hit.type == intersection_type::triangle) {
+        // `TriNormals` (2 x 32-bit), the smallest encoding, is the default used by
+        // `ModelAccelerationStructure::from_file()`.
         const auto p = (device TriNormals *) hit.primitive_data;
         return half4(p->normal(hit.triangle_barycentric_coord, &m_normal_to_worlds[hit.geometry_id]), 1);
     }